@@ -11,6 +11,7 @@
 //! - Animation LOD
 
 use glam::{Quat, Vec3};
+use odeza_core::time::{ClockId, TimeManager};
 use std::collections::HashMap;
 
 /// Animation clip
@@ -35,6 +36,60 @@ impl Default for AnimationClip {
     }
 }
 
+/// Playback position in a clip, driven by a chosen clock
+#[derive(Debug, Clone)]
+pub struct AnimationPlayer {
+    pub time: f32,
+    pub speed: f32,
+    pub clock: ClockId,
+    pub playing: bool,
+}
+
+impl Default for AnimationPlayer {
+    fn default() -> Self {
+        Self {
+            time: 0.0,
+            speed: 1.0,
+            clock: ClockId::GAME,
+            playing: true,
+        }
+    }
+}
+
+impl AnimationPlayer {
+    pub fn new(clock: ClockId) -> Self {
+        Self {
+            clock,
+            ..Self::default()
+        }
+    }
+
+    /// Advance by this frame's delta of the player's clock
+    ///
+    /// Returns true when a non-looping clip reaches its end.
+    pub fn advance(&mut self, clip: &AnimationClip, time: &TimeManager) -> bool {
+        self.advance_by(clip, time.clock_delta(self.clock) as f32)
+    }
+
+    pub fn advance_by(&mut self, clip: &AnimationClip, delta: f32) -> bool {
+        if !self.playing || clip.duration <= 0.0 {
+            return false;
+        }
+
+        self.time += delta * self.speed;
+        if clip.looping {
+            self.time = self.time.rem_euclid(clip.duration);
+            false
+        } else if self.time >= clip.duration {
+            self.time = clip.duration;
+            self.playing = false;
+            true
+        } else {
+            false
+        }
+    }
+}
+
 /// Animation track for a single bone
 #[derive(Debug, Clone)]
 pub struct AnimationTrack {
@@ -193,6 +248,24 @@ mod tests {
         assert_eq!(clip.sample_rate, 30.0);
     }
 
+    #[test]
+    fn test_animation_player_clock() {
+        let clip = AnimationClip {
+            duration: 1.0,
+            ..AnimationClip::default()
+        };
+        let mut time = TimeManager::new();
+        time.pause();
+        time.update(0.1);
+
+        let mut gameplay = AnimationPlayer::default();
+        let mut menu = AnimationPlayer::new(ClockId::UI);
+        gameplay.advance(&clip, &time);
+        menu.advance(&clip, &time);
+        assert_eq!(gameplay.time, 0.0);
+        assert!((menu.time - 0.1).abs() < 1e-6);
+    }
+
     #[test]
     fn test_two_bone_ik() {
         let ik = TwoBoneIK::new(Vec3::new(1.0, 0.0, 0.0));
@@ -7,6 +7,11 @@
 //! - Dependency graph tracking
 //! - Incremental cooking
 //! - Platform-specific asset compilation
+//! - Versioned scene files (.oscn) in text and binary form
+//! - World partition and cell-based level streaming
+
+pub mod scene;
+pub mod streaming;
 
 use std::collections::HashSet;
 use std::path::{Path, PathBuf};
@@ -33,11 +38,17 @@ pub enum AssetError {
     
     #[error("Serialization error: {0}")]
     SerializationError(String),
+    
+    #[error("Unsupported format version: {0}")]
+    UnsupportedVersion(u32),
 }
 
 /// Result type for asset operations
 pub type AssetResult<T> = Result<T, AssetError>;
 
+pub use scene::{SceneDocument, SceneLoader, SceneFormat, SceneNodeData, SceneComponent, SceneMigration, SCENE_FORMAT_VERSION};
+pub use streaming::{WorldPartition, StreamingManager, StreamingSource, StreamingConfig, StreamingEvent, CellCoord, CellState};
+
 /// Content-addressed asset ID (hash-based)
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct AssetId(pub u64);
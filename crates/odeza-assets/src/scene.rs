@@ -0,0 +1,427 @@
+//! Scene Files (.oscn)
+//!
+//! Versioned scene format capturing the scene graph hierarchy, node state and
+//! registered components:
+//! - Text form: pretty-printed JSON for diff-friendly source control
+//! - Binary form: bincode payload behind an `OSCN` header for cooked builds
+//! - Migration functions upgrade old text scenes to the current version
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use odeza_core::ecs::{Entity, World};
+use odeza_core::scene::{SceneGraph, Transform};
+use serde::{Deserialize, Serialize};
+
+use crate::{AssetError, AssetResult};
+
+/// Current scene format version
+pub const SCENE_FORMAT_VERSION: u32 = 1;
+
+/// Magic bytes at the start of a binary scene
+const BINARY_MAGIC: &[u8; 4] = b"OSCN";
+
+/// Upgrades a text scene from one version to the next
+pub type SceneMigration = fn(&mut serde_json::Value) -> Result<(), String>;
+
+/// Scene file encoding
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SceneFormat {
+    /// Human-readable JSON
+    Text,
+    /// Cooked bincode
+    Binary,
+}
+
+/// Serialized component on a scene node
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SceneComponent {
+    /// Name the component type is registered under
+    pub type_name: String,
+    /// Component value
+    #[serde(with = "component_value")]
+    pub data: serde_json::Value,
+}
+
+/// Serialized scene graph node
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SceneNodeData {
+    /// Node name
+    pub name: String,
+    /// Index of the parent node (always before this node)
+    pub parent: Option<u32>,
+    /// Local transform
+    pub transform: Transform,
+    /// Whether the node is visible
+    pub visible: bool,
+    /// Whether the node is enabled
+    pub enabled: bool,
+    /// Node tags
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Registered components on the node's entity
+    pub components: Vec<SceneComponent>,
+}
+
+/// Contents of a scene file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SceneDocument {
+    /// Format version
+    pub version: u32,
+    /// Nodes in depth-first order
+    pub nodes: Vec<SceneNodeData>,
+}
+
+impl Default for SceneDocument {
+    fn default() -> Self {
+        Self {
+            version: SCENE_FORMAT_VERSION,
+            nodes: Vec::new(),
+        }
+    }
+}
+
+impl SceneDocument {
+    /// Capture a scene graph and its registered components
+    ///
+    /// Components are read through the world's component registry; types
+    /// that are not registered are skipped.
+    pub fn capture(scene_graph: &SceneGraph, world: &World) -> AssetResult<Self> {
+        let mut document = Self::default();
+        let mut stack: Vec<(Entity, Option<u32>)> =
+            scene_graph.roots().iter().rev().map(|&root| (root, None)).collect();
+
+        while let Some((entity, parent)) = stack.pop() {
+            let Some(node) = scene_graph.get_node(entity) else {
+                continue;
+            };
+            let components = world
+                .component_registry()
+                .extract(world, entity)
+                .map_err(AssetError::SerializationError)?
+                .into_iter()
+                .map(|(type_name, data)| SceneComponent { type_name, data })
+                .collect();
+
+            let index = document.nodes.len() as u32;
+            document.nodes.push(SceneNodeData {
+                name: node.name.clone(),
+                parent,
+                transform: node.local_transform,
+                visible: node.visible,
+                enabled: node.enabled,
+                tags: node.tags.to_vec(),
+                components,
+            });
+            stack.extend(node.children.iter().rev().map(|&child| (child, Some(index))));
+        }
+
+        Ok(document)
+    }
+
+    /// Spawn the scene into a world and scene graph
+    ///
+    /// Returns the spawned entities in node order. On error, everything
+    /// spawned so far is removed again.
+    pub fn spawn(&self, world: &mut World, scene_graph: &mut SceneGraph) -> AssetResult<Vec<Entity>> {
+        let mut spawned: Vec<Entity> = Vec::with_capacity(self.nodes.len());
+        let result = self.spawn_nodes(world, scene_graph, &mut spawned);
+
+        if result.is_err() {
+            for &entity in &spawned {
+                scene_graph.remove_node(entity);
+                world.despawn(entity);
+            }
+        }
+        result.map(|_| spawned)
+    }
+
+    fn spawn_nodes(
+        &self,
+        world: &mut World,
+        scene_graph: &mut SceneGraph,
+        spawned: &mut Vec<Entity>,
+    ) -> AssetResult<()> {
+        for (index, data) in self.nodes.iter().enumerate() {
+            let parent = match data.parent {
+                Some(parent) if (parent as usize) < index => Some(spawned[parent as usize]),
+                Some(parent) => {
+                    return Err(AssetError::ImportFailed(format!(
+                        "Scene node '{}' has invalid parent index {}",
+                        data.name, parent
+                    )));
+                }
+                None => None,
+            };
+
+            let entity = world.spawn();
+            spawned.push(entity);
+            let node = scene_graph.add_node(entity, data.name.clone());
+            node.local_transform = data.transform;
+            node.visible = data.visible;
+            node.enabled = data.enabled;
+            node.tags = data.tags.iter().cloned().collect();
+            if parent.is_some() {
+                scene_graph.set_parent(entity, parent);
+            }
+
+            for component in &data.components {
+                world
+                    .add_serialized_component(entity, &component.type_name, &component.data)
+                    .map_err(|e| {
+                        AssetError::ImportFailed(format!("Scene node '{}': {}", data.name, e))
+                    })?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Encode as text
+    pub fn to_text(&self) -> AssetResult<String> {
+        serde_json::to_string_pretty(self).map_err(|e| AssetError::SerializationError(e.to_string()))
+    }
+
+    /// Encode as cooked binary
+    pub fn to_binary(&self) -> AssetResult<Vec<u8>> {
+        let mut bytes = BINARY_MAGIC.to_vec();
+        bytes.extend_from_slice(&self.version.to_le_bytes());
+        bincode::serialize_into(&mut bytes, self)
+            .map_err(|e| AssetError::SerializationError(e.to_string()))?;
+        Ok(bytes)
+    }
+
+    /// Encode in the given format
+    pub fn to_bytes(&self, format: SceneFormat) -> AssetResult<Vec<u8>> {
+        match format {
+            SceneFormat::Text => self.to_text().map(String::into_bytes),
+            SceneFormat::Binary => self.to_binary(),
+        }
+    }
+
+    /// Save to a file
+    pub fn save(&self, path: &Path, format: SceneFormat) -> AssetResult<()> {
+        std::fs::write(path, self.to_bytes(format)?)?;
+        Ok(())
+    }
+}
+
+/// Scene reader with version migrations
+#[derive(Clone)]
+pub struct SceneLoader {
+    /// Migrations keyed by the version they upgrade from
+    migrations: BTreeMap<u32, SceneMigration>,
+}
+
+impl SceneLoader {
+    /// Create a loader with no migrations registered
+    pub fn new() -> Self {
+        Self {
+            migrations: BTreeMap::new(),
+        }
+    }
+
+    /// Register a migration from `from_version` to `from_version + 1`
+    pub fn add_migration(&mut self, from_version: u32, migration: SceneMigration) {
+        self.migrations.insert(from_version, migration);
+    }
+
+    /// Decode a text scene, migrating it to the current version
+    pub fn from_text(&self, text: &str) -> AssetResult<SceneDocument> {
+        let mut value: serde_json::Value =
+            serde_json::from_str(text).map_err(|e| AssetError::SerializationError(e.to_string()))?;
+
+        let raw_version = value
+            .get("version")
+            .and_then(serde_json::Value::as_u64)
+            .ok_or_else(|| AssetError::SerializationError("Scene has no version".into()))?;
+        let mut version = u32::try_from(raw_version)
+            .map_err(|_| AssetError::ImportFailed(format!("Scene version {} is out of range", raw_version)))?;
+        if version > SCENE_FORMAT_VERSION {
+            return Err(AssetError::UnsupportedVersion(version));
+        }
+
+        while version < SCENE_FORMAT_VERSION {
+            let migration = self
+                .migrations
+                .get(&version)
+                .ok_or(AssetError::UnsupportedVersion(version))?;
+            migration(&mut value).map_err(|e| {
+                AssetError::ImportFailed(format!("Scene migration from version {} failed: {}", version, e))
+            })?;
+            version += 1;
+            value["version"] = version.into();
+        }
+
+        serde_json::from_value(value).map_err(|e| AssetError::SerializationError(e.to_string()))
+    }
+
+    /// Decode a cooked binary scene
+    ///
+    /// Cooked scenes are not migrated; recook them from the text source.
+    pub fn from_binary(&self, bytes: &[u8]) -> AssetResult<SceneDocument> {
+        if bytes.len() < 8 || &bytes[..4] != BINARY_MAGIC {
+            return Err(AssetError::SerializationError("Not a binary scene".into()));
+        }
+        let version = u32::from_le_bytes([bytes[4], bytes[5], bytes[6], bytes[7]]);
+        if version != SCENE_FORMAT_VERSION {
+            return Err(AssetError::UnsupportedVersion(version));
+        }
+        bincode::deserialize(&bytes[8..]).map_err(|e| AssetError::SerializationError(e.to_string()))
+    }
+
+    /// Decode a scene, detecting the format
+    pub fn from_bytes(&self, bytes: &[u8]) -> AssetResult<SceneDocument> {
+        if bytes.starts_with(BINARY_MAGIC) {
+            self.from_binary(bytes)
+        } else {
+            let text = std::str::from_utf8(bytes)
+                .map_err(|e| AssetError::SerializationError(e.to_string()))?;
+            self.from_text(text)
+        }
+    }
+
+    /// Load a scene file
+    pub fn load(&self, path: &Path) -> AssetResult<SceneDocument> {
+        self.from_bytes(&std::fs::read(path)?)
+    }
+
+    /// Load a scene file without blocking the calling task
+    pub async fn load_async(&self, path: &Path) -> AssetResult<SceneDocument> {
+        let bytes = tokio::fs::read(path).await?;
+        self.from_bytes(&bytes)
+    }
+}
+
+impl Default for SceneLoader {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Component values are JSON in text scenes and JSON strings in binary ones,
+/// since bincode cannot decode self-describing values
+mod component_value {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S: Serializer>(value: &serde_json::Value, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            value.serialize(serializer)
+        } else {
+            value.to_string().serialize(serializer)
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<serde_json::Value, D::Error> {
+        if deserializer.is_human_readable() {
+            serde_json::Value::deserialize(deserializer)
+        } else {
+            let text = String::deserialize(deserializer)?;
+            serde_json::from_str(&text).map_err(serde::de::Error::custom)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use odeza_core::math::Vec3;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Health {
+        current: f32,
+        max: f32,
+    }
+
+    fn test_world() -> (World, SceneGraph) {
+        let mut world = World::new();
+        world.component_registry_mut().register::<Health>("Health");
+        let mut sg = SceneGraph::new();
+
+        let root = world.spawn();
+        let child = world.spawn();
+        sg.add_node(root, "Root").local_transform = Transform::from_position(Vec3::new(1.0, 2.0, 3.0));
+        sg.add_node(child, "Child").visible = false;
+        sg.get_node_mut(child).unwrap().add_tag("Enemy");
+        sg.set_parent(child, Some(root));
+        world.add_component(child, Health { current: 50.0, max: 100.0 });
+        (world, sg)
+    }
+
+    #[test]
+    fn test_scene_round_trip() {
+        let (world, sg) = test_world();
+        let document = SceneDocument::capture(&sg, &world).unwrap();
+        assert_eq!(document.nodes.len(), 2);
+        assert_eq!(document.nodes[1].parent, Some(0));
+
+        let loader = SceneLoader::new();
+        for format in [SceneFormat::Text, SceneFormat::Binary] {
+            let bytes = document.to_bytes(format).unwrap();
+            let loaded = loader.from_bytes(&bytes).unwrap();
+            assert_eq!(loaded, document);
+
+            let mut world2 = World::new();
+            world2.component_registry_mut().register::<Health>("Health");
+            let mut sg2 = SceneGraph::new();
+            let entities = loaded.spawn(&mut world2, &mut sg2).unwrap();
+
+            let child = sg2.get_node(entities[1]).unwrap();
+            assert_eq!(child.name, "Child");
+            assert_eq!(child.parent, Some(entities[0]));
+            assert!(!child.visible);
+            assert!(child.has_tag("Enemy"));
+            assert_eq!(sg2.get_node(entities[0]).unwrap().local_transform.position, Vec3::new(1.0, 2.0, 3.0));
+            assert_eq!(world2.get_component::<Health>(entities[1]), Some(&Health { current: 50.0, max: 100.0 }));
+        }
+    }
+
+    #[test]
+    fn test_scene_migration() {
+        // Version 0 stored `hidden` instead of `visible`
+        let old = r#"{
+            "version": 0,
+            "nodes": [{
+                "name": "Root",
+                "parent": null,
+                "transform": { "position": [0, 0, 0], "rotation": [0, 0, 0, 1], "scale": [1, 1, 1] },
+                "hidden": true,
+                "enabled": true,
+                "components": []
+            }]
+        }"#;
+
+        let mut loader = SceneLoader::new();
+        assert!(matches!(loader.from_text(old), Err(AssetError::UnsupportedVersion(0))));
+
+        loader.add_migration(0, |scene| {
+            for node in scene["nodes"].as_array_mut().ok_or("nodes must be an array")? {
+                let node = node.as_object_mut().ok_or("node must be an object")?;
+                let hidden = node.remove("hidden").and_then(|h| h.as_bool()).unwrap_or(false);
+                node.insert("visible".into(), (!hidden).into());
+            }
+            Ok(())
+        });
+        let document = loader.from_text(old).unwrap();
+        assert_eq!(document.version, SCENE_FORMAT_VERSION);
+        assert!(!document.nodes[0].visible);
+
+        let future = r#"{ "version": 99, "nodes": [] }"#;
+        assert!(matches!(loader.from_text(future), Err(AssetError::UnsupportedVersion(99))));
+
+        // 2^32 + 1 must not wrap around to version 1
+        let huge = r#"{ "version": 4294967297, "nodes": [] }"#;
+        assert!(matches!(loader.from_text(huge), Err(AssetError::ImportFailed(_))));
+    }
+
+    #[test]
+    fn test_scene_spawn_rolls_back() {
+        let (world, sg) = test_world();
+        let document = SceneDocument::capture(&sg, &world).unwrap();
+
+        let mut world2 = World::new();
+        let mut sg2 = SceneGraph::new();
+        assert!(document.spawn(&mut world2, &mut sg2).is_err());
+        assert_eq!(world2.entity_count(), 0);
+        assert!(sg2.is_empty());
+    }
+}
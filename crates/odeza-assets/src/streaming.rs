@@ -0,0 +1,620 @@
+//! World Partition and Level Streaming
+//!
+//! Splits the world into a grid of cells, each stored as its own scene file:
+//! - Cells load around streaming sources (players, cameras) by distance
+//! - Unloading waits for an extra margin to avoid thrashing at cell borders
+//! - Nearer cells and higher-priority sources load first
+//! - Loads run asynchronously and stay within a memory budget
+
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::Arc;
+
+use ahash::AHashMap;
+use odeza_core::ecs::{Entity, World};
+use odeza_core::math::{Aabb, Vec3};
+use odeza_core::scene::SceneGraph;
+
+use crate::scene::{SceneDocument, SceneLoader};
+use crate::AssetResult;
+
+/// Grid coordinate of a streaming cell on the XZ plane
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct CellCoord {
+    pub x: i32,
+    pub z: i32,
+}
+
+impl CellCoord {
+    /// Create a cell coordinate
+    pub fn new(x: i32, z: i32) -> Self {
+        Self { x, z }
+    }
+}
+
+/// Streaming cell description
+#[derive(Debug, Clone)]
+pub struct StreamingCell {
+    /// Grid coordinate
+    pub coord: CellCoord,
+    /// Scene file holding the cell's contents
+    pub scene_path: PathBuf,
+    /// Estimated memory while loaded, in bytes
+    pub memory_estimate: usize,
+}
+
+/// World split into a grid of streaming cells
+#[derive(Debug, Clone)]
+pub struct WorldPartition {
+    /// Directory holding the cell scene files
+    root: PathBuf,
+    /// Cell edge length in world units
+    cell_size: f32,
+    cells: AHashMap<CellCoord, StreamingCell>,
+}
+
+impl WorldPartition {
+    /// Create an empty partition
+    pub fn new(root: impl Into<PathBuf>, cell_size: f32) -> Self {
+        Self {
+            root: root.into(),
+            cell_size,
+            cells: AHashMap::new(),
+        }
+    }
+
+    /// Get the directory holding the cell scene files
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Get the cell edge length
+    pub fn cell_size(&self) -> f32 {
+        self.cell_size
+    }
+
+    /// Get the default scene file path for a cell
+    pub fn cell_path(&self, coord: CellCoord) -> PathBuf {
+        self.root.join(format!("cell_{}_{}.oscn", coord.x, coord.z))
+    }
+
+    /// Add a cell stored at the default path
+    pub fn add_cell(&mut self, coord: CellCoord, memory_estimate: usize) {
+        let scene_path = self.cell_path(coord);
+        self.insert_cell(StreamingCell {
+            coord,
+            scene_path,
+            memory_estimate,
+        });
+    }
+
+    /// Add or replace a cell
+    pub fn insert_cell(&mut self, cell: StreamingCell) {
+        self.cells.insert(cell.coord, cell);
+    }
+
+    /// Get a cell
+    pub fn cell(&self, coord: CellCoord) -> Option<&StreamingCell> {
+        self.cells.get(&coord)
+    }
+
+    /// Iterate all cells
+    pub fn cells(&self) -> impl Iterator<Item = &StreamingCell> {
+        self.cells.values()
+    }
+
+    /// Get the number of cells
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    /// Check if the partition has no cells
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// Get the cell containing a position
+    pub fn cell_at(&self, position: Vec3) -> CellCoord {
+        CellCoord::new(
+            (position.x / self.cell_size).floor() as i32,
+            (position.z / self.cell_size).floor() as i32,
+        )
+    }
+
+    /// Get a cell's bounds (unbounded vertically)
+    pub fn cell_bounds(&self, coord: CellCoord) -> Aabb {
+        let min = Vec3::new(coord.x as f32 * self.cell_size, f32::MIN, coord.z as f32 * self.cell_size);
+        let max = Vec3::new(min.x + self.cell_size, f32::MAX, min.z + self.cell_size);
+        Aabb::new(min, max)
+    }
+
+    /// Horizontal distance from a position to the nearest point of a cell
+    pub fn distance_to_cell(&self, coord: CellCoord, position: Vec3) -> f32 {
+        let bounds = self.cell_bounds(coord);
+        let dx = (bounds.min.x - position.x).max(position.x - bounds.max.x).max(0.0);
+        let dz = (bounds.min.z - position.z).max(position.z - bounds.max.z).max(0.0);
+        (dx * dx + dz * dz).sqrt()
+    }
+
+    /// Collect existing cells within a horizontal radius of a position
+    pub fn cells_within(&self, position: Vec3, radius: f32, results: &mut Vec<CellCoord>) {
+        let min = self.cell_at(position - Vec3::splat(radius));
+        let max = self.cell_at(position + Vec3::splat(radius));
+        for x in min.x..=max.x {
+            for z in min.z..=max.z {
+                let coord = CellCoord::new(x, z);
+                if self.cells.contains_key(&coord) && self.distance_to_cell(coord, position) <= radius {
+                    results.push(coord);
+                }
+            }
+        }
+    }
+}
+
+/// Point the world streams in around
+#[derive(Debug, Clone, Copy)]
+pub struct StreamingSource {
+    /// World position
+    pub position: Vec3,
+    /// Cells within this distance are loaded
+    pub load_radius: f32,
+    /// Higher priority sources get their cells loaded first
+    pub priority: f32,
+}
+
+impl StreamingSource {
+    /// Create a source with priority 1
+    pub fn new(position: Vec3, load_radius: f32) -> Self {
+        Self {
+            position,
+            load_radius,
+            priority: 1.0,
+        }
+    }
+}
+
+/// Identifier of a registered streaming source
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct StreamingSourceId(u32);
+
+/// Streaming configuration
+#[derive(Debug, Clone)]
+pub struct StreamingConfig {
+    /// Extra distance beyond a source's load radius before cells unload
+    pub unload_margin: f32,
+    /// Maximum estimated memory of loaded and loading cells, in bytes
+    pub memory_budget: usize,
+    /// Maximum number of cells loading at once
+    pub max_concurrent_loads: usize,
+}
+
+impl Default for StreamingConfig {
+    fn default() -> Self {
+        Self {
+            unload_margin: 32.0,
+            memory_budget: 256 * 1024 * 1024, // 256 MB default
+            max_concurrent_loads: 2,
+        }
+    }
+}
+
+/// Change reported by [`StreamingManager::update`]
+#[derive(Debug, Clone, PartialEq)]
+pub enum StreamingEvent {
+    /// A cell finished loading and its entities were spawned
+    Loaded { coord: CellCoord, entities: usize },
+    /// A cell was unloaded and its entities despawned
+    Unloaded(CellCoord),
+    /// A cell failed to load
+    Failed { coord: CellCoord, error: String },
+}
+
+/// Streaming state of a cell
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CellState {
+    /// Load requested, waiting for the scene file
+    Loading,
+    /// Loaded and spawned
+    Loaded,
+}
+
+/// Resident or loading cell
+struct ResidentCell {
+    state: CellState,
+    memory: usize,
+    entities: Vec<Entity>,
+    /// Incremented per load request so that cancelled loads are ignored
+    request: u64,
+}
+
+/// Result of a background cell load
+type LoadResult = (CellCoord, u64, AssetResult<SceneDocument>);
+
+/// Loads and unloads world partition cells around streaming sources
+pub struct StreamingManager {
+    partition: WorldPartition,
+    config: StreamingConfig,
+    loader: Arc<SceneLoader>,
+    runtime: tokio::runtime::Handle,
+    sources: Vec<(StreamingSourceId, StreamingSource)>,
+    next_source_id: u32,
+    cells: AHashMap<CellCoord, ResidentCell>,
+    next_request: u64,
+    completed_tx: Sender<LoadResult>,
+    completed_rx: Receiver<LoadResult>,
+}
+
+impl StreamingManager {
+    /// Create a manager that loads cells on a tokio runtime
+    pub fn new(
+        partition: WorldPartition,
+        config: StreamingConfig,
+        loader: SceneLoader,
+        runtime: tokio::runtime::Handle,
+    ) -> Self {
+        let (completed_tx, completed_rx) = mpsc::channel();
+        Self {
+            partition,
+            config,
+            loader: Arc::new(loader),
+            runtime,
+            sources: Vec::new(),
+            next_source_id: 0,
+            cells: AHashMap::new(),
+            next_request: 0,
+            completed_tx,
+            completed_rx,
+        }
+    }
+
+    /// Get the world partition
+    pub fn partition(&self) -> &WorldPartition {
+        &self.partition
+    }
+
+    /// Get the configuration
+    pub fn config(&self) -> &StreamingConfig {
+        &self.config
+    }
+
+    /// Get the configuration for modification
+    pub fn config_mut(&mut self) -> &mut StreamingConfig {
+        &mut self.config
+    }
+
+    /// Add a streaming source
+    pub fn add_source(&mut self, source: StreamingSource) -> StreamingSourceId {
+        let id = StreamingSourceId(self.next_source_id);
+        self.next_source_id += 1;
+        self.sources.push((id, source));
+        id
+    }
+
+    /// Get a streaming source for modification (e.g. to move it)
+    pub fn source_mut(&mut self, id: StreamingSourceId) -> Option<&mut StreamingSource> {
+        self.sources.iter_mut().find(|(source_id, _)| *source_id == id).map(|(_, source)| source)
+    }
+
+    /// Remove a streaming source
+    pub fn remove_source(&mut self, id: StreamingSourceId) -> bool {
+        let before = self.sources.len();
+        self.sources.retain(|(source_id, _)| *source_id != id);
+        self.sources.len() != before
+    }
+
+    /// Get a cell's streaming state (`None` when unloaded)
+    pub fn cell_state(&self, coord: CellCoord) -> Option<CellState> {
+        self.cells.get(&coord).map(|cell| cell.state)
+    }
+
+    /// Get the entities spawned for a loaded cell
+    pub fn cell_entities(&self, coord: CellCoord) -> &[Entity] {
+        self.cells.get(&coord).map_or(&[], |cell| &cell.entities)
+    }
+
+    /// Get the estimated memory of loaded and loading cells
+    pub fn memory_used(&self) -> usize {
+        self.cells.values().map(|cell| cell.memory).sum()
+    }
+
+    /// Get the number of loads in flight
+    pub fn pending_loads(&self) -> usize {
+        self.cells.values().filter(|cell| cell.state == CellState::Loading).count()
+    }
+
+    /// Stream cells in and out around the current sources
+    ///
+    /// Spawns cells whose loads completed, unloads cells that moved out of
+    /// range and starts new loads nearest-first within the memory budget.
+    pub fn update(&mut self, world: &mut World, scene_graph: &mut SceneGraph) -> Vec<StreamingEvent> {
+        let mut events = Vec::new();
+        self.finish_loads(world, scene_graph, &mut events);
+
+        // Unload cells outside every source's range plus margin
+        let out_of_range: Vec<CellCoord> = self
+            .cells
+            .keys()
+            .copied()
+            .filter(|&coord| self.score(coord, self.config.unload_margin).is_none())
+            .collect();
+        for coord in out_of_range {
+            self.unload(coord, world, scene_graph, &mut events);
+        }
+
+        // Cells wanted by some source, best first
+        let mut wanted: Vec<(f32, CellCoord)> = Vec::new();
+        let mut in_range = Vec::new();
+        for (_, source) in &self.sources {
+            in_range.clear();
+            self.partition.cells_within(source.position, source.load_radius, &mut in_range);
+            for &coord in &in_range {
+                if !self.cells.contains_key(&coord)
+                    && !wanted.iter().any(|&(_, c)| c == coord)
+                    && let Some(score) = self.score(coord, 0.0)
+                {
+                    wanted.push((score, coord));
+                }
+            }
+        }
+        wanted.sort_by(|a, b| a.0.total_cmp(&b.0).then(a.1.cmp(&b.1)));
+
+        for (score, coord) in wanted {
+            if self.pending_loads() >= self.config.max_concurrent_loads {
+                break;
+            }
+            let memory = self.partition.cells[&coord].memory_estimate;
+            if !self.make_room(memory, score, world, scene_graph, &mut events) {
+                break;
+            }
+            self.start_load(coord, memory);
+        }
+
+        events
+    }
+
+    /// Unload every cell and cancel pending loads
+    pub fn unload_all(&mut self, world: &mut World, scene_graph: &mut SceneGraph) -> Vec<StreamingEvent> {
+        let mut events = Vec::new();
+        let coords: Vec<CellCoord> = self.cells.keys().copied().collect();
+        for coord in coords {
+            self.unload(coord, world, scene_graph, &mut events);
+        }
+        events
+    }
+
+    /// Priority score of a cell (lower loads first), or `None` if out of range
+    ///
+    /// `margin` extends every source's load radius.
+    fn score(&self, coord: CellCoord, margin: f32) -> Option<f32> {
+        self.sources
+            .iter()
+            .filter_map(|(_, source)| {
+                let distance = self.partition.distance_to_cell(coord, source.position);
+                (distance <= source.load_radius + margin).then(|| distance / source.priority.max(f32::EPSILON))
+            })
+            .min_by(f32::total_cmp)
+    }
+
+    /// Free budget for a new cell by evicting lower-priority cells in the unload margin
+    fn make_room(
+        &mut self,
+        memory: usize,
+        score: f32,
+        world: &mut World,
+        scene_graph: &mut SceneGraph,
+        events: &mut Vec<StreamingEvent>,
+    ) -> bool {
+        loop {
+            if self.memory_used() + memory <= self.config.memory_budget {
+                return true;
+            }
+            // Only cells no source strictly needs may be evicted
+            let victim = self
+                .cells
+                .keys()
+                .filter(|&&coord| self.score(coord, 0.0).is_none())
+                .filter_map(|&coord| self.score(coord, self.config.unload_margin).map(|s| (s, coord)))
+                .filter(|&(victim_score, _)| victim_score > score)
+                .max_by(|a, b| a.0.total_cmp(&b.0));
+            match victim {
+                Some((_, coord)) => self.unload(coord, world, scene_graph, events),
+                None => return false,
+            }
+        }
+    }
+
+    fn start_load(&mut self, coord: CellCoord, memory: usize) {
+        let request = self.next_request;
+        self.next_request += 1;
+        self.cells.insert(
+            coord,
+            ResidentCell {
+                state: CellState::Loading,
+                memory,
+                entities: Vec::new(),
+                request,
+            },
+        );
+
+        let path = self.partition.cells[&coord].scene_path.clone();
+        let loader = Arc::clone(&self.loader);
+        let completed = self.completed_tx.clone();
+        self.runtime.spawn(async move {
+            let result = loader.load_async(&path).await;
+            // The manager may have been dropped
+            let _ = completed.send((coord, request, result));
+        });
+    }
+
+    fn finish_loads(&mut self, world: &mut World, scene_graph: &mut SceneGraph, events: &mut Vec<StreamingEvent>) {
+        while let Ok((coord, request, result)) = self.completed_rx.try_recv() {
+            // Ignore loads cancelled or superseded since they were requested
+            let current = self
+                .cells
+                .get(&coord)
+                .is_some_and(|cell| cell.state == CellState::Loading && cell.request == request);
+            if !current {
+                continue;
+            }
+
+            match result.and_then(|document| document.spawn(world, scene_graph)) {
+                Ok(entities) => {
+                    events.push(StreamingEvent::Loaded {
+                        coord,
+                        entities: entities.len(),
+                    });
+                    let cell = self.cells.get_mut(&coord).unwrap();
+                    cell.state = CellState::Loaded;
+                    cell.entities = entities;
+                }
+                Err(error) => {
+                    log::warn!("Failed to stream cell ({}, {}): {}", coord.x, coord.z, error);
+                    self.cells.remove(&coord);
+                    events.push(StreamingEvent::Failed {
+                        coord,
+                        error: error.to_string(),
+                    });
+                }
+            }
+        }
+    }
+
+    fn unload(
+        &mut self,
+        coord: CellCoord,
+        world: &mut World,
+        scene_graph: &mut SceneGraph,
+        events: &mut Vec<StreamingEvent>,
+    ) {
+        let Some(cell) = self.cells.remove(&coord) else {
+            return;
+        };
+        if cell.state == CellState::Loaded {
+            for entity in cell.entities {
+                scene_graph.remove_node(entity);
+                world.despawn(entity);
+            }
+            events.push(StreamingEvent::Unloaded(coord));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scene::{SceneFormat, SceneNodeData};
+    use odeza_core::scene::Transform;
+    use std::time::{Duration, Instant};
+
+    /// Write a one-node scene for each cell in a 4x1 strip
+    fn test_partition(name: &str) -> WorldPartition {
+        let root = std::env::temp_dir().join(format!("odeza_streaming_{}_{}", name, std::process::id()));
+        std::fs::create_dir_all(&root).unwrap();
+        let mut partition = WorldPartition::new(&root, 100.0);
+        for x in 0..4 {
+            let coord = CellCoord::new(x, 0);
+            partition.add_cell(coord, 1000);
+            let document = SceneDocument {
+                nodes: vec![SceneNodeData {
+                    name: format!("Cell{}", x),
+                    parent: None,
+                    transform: Transform::IDENTITY,
+                    visible: true,
+                    enabled: true,
+                    tags: Vec::new(),
+                    components: Vec::new(),
+                }],
+                ..Default::default()
+            };
+            document.save(&partition.cell_path(coord), SceneFormat::Binary).unwrap();
+        }
+        partition
+    }
+
+    /// Update until no loads are pending
+    fn settle(manager: &mut StreamingManager, world: &mut World, sg: &mut SceneGraph) -> Vec<StreamingEvent> {
+        let deadline = Instant::now() + Duration::from_secs(5);
+        let mut events = manager.update(world, sg);
+        while manager.pending_loads() > 0 && Instant::now() < deadline {
+            std::thread::sleep(Duration::from_millis(1));
+            events.extend(manager.update(world, sg));
+        }
+        events
+    }
+
+    #[test]
+    fn test_partition_cells() {
+        let partition = test_partition("cells");
+        assert_eq!(partition.cell_at(Vec3::new(150.0, 0.0, 50.0)), CellCoord::new(1, 0));
+        assert_eq!(partition.cell_at(Vec3::new(-1.0, 0.0, 0.0)), CellCoord::new(-1, 0));
+        assert_eq!(partition.distance_to_cell(CellCoord::new(2, 0), Vec3::new(150.0, 0.0, 50.0)), 50.0);
+
+        let mut cells = Vec::new();
+        partition.cells_within(Vec3::new(150.0, 0.0, 50.0), 60.0, &mut cells);
+        cells.sort();
+        assert_eq!(cells, [CellCoord::new(0, 0), CellCoord::new(1, 0), CellCoord::new(2, 0)]);
+        std::fs::remove_dir_all(partition.root()).unwrap();
+    }
+
+    #[test]
+    fn test_streaming_loads_and_unloads_with_hysteresis() {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let partition = test_partition("hysteresis");
+        let root = partition.root().to_path_buf();
+        let config = StreamingConfig {
+            unload_margin: 50.0,
+            ..Default::default()
+        };
+        let mut manager = StreamingManager::new(partition, config, SceneLoader::new(), runtime.handle().clone());
+        let mut world = World::new();
+        let mut sg = SceneGraph::new();
+
+        let source = manager.add_source(StreamingSource::new(Vec3::new(50.0, 0.0, 50.0), 60.0));
+        let events = settle(&mut manager, &mut world, &mut sg);
+        assert_eq!(events.len(), 2);
+        assert_eq!(manager.cell_state(CellCoord::new(0, 0)), Some(CellState::Loaded));
+        assert_eq!(manager.cell_state(CellCoord::new(1, 0)), Some(CellState::Loaded));
+        assert_eq!(manager.cell_state(CellCoord::new(2, 0)), None);
+        assert_eq!(sg.node_count(), 2);
+
+        // Cell 0 is 80 away: beyond the load radius but inside the margin
+        manager.source_mut(source).unwrap().position.x = 180.0;
+        settle(&mut manager, &mut world, &mut sg);
+        assert_eq!(manager.cell_state(CellCoord::new(0, 0)), Some(CellState::Loaded));
+        assert_eq!(manager.cell_state(CellCoord::new(2, 0)), Some(CellState::Loaded));
+
+        manager.source_mut(source).unwrap().position.x = 250.0;
+        let events = settle(&mut manager, &mut world, &mut sg);
+        assert!(events.contains(&StreamingEvent::Unloaded(CellCoord::new(0, 0))));
+        assert_eq!(manager.cell_entities(CellCoord::new(0, 0)), &[]);
+
+        manager.unload_all(&mut world, &mut sg);
+        assert!(sg.is_empty());
+        assert_eq!(world.entity_count(), 0);
+        std::fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn test_streaming_memory_budget_and_priority() {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let partition = test_partition("budget");
+        let root = partition.root().to_path_buf();
+        let config = StreamingConfig {
+            memory_budget: 2000,
+            max_concurrent_loads: 4,
+            ..Default::default()
+        };
+        let mut manager = StreamingManager::new(partition, config, SceneLoader::new(), runtime.handle().clone());
+        let mut world = World::new();
+        let mut sg = SceneGraph::new();
+
+        // Covers every cell, but only the two nearest fit the budget
+        manager.add_source(StreamingSource::new(Vec3::new(350.0, 0.0, 50.0), 400.0));
+        settle(&mut manager, &mut world, &mut sg);
+        assert_eq!(manager.memory_used(), 2000);
+        assert_eq!(manager.cell_state(CellCoord::new(3, 0)), Some(CellState::Loaded));
+        assert_eq!(manager.cell_state(CellCoord::new(2, 0)), Some(CellState::Loaded));
+        assert_eq!(manager.cell_state(CellCoord::new(0, 0)), None);
+        std::fs::remove_dir_all(root).unwrap();
+    }
+}
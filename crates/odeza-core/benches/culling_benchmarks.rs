@@ -0,0 +1,47 @@
+//! Culling Benchmarks
+//!
+//! Per-object frustum tests against the SoA batch paths
+
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, Criterion, BenchmarkId};
+use odeza_core::math::{Aabb, AabbSoa, Frustum, Mat4, Vec3};
+
+/// Boxes on a grid in front of and around the camera
+fn build_boxes(count: usize) -> Vec<Aabb> {
+    let side = (count as f32).sqrt().ceil() as usize;
+    (0..count)
+        .map(|i| {
+            let x = (i % side) as f32 * 4.0 - side as f32 * 2.0;
+            let z = (i / side) as f32 * 4.0 - side as f32 * 2.0;
+            Aabb::from_center_half_extents(Vec3::new(x, 0.0, z), Vec3::splat(1.0))
+        })
+        .collect()
+}
+
+fn bench_frustum_culling(c: &mut Criterion) {
+    let view = Mat4::look_at_rh(Vec3::new(0.0, 20.0, 0.0), Vec3::new(0.0, 0.0, -100.0), Vec3::Y);
+    let proj = Mat4::perspective_rh(60f32.to_radians(), 16.0 / 9.0, 0.1, 500.0);
+    let frustum = Frustum::from_matrix(proj * view);
+
+    let mut group = c.benchmark_group("frustum_culling");
+    for count in [1_000, 20_000] {
+        let boxes = build_boxes(count);
+        let soa: AabbSoa = boxes.iter().copied().collect();
+        let mut mask = Vec::new();
+
+        group.bench_with_input(BenchmarkId::new("per_object", count), &boxes, |b, boxes| {
+            b.iter(|| boxes.iter().filter(|aabb| frustum.intersects_aabb(black_box(aabb))).count())
+        });
+        group.bench_with_input(BenchmarkId::new("batch_scalar", count), &soa, |b, soa| {
+            b.iter(|| frustum.cull_aabbs_scalar(black_box(soa), &mut mask))
+        });
+        group.bench_with_input(BenchmarkId::new("batch_simd", count), &soa, |b, soa| {
+            b.iter(|| frustum.cull_aabbs(black_box(soa), &mut mask))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_frustum_culling);
+criterion_main!(benches);
@@ -0,0 +1,59 @@
+//! Scene Graph Benchmarks
+//!
+//! Performance benchmarks for transform propagation
+
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, Criterion, BenchmarkId};
+use odeza_core::ecs::Entity;
+use odeza_core::math::Vec3;
+use odeza_core::scene::SceneGraph;
+
+/// Build roots with chains of children, `count` nodes in total
+fn build_scene(count: u32) -> SceneGraph {
+    let mut sg = SceneGraph::new();
+    let mut parent = None;
+    for i in 0..count {
+        let entity = Entity::new(i, 0);
+        sg.add_node(entity, "Node").local_transform.position = Vec3::X;
+        if i % 10 != 0 {
+            sg.set_parent(entity, parent);
+        }
+        parent = Some(entity);
+    }
+    sg.update_transforms();
+    sg
+}
+
+fn bench_update_transforms(c: &mut Criterion) {
+    let mut group = c.benchmark_group("update_transforms");
+    
+    for count in [1000, 10000, 50000].iter() {
+        group.bench_with_input(BenchmarkId::new("few_movers", count), count, |b, &count| {
+            let mut sg = build_scene(count);
+            b.iter(|| {
+                for i in (0..count).step_by(1000) {
+                    sg.get_node_mut(Entity::new(i, 0)).unwrap().local_transform.position.y += 0.1;
+                }
+                sg.update_transforms();
+                black_box(&sg);
+            });
+        });
+        
+        group.bench_with_input(BenchmarkId::new("all_dirty", count), count, |b, &count| {
+            let mut sg = build_scene(count);
+            b.iter(|| {
+                for i in (0..count).step_by(10) {
+                    sg.mark_dirty(Entity::new(i, 0));
+                }
+                sg.update_transforms();
+                black_box(&sg);
+            });
+        });
+    }
+    
+    group.finish();
+}
+
+criterion_group!(benches, bench_update_transforms);
+criterion_main!(benches);
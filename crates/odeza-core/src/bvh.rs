@@ -0,0 +1,622 @@
+//! Dynamic Bounding Volume Hierarchy
+//!
+//! Incrementally updated AABB tree for spatial queries:
+//! - Insert, remove and refit without rebuilding
+//! - Fat AABBs so small movements don't touch the tree
+//! - Tree rotations keep it balanced
+//! - Queries against AABBs, spheres, frustums and rays
+//! - Sync with scene graph world transforms
+
+use std::collections::HashMap;
+
+use smallvec::SmallVec;
+
+use crate::ecs::Entity;
+use crate::math::{Aabb, BoundingSphere, Frustum, Ray};
+use crate::scene::SceneGraph;
+
+/// Marks a missing node link
+const NULL: u32 = u32::MAX;
+
+/// Default fat AABB margin
+pub const DEFAULT_MARGIN: f32 = 0.1;
+
+/// Tree node
+#[derive(Debug, Clone)]
+struct BvhNode {
+    /// Bounds of the subtree (fat bounds for leaves)
+    aabb: Aabb,
+    /// Tight bounds (leaves only)
+    tight: Aabb,
+    parent: u32,
+    left: u32,
+    right: u32,
+    /// Leaf height is 0; free nodes are -1
+    height: i32,
+    entity: Entity,
+}
+
+impl BvhNode {
+    fn is_leaf(&self) -> bool {
+        self.left == NULL
+    }
+}
+
+/// Dynamic AABB tree keyed by entity
+pub struct DynamicBvh {
+    nodes: Vec<BvhNode>,
+    free_nodes: Vec<u32>,
+    root: u32,
+    leaves: HashMap<Entity, u32>,
+    /// Local bounds of entities that follow scene graph nodes
+    tracked: HashMap<Entity, Aabb>,
+    margin: f32,
+}
+
+impl DynamicBvh {
+    /// Create an empty tree with the default margin
+    pub fn new() -> Self {
+        Self::with_margin(DEFAULT_MARGIN)
+    }
+
+    /// Create an empty tree with a fat AABB margin
+    pub fn with_margin(margin: f32) -> Self {
+        Self {
+            nodes: Vec::new(),
+            free_nodes: Vec::new(),
+            root: NULL,
+            leaves: HashMap::new(),
+            tracked: HashMap::new(),
+            margin,
+        }
+    }
+
+    /// Get the fat AABB margin
+    pub fn margin(&self) -> f32 {
+        self.margin
+    }
+
+    /// Get the number of entities in the tree
+    pub fn len(&self) -> usize {
+        self.leaves.len()
+    }
+
+    /// Check if the tree is empty
+    pub fn is_empty(&self) -> bool {
+        self.leaves.is_empty()
+    }
+
+    /// Check if an entity is in the tree
+    pub fn contains(&self, entity: Entity) -> bool {
+        self.leaves.contains_key(&entity)
+    }
+
+    /// Get an entity's bounds
+    pub fn bounds(&self, entity: Entity) -> Option<Aabb> {
+        self.leaves.get(&entity).map(|&leaf| self.nodes[leaf as usize].tight)
+    }
+
+    /// Get an entity's fat bounds
+    pub fn fat_bounds(&self, entity: Entity) -> Option<Aabb> {
+        self.leaves.get(&entity).map(|&leaf| self.nodes[leaf as usize].aabb)
+    }
+
+    /// Get the height of the tree (0 for a single leaf)
+    pub fn height(&self) -> i32 {
+        if self.root == NULL {
+            0
+        } else {
+            self.nodes[self.root as usize].height
+        }
+    }
+
+    /// Insert an entity, replacing its bounds if already present
+    pub fn insert(&mut self, entity: Entity, aabb: Aabb) {
+        if self.leaves.contains_key(&entity) {
+            self.update(entity, aabb);
+            return;
+        }
+        let leaf = self.allocate_node();
+        let node = &mut self.nodes[leaf as usize];
+        node.aabb = aabb.expanded(self.margin);
+        node.tight = aabb;
+        node.entity = entity;
+        node.height = 0;
+        self.leaves.insert(entity, leaf);
+        self.insert_leaf(leaf);
+    }
+
+    /// Remove an entity
+    pub fn remove(&mut self, entity: Entity) -> bool {
+        let Some(leaf) = self.leaves.remove(&entity) else {
+            return false;
+        };
+        self.tracked.remove(&entity);
+        self.remove_leaf(leaf);
+        self.free_node(leaf);
+        true
+    }
+
+    /// Update an entity's bounds
+    ///
+    /// Returns true if the entity left its fat bounds and was reinserted.
+    pub fn update(&mut self, entity: Entity, aabb: Aabb) -> bool {
+        let Some(&leaf) = self.leaves.get(&entity) else {
+            return false;
+        };
+        self.nodes[leaf as usize].tight = aabb;
+        if self.nodes[leaf as usize].aabb.contains_aabb(&aabb) {
+            return false;
+        }
+
+        self.remove_leaf(leaf);
+        self.nodes[leaf as usize].aabb = aabb.expanded(self.margin);
+        self.insert_leaf(leaf);
+        true
+    }
+
+    /// Remove every entity
+    pub fn clear(&mut self) {
+        self.nodes.clear();
+        self.free_nodes.clear();
+        self.leaves.clear();
+        self.tracked.clear();
+        self.root = NULL;
+    }
+
+    /// Insert a scene node with bounds in its local space
+    ///
+    /// The node's world bounds follow its world transform through
+    /// [`DynamicBvh::sync_with_scene`]. Returns false if the node is missing.
+    pub fn track_node(&mut self, scene_graph: &SceneGraph, entity: Entity, local_bounds: Aabb) -> bool {
+        let Some(world_matrix) = scene_graph.compute_world_matrix(entity) else {
+            return false;
+        };
+        self.tracked.insert(entity, local_bounds);
+        self.insert(entity, local_bounds.transform(world_matrix));
+        true
+    }
+
+    /// Refit tracked nodes moved by the last [`SceneGraph::update_transforms`]
+    ///
+    /// Tracked nodes removed from the scene before that update are removed.
+    pub fn sync_with_scene(&mut self, scene_graph: &SceneGraph) {
+        for &entity in scene_graph.removed_nodes() {
+            if self.tracked.contains_key(&entity) && scene_graph.get_node(entity).is_none() {
+                self.remove(entity);
+            }
+        }
+        for &entity in scene_graph.moved_nodes() {
+            let (Some(&local_bounds), Some(node)) = (self.tracked.get(&entity), scene_graph.get_node(entity)) else {
+                continue;
+            };
+            self.update(entity, local_bounds.transform(node.world_matrix()));
+        }
+    }
+
+    /// Collect entities whose bounds intersect an AABB
+    pub fn query_aabb(&self, aabb: &Aabb, results: &mut Vec<Entity>) {
+        self.query(|bounds| bounds.intersects(aabb), results);
+    }
+
+    /// Collect entities whose bounds intersect a sphere
+    pub fn query_sphere(&self, sphere: &BoundingSphere, results: &mut Vec<Entity>) {
+        self.query(|bounds| sphere.intersects_aabb(bounds), results);
+    }
+
+    /// Collect entities whose bounds intersect a frustum
+    pub fn query_frustum(&self, frustum: &Frustum, results: &mut Vec<Entity>) {
+        self.query(|bounds| frustum.intersects_aabb(bounds), results);
+    }
+
+    /// Find the nearest entity hit by a ray within `max_distance`
+    pub fn raycast(&self, ray: &Ray, max_distance: f32) -> Option<(Entity, f32)> {
+        let mut nearest: Option<(Entity, f32)> = None;
+        let mut stack: SmallVec<[u32; 64]> = SmallVec::new();
+        if self.root != NULL {
+            stack.push(self.root);
+        }
+
+        while let Some(index) = stack.pop() {
+            let node = &self.nodes[index as usize];
+            let limit = nearest.map_or(max_distance, |(_, t)| t);
+            let bounds = if node.is_leaf() { &node.tight } else { &node.aabb };
+            let Some((t, _)) = ray.intersect_aabb(bounds) else {
+                continue;
+            };
+            if t > limit {
+                continue;
+            }
+            if node.is_leaf() {
+                nearest = Some((node.entity, t));
+            } else {
+                stack.push(node.left);
+                stack.push(node.right);
+            }
+        }
+        nearest
+    }
+
+    /// Collect every entity hit by a ray within `max_distance`, nearest first
+    pub fn raycast_all(&self, ray: &Ray, max_distance: f32, results: &mut Vec<(Entity, f32)>) {
+        let start = results.len();
+        let mut stack: SmallVec<[u32; 64]> = SmallVec::new();
+        if self.root != NULL {
+            stack.push(self.root);
+        }
+
+        while let Some(index) = stack.pop() {
+            let node = &self.nodes[index as usize];
+            let bounds = if node.is_leaf() { &node.tight } else { &node.aabb };
+            match ray.intersect_aabb(bounds) {
+                Some((t, _)) if t <= max_distance => {
+                    if node.is_leaf() {
+                        results.push((node.entity, t));
+                    } else {
+                        stack.push(node.left);
+                        stack.push(node.right);
+                    }
+                }
+                _ => {}
+            }
+        }
+        results[start..].sort_by(|a, b| a.1.total_cmp(&b.1));
+    }
+
+    /// Traverse nodes whose bounds pass `test`, collecting leaves
+    fn query(&self, test: impl Fn(&Aabb) -> bool, results: &mut Vec<Entity>) {
+        let mut stack: SmallVec<[u32; 64]> = SmallVec::new();
+        if self.root != NULL {
+            stack.push(self.root);
+        }
+
+        while let Some(index) = stack.pop() {
+            let node = &self.nodes[index as usize];
+            if node.is_leaf() {
+                if test(&node.tight) {
+                    results.push(node.entity);
+                }
+            } else if test(&node.aabb) {
+                stack.push(node.left);
+                stack.push(node.right);
+            }
+        }
+    }
+
+    fn allocate_node(&mut self) -> u32 {
+        let node = BvhNode {
+            aabb: Aabb::EMPTY,
+            tight: Aabb::EMPTY,
+            parent: NULL,
+            left: NULL,
+            right: NULL,
+            height: 0,
+            entity: Entity::null(),
+        };
+        match self.free_nodes.pop() {
+            Some(index) => {
+                self.nodes[index as usize] = node;
+                index
+            }
+            None => {
+                self.nodes.push(node);
+                (self.nodes.len() - 1) as u32
+            }
+        }
+    }
+
+    fn free_node(&mut self, index: u32) {
+        self.nodes[index as usize].height = -1;
+        self.free_nodes.push(index);
+    }
+
+    fn insert_leaf(&mut self, leaf: u32) {
+        if self.root == NULL {
+            self.root = leaf;
+            self.nodes[leaf as usize].parent = NULL;
+            return;
+        }
+
+        // Find the cheapest sibling by surface area heuristic
+        let leaf_aabb = self.nodes[leaf as usize].aabb;
+        let mut index = self.root;
+        while !self.nodes[index as usize].is_leaf() {
+            let node = &self.nodes[index as usize];
+            let area = node.aabb.surface_area();
+            let combined_area = node.aabb.merge(&leaf_aabb).surface_area();
+
+            // Cost of pairing with this node, and of pushing the leaf further down
+            let cost = 2.0 * combined_area;
+            let inheritance = 2.0 * (combined_area - area);
+            let child_cost = |child: u32| {
+                let child = &self.nodes[child as usize];
+                let merged = child.aabb.merge(&leaf_aabb).surface_area();
+                if child.is_leaf() {
+                    merged + inheritance
+                } else {
+                    merged - child.aabb.surface_area() + inheritance
+                }
+            };
+            let left_cost = child_cost(node.left);
+            let right_cost = child_cost(node.right);
+
+            if cost < left_cost && cost < right_cost {
+                break;
+            }
+            index = if left_cost < right_cost { node.left } else { node.right };
+        }
+
+        // Replace the sibling with a new parent of both
+        let sibling = index;
+        let old_parent = self.nodes[sibling as usize].parent;
+        let new_parent = self.allocate_node();
+        {
+            let sibling_node = &self.nodes[sibling as usize];
+            let aabb = leaf_aabb.merge(&sibling_node.aabb);
+            let height = sibling_node.height + 1;
+            let node = &mut self.nodes[new_parent as usize];
+            node.parent = old_parent;
+            node.aabb = aabb;
+            node.height = height;
+            node.left = sibling;
+            node.right = leaf;
+        }
+        self.nodes[sibling as usize].parent = new_parent;
+        self.nodes[leaf as usize].parent = new_parent;
+
+        if old_parent == NULL {
+            self.root = new_parent;
+        } else {
+            self.replace_child(old_parent, sibling, new_parent);
+        }
+
+        self.refit_ancestors(new_parent);
+    }
+
+    fn remove_leaf(&mut self, leaf: u32) {
+        if leaf == self.root {
+            self.root = NULL;
+            return;
+        }
+
+        let parent = self.nodes[leaf as usize].parent;
+        let grandparent = self.nodes[parent as usize].parent;
+        let sibling = if self.nodes[parent as usize].left == leaf {
+            self.nodes[parent as usize].right
+        } else {
+            self.nodes[parent as usize].left
+        };
+
+        self.nodes[sibling as usize].parent = grandparent;
+        self.free_node(parent);
+        if grandparent == NULL {
+            self.root = sibling;
+        } else {
+            self.replace_child(grandparent, parent, sibling);
+            self.refit_ancestors(grandparent);
+        }
+    }
+
+    fn replace_child(&mut self, parent: u32, old: u32, new: u32) {
+        let node = &mut self.nodes[parent as usize];
+        if node.left == old {
+            node.left = new;
+        } else {
+            node.right = new;
+        }
+    }
+
+    /// Rebalance and refit from a node up to the root
+    fn refit_ancestors(&mut self, start: u32) {
+        let mut index = start;
+        while index != NULL {
+            index = self.balance(index);
+            let (left, right) = (self.nodes[index as usize].left, self.nodes[index as usize].right);
+            let aabb = self.nodes[left as usize].aabb.merge(&self.nodes[right as usize].aabb);
+            let height = 1 + self.nodes[left as usize].height.max(self.nodes[right as usize].height);
+            let node = &mut self.nodes[index as usize];
+            node.aabb = aabb;
+            node.height = height;
+            index = node.parent;
+        }
+    }
+
+    /// Rotate the taller child up if `a` is unbalanced; returns the subtree root
+    fn balance(&mut self, a: u32) -> u32 {
+        let node_a = &self.nodes[a as usize];
+        if node_a.is_leaf() || node_a.height < 2 {
+            return a;
+        }
+        let (b, c) = (node_a.left, node_a.right);
+        let balance = self.nodes[c as usize].height - self.nodes[b as usize].height;
+
+        if balance > 1 {
+            self.rotate_up(a, c, b, false)
+        } else if balance < -1 {
+            self.rotate_up(a, b, c, true)
+        } else {
+            a
+        }
+    }
+
+    /// Rotate child `up` of `a` into its place; `other` is `a`'s other child
+    ///
+    /// `up_was_left` says which side of `a` `up` was on.
+    fn rotate_up(&mut self, a: u32, up: u32, other: u32, up_was_left: bool) -> u32 {
+        let (f, g) = (self.nodes[up as usize].left, self.nodes[up as usize].right);
+
+        // `up` takes `a`'s place
+        let a_parent = self.nodes[a as usize].parent;
+        self.nodes[up as usize].left = a;
+        self.nodes[up as usize].parent = a_parent;
+        self.nodes[a as usize].parent = up;
+        if a_parent == NULL {
+            self.root = up;
+        } else {
+            self.replace_child(a_parent, a, up);
+        }
+
+        // The taller grandchild stays under `up`, the shorter moves to `a`
+        let (keep, give) = if self.nodes[f as usize].height > self.nodes[g as usize].height {
+            (f, g)
+        } else {
+            (g, f)
+        };
+        self.nodes[up as usize].right = keep;
+        if up_was_left {
+            self.nodes[a as usize].left = give;
+        } else {
+            self.nodes[a as usize].right = give;
+        }
+        self.nodes[give as usize].parent = a;
+
+        let a_aabb = self.nodes[other as usize].aabb.merge(&self.nodes[give as usize].aabb);
+        let a_height = 1 + self.nodes[other as usize].height.max(self.nodes[give as usize].height);
+        self.nodes[a as usize].aabb = a_aabb;
+        self.nodes[a as usize].height = a_height;
+
+        let up_aabb = a_aabb.merge(&self.nodes[keep as usize].aabb);
+        let up_height = 1 + a_height.max(self.nodes[keep as usize].height);
+        self.nodes[up as usize].aabb = up_aabb;
+        self.nodes[up as usize].height = up_height;
+
+        up
+    }
+}
+
+impl Default for DynamicBvh {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::{Mat4, Vec3};
+
+    fn unit_box(center: Vec3) -> Aabb {
+        Aabb::from_center_half_extents(center, Vec3::splat(0.5))
+    }
+
+    /// Check parent links and that every node's bounds contain its children
+    fn validate(bvh: &DynamicBvh) {
+        if bvh.root == NULL {
+            return;
+        }
+        let mut stack = vec![bvh.root];
+        let mut leaves = 0;
+        while let Some(index) = stack.pop() {
+            let node = &bvh.nodes[index as usize];
+            if node.is_leaf() {
+                leaves += 1;
+                assert!(node.aabb.contains_aabb(&node.tight));
+                continue;
+            }
+            for child in [node.left, node.right] {
+                assert_eq!(bvh.nodes[child as usize].parent, index);
+                assert!(node.aabb.contains_aabb(&bvh.nodes[child as usize].aabb));
+                stack.push(child);
+            }
+            let balance = bvh.nodes[node.left as usize].height - bvh.nodes[node.right as usize].height;
+            assert!(balance.abs() <= 1);
+        }
+        assert_eq!(leaves, bvh.len());
+    }
+
+    #[test]
+    fn test_bvh_insert_remove_query() {
+        let mut bvh = DynamicBvh::new();
+        for i in 0..100 {
+            bvh.insert(Entity::new(i, 0), unit_box(Vec3::new(i as f32 * 2.0, 0.0, 0.0)));
+        }
+        validate(&bvh);
+        assert_eq!(bvh.len(), 100);
+        assert!(bvh.height() <= 10);
+
+        let mut results = Vec::new();
+        bvh.query_aabb(&Aabb::new(Vec3::new(9.0, -1.0, -1.0), Vec3::new(13.0, 1.0, 1.0)), &mut results);
+        results.sort_by_key(|e| e.index());
+        assert_eq!(results, [Entity::new(5, 0), Entity::new(6, 0)]);
+
+        for i in (0..100).step_by(2) {
+            assert!(bvh.remove(Entity::new(i, 0)));
+        }
+        assert!(!bvh.remove(Entity::new(0, 0)));
+        validate(&bvh);
+
+        results.clear();
+        bvh.query_sphere(&BoundingSphere::new(Vec3::new(10.0, 0.0, 0.0), 2.6), &mut results);
+        results.sort_by_key(|e| e.index());
+        assert_eq!(results, [Entity::new(5, 0)]);
+    }
+
+    #[test]
+    fn test_bvh_update_uses_fat_bounds() {
+        let mut bvh = DynamicBvh::with_margin(0.5);
+        let entity = Entity::new(0, 0);
+        bvh.insert(entity, unit_box(Vec3::ZERO));
+        bvh.insert(Entity::new(1, 0), unit_box(Vec3::splat(5.0)));
+
+        assert!(!bvh.update(entity, unit_box(Vec3::new(0.25, 0.0, 0.0))));
+        assert_eq!(bvh.bounds(entity), Some(unit_box(Vec3::new(0.25, 0.0, 0.0))));
+        assert!(bvh.update(entity, unit_box(Vec3::new(3.0, 0.0, 0.0))));
+        validate(&bvh);
+
+        let mut results = Vec::new();
+        bvh.query_aabb(&unit_box(Vec3::ZERO), &mut results);
+        assert!(results.is_empty());
+    }
+
+    #[test]
+    fn test_bvh_frustum_and_rays() {
+        let mut bvh = DynamicBvh::new();
+        for i in 0..10 {
+            bvh.insert(Entity::new(i, 0), unit_box(Vec3::new(0.0, 0.0, -(i as f32) * 3.0 - 5.0)));
+        }
+        bvh.insert(Entity::new(99, 0), unit_box(Vec3::new(0.0, 0.0, 10.0)));
+
+        let projection = Mat4::perspective_rh(std::f32::consts::FRAC_PI_2, 1.0, 0.1, 20.0);
+        let mut results = Vec::new();
+        bvh.query_frustum(&Frustum::from_matrix(projection), &mut results);
+        assert!(!results.contains(&Entity::new(99, 0)));
+        assert!(results.contains(&Entity::new(0, 0)));
+
+        let ray = Ray::new(Vec3::ZERO, Vec3::NEG_Z);
+        let (nearest, distance) = bvh.raycast(&ray, 100.0).unwrap();
+        assert_eq!(nearest, Entity::new(0, 0));
+        assert!((distance - 4.5).abs() < 0.001);
+        assert!(bvh.raycast(&ray, 4.0).is_none());
+
+        let mut hits = Vec::new();
+        bvh.raycast_all(&ray, 12.0, &mut hits);
+        let hit_entities: Vec<_> = hits.iter().map(|(e, _)| e.index()).collect();
+        assert_eq!(hit_entities, [0, 1, 2]);
+    }
+
+    #[test]
+    fn test_bvh_follows_scene_graph() {
+        let mut sg = SceneGraph::new();
+        let parent = Entity::new(0, 0);
+        let child = Entity::new(1, 0);
+        sg.add_node(parent, "Parent");
+        sg.add_node(child, "Child").local_transform.position = Vec3::new(1.0, 0.0, 0.0);
+        sg.set_parent(child, Some(parent));
+        sg.update_transforms();
+
+        let mut bvh = DynamicBvh::new();
+        assert!(bvh.track_node(&sg, child, unit_box(Vec3::ZERO)));
+        assert_eq!(bvh.bounds(child).unwrap().center(), Vec3::new(1.0, 0.0, 0.0));
+
+        // Moving the parent moves the tracked child
+        sg.get_node_mut(parent).unwrap().local_transform.position = Vec3::new(0.0, 10.0, 0.0);
+        sg.update_transforms();
+        bvh.sync_with_scene(&sg);
+        assert_eq!(bvh.bounds(child).unwrap().center(), Vec3::new(1.0, 10.0, 0.0));
+
+        sg.remove_node(child);
+        sg.update_transforms();
+        bvh.sync_with_scene(&sg);
+        assert!(bvh.is_empty());
+    }
+}
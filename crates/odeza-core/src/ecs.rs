@@ -6,11 +6,14 @@
 //! - Components stored in SoA (Structure of Arrays) layout where possible
 //! - Systems scheduled via job graph
 //! - Efficient component queries
+//! - Component registry for building components from serialized data
 
 use std::any::{Any, TypeId};
 use std::sync::atomic::{AtomicU32, Ordering};
 
 use ahash::AHashMap;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
 use smallvec::SmallVec;
 
 /// Marker trait for components
@@ -80,6 +83,10 @@ trait ComponentStorage: Send + Sync {
     fn as_any(&self) -> &dyn Any;
     fn as_any_mut(&mut self) -> &mut dyn Any;
     fn remove(&mut self, index: usize);
+    /// Move the component at `index` to the end of `dst` (swap-remove)
+    fn move_to(&mut self, index: usize, dst: &mut dyn ComponentStorage);
+    /// Create an empty storage of the same component type
+    fn new_empty(&self) -> Box<dyn ComponentStorage>;
     fn len(&self) -> usize;
     fn is_empty(&self) -> bool {
         self.len() == 0
@@ -124,6 +131,18 @@ impl<T: Component> ComponentStorage for TypedStorage<T> {
         }
     }
 
+    fn move_to(&mut self, index: usize, dst: &mut dyn ComponentStorage) {
+        let component = self.data.swap_remove(index);
+        dst.as_any_mut()
+            .downcast_mut::<TypedStorage<T>>()
+            .expect("Component storage type mismatch")
+            .push(component);
+    }
+
+    fn new_empty(&self) -> Box<dyn ComponentStorage> {
+        Box::new(TypedStorage::<T>::new())
+    }
+
     fn len(&self) -> usize {
         self.data.len()
     }
@@ -153,6 +172,15 @@ impl Archetype {
     fn contains_type(&self, type_id: TypeId) -> bool {
         self.component_types.contains(&type_id)
     }
+
+    /// Swap-remove a row, returning the entity moved into its place
+    fn swap_remove_row(&mut self, row: usize) -> Option<Entity> {
+        for storage in self.storages.values_mut() {
+            storage.remove(row);
+        }
+        self.entities.swap_remove(row);
+        self.entities.get(row).copied()
+    }
 }
 
 /// The ECS world containing all entities and components
@@ -171,6 +199,8 @@ pub struct World {
     next_archetype_id: u32,
     /// Entity to archetype index mapping
     entity_archetype_row: AHashMap<Entity, usize>,
+    /// Components constructible from serialized data
+    component_registry: ComponentRegistry,
 }
 
 impl World {
@@ -184,6 +214,7 @@ impl World {
             archetype_map: AHashMap::new(),
             next_archetype_id: 0,
             entity_archetype_row: AHashMap::new(),
+            component_registry: ComponentRegistry::new(),
         }
     }
 
@@ -218,8 +249,13 @@ impl World {
         meta.alive = false;
         
         // Remove from archetype if present
-        if let Some(_archetype_id) = meta.archetype_id.take() {
-            self.entity_archetype_row.remove(&entity);
+        if let Some(archetype_id) = meta.archetype_id.take()
+            && let Some(row) = self.entity_archetype_row.remove(&entity)
+        {
+            let archetype = &mut self.archetypes[archetype_id.0 as usize];
+            if let Some(moved) = archetype.swap_remove_row(row) {
+                self.entity_archetype_row.insert(moved, row);
+            }
         }
 
         self.free_indices.push(entity.index());
@@ -239,23 +275,41 @@ impl World {
     }
 
     /// Add a component to an entity
+    ///
+    /// Replaces the existing value if the entity already has a `T`. Otherwise
+    /// the entity moves to the archetype that includes `T`.
     pub fn add_component<T: Component>(&mut self, entity: Entity, component: T) -> bool {
         if !self.is_alive(entity) {
             return false;
         }
 
+        if let Some(existing) = self.get_component_mut::<T>(entity) {
+            *existing = component;
+            return true;
+        }
+
         let type_id = TypeId::of::<T>();
+        let old_archetype = self.entities[entity.index() as usize].archetype_id;
         
-        // Get or create archetype with this component
-        let archetype_id = self.get_or_create_archetype_for_component::<T>(entity);
+        // Get or create archetype with the extra component
+        let archetype_id = self.get_or_create_archetype_with::<T>(old_archetype);
         
+        // Move existing components over
+        if let Some(old_id) = old_archetype {
+            let row = self.entity_archetype_row[&entity];
+            let (old, new) = two_mut(&mut self.archetypes, old_id.0 as usize, archetype_id.0 as usize);
+            for (type_id, storage) in old.storages.iter_mut() {
+                let dst = new.storages.get_mut(type_id).expect("Archetype storage missing");
+                storage.move_to(row, dst.as_mut());
+            }
+            old.entities.swap_remove(row);
+            if let Some(&moved) = old.entities.get(row) {
+                self.entity_archetype_row.insert(moved, row);
+            }
+        }
+
         // Get the archetype
         let archetype = &mut self.archetypes[archetype_id.0 as usize];
-        
-        // Ensure storage exists for this component type
-        if !archetype.storages.contains_key(&type_id) {
-            archetype.storages.insert(type_id, Box::new(TypedStorage::<T>::new()));
-        }
 
         // Add component to storage
         let storage = archetype.storages.get_mut(&type_id).unwrap();
@@ -321,9 +375,48 @@ impl World {
         archetype.contains_type(TypeId::of::<T>())
     }
 
-    fn get_or_create_archetype_for_component<T: Component>(&mut self, _entity: Entity) -> ArchetypeId {
+    /// Get the component registry
+    pub fn component_registry(&self) -> &ComponentRegistry {
+        &self.component_registry
+    }
+
+    /// Get the component registry for registering types
+    pub fn component_registry_mut(&mut self) -> &mut ComponentRegistry {
+        &mut self.component_registry
+    }
+
+    /// Add a component to an entity from serialized data
+    ///
+    /// The component type is looked up by its registered name.
+    pub fn add_serialized_component(
+        &mut self,
+        entity: Entity,
+        name: &str,
+        data: &serde_json::Value,
+    ) -> Result<(), String> {
+        let registry = std::mem::take(&mut self.component_registry);
+        let result = registry.insert(self, entity, name, data);
+        self.component_registry = registry;
+        result
+    }
+
+    /// Get the number of component types on an entity
+    pub fn component_count(&self, entity: Entity) -> usize {
+        if !self.is_alive(entity) {
+            return 0;
+        }
+        self.entities[entity.index() as usize]
+            .archetype_id
+            .map_or(0, |id| self.archetypes[id.0 as usize].component_types.len())
+    }
+
+    fn get_or_create_archetype_with<T: Component>(&mut self, base: Option<ArchetypeId>) -> ArchetypeId {
         let type_id = TypeId::of::<T>();
-        let types: SmallVec<[TypeId; 8]> = smallvec::smallvec![type_id];
+        let mut types: SmallVec<[TypeId; 8]> = base
+            .map(|id| self.archetypes[id.0 as usize].component_types.clone())
+            .unwrap_or_default();
+        types.push(type_id);
+        types.sort();
 
         if let Some(&id) = self.archetype_map.get(&types) {
             return id;
@@ -332,7 +425,13 @@ impl World {
         let id = ArchetypeId(self.next_archetype_id);
         self.next_archetype_id += 1;
 
-        let archetype = Archetype::new(id, types.clone());
+        let mut archetype = Archetype::new(id, types.clone());
+        if let Some(base) = base {
+            for (type_id, storage) in &self.archetypes[base.0 as usize].storages {
+                archetype.storages.insert(*type_id, storage.new_empty());
+            }
+        }
+        archetype.storages.insert(type_id, Box::new(TypedStorage::<T>::new()));
         self.archetypes.push(archetype);
         self.archetype_map.insert(types, id);
 
@@ -340,6 +439,99 @@ impl World {
     }
 }
 
+/// Borrow two distinct elements mutably
+fn two_mut<T>(items: &mut [T], a: usize, b: usize) -> (&mut T, &mut T) {
+    assert_ne!(a, b);
+    if a < b {
+        let (left, right) = items.split_at_mut(b);
+        (&mut left[a], &mut right[0])
+    } else {
+        let (left, right) = items.split_at_mut(a);
+        (&mut right[0], &mut left[b])
+    }
+}
+
+/// Inserts a component deserialized from data
+type ComponentInserter =
+    Box<dyn Fn(&mut World, Entity, &serde_json::Value) -> Result<(), String> + Send + Sync>;
+
+/// Serializes a component if the entity has one
+type ComponentExtractor =
+    Box<dyn Fn(&World, Entity) -> Option<Result<serde_json::Value, String>> + Send + Sync>;
+
+/// Serialization functions for a registered component type
+struct RegisteredComponent {
+    insert: ComponentInserter,
+    extract: ComponentExtractor,
+}
+
+/// Registry of components that can be built from serialized data
+///
+/// Prefabs and scene files refer to components by registered name.
+#[derive(Default)]
+pub struct ComponentRegistry {
+    components: AHashMap<String, RegisteredComponent>,
+}
+
+impl ComponentRegistry {
+    /// Create an empty registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a component type under a name
+    pub fn register<T: Component + Serialize + DeserializeOwned>(&mut self, name: &str) {
+        self.components.insert(
+            name.to_string(),
+            RegisteredComponent {
+                insert: Box::new(|world, entity, data| {
+                    let component = T::deserialize(data).map_err(|e| e.to_string())?;
+                    world.add_component(entity, component);
+                    Ok(())
+                }),
+                extract: Box::new(|world, entity| {
+                    let component = world.get_component::<T>(entity)?;
+                    Some(serde_json::to_value(component).map_err(|e| e.to_string()))
+                }),
+            },
+        );
+    }
+
+    /// Check if a component name is registered
+    pub fn contains(&self, name: &str) -> bool {
+        self.components.contains_key(name)
+    }
+
+    /// Deserialize a component and add it to an entity
+    ///
+    /// Returns an error message if the name is unknown or the data is invalid.
+    pub fn insert(
+        &self,
+        world: &mut World,
+        entity: Entity,
+        name: &str,
+        data: &serde_json::Value,
+    ) -> Result<(), String> {
+        let component = self
+            .components
+            .get(name)
+            .ok_or_else(|| format!("Unknown component type '{}'", name))?;
+        (component.insert)(world, entity, data)
+    }
+
+    /// Serialize every registered component on an entity, sorted by name
+    pub fn extract(&self, world: &World, entity: Entity) -> Result<Vec<(String, serde_json::Value)>, String> {
+        let mut extracted = Vec::new();
+        for (name, component) in &self.components {
+            if let Some(data) = (component.extract)(world, entity) {
+                extracted.push((name.clone(), data?));
+            }
+        }
+        extracted.sort_by(|a, b| a.0.cmp(&b.0));
+        Ok(extracted)
+    }
+}
+
 impl Default for World {
     fn default() -> Self {
         Self::new()
@@ -427,6 +619,49 @@ mod tests {
         assert_eq!(pos.x, 10.0);
     }
 
+    #[test]
+    fn test_multiple_components() {
+        let mut world = World::new();
+        let a = world.spawn();
+        let b = world.spawn();
+        
+        world.add_component(a, Position { x: 1.0, y: 0.0, z: 0.0 });
+        world.add_component(b, Position { x: 2.0, y: 0.0, z: 0.0 });
+        world.add_component(a, Velocity { x: 3.0, y: 0.0, z: 0.0 });
+        world.add_component(a, Position { x: 4.0, y: 0.0, z: 0.0 });
+        
+        assert_eq!(world.component_count(a), 2);
+        assert_eq!(world.get_component::<Position>(a).unwrap().x, 4.0);
+        assert_eq!(world.get_component::<Velocity>(a).unwrap().x, 3.0);
+        assert_eq!(world.get_component::<Position>(b).unwrap().x, 2.0);
+        
+        // Despawning keeps the remaining rows addressable
+        world.despawn(a);
+        assert_eq!(world.get_component::<Position>(b).unwrap().x, 2.0);
+        assert!(world.get_component::<Velocity>(a).is_none());
+    }
+
+    #[test]
+    fn test_component_registry() {
+        #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+        struct Health(f32);
+
+        let mut world = World::new();
+        world.component_registry_mut().register::<Health>("Health");
+        assert!(world.component_registry().contains("Health"));
+        
+        let entity = world.spawn();
+        world
+            .add_serialized_component(entity, "Health", &serde_json::json!(75.0))
+            .unwrap();
+        assert_eq!(world.get_component::<Health>(entity), Some(&Health(75.0)));
+        let extracted = world.component_registry().extract(&world, entity).unwrap();
+        assert_eq!(extracted, vec![("Health".to_string(), serde_json::json!(75.0))]);
+        
+        assert!(world.add_serialized_component(entity, "Mana", &serde_json::json!(1)).is_err());
+        assert!(world.add_serialized_component(entity, "Health", &serde_json::json!("x")).is_err());
+    }
+
     #[test]
     fn test_has_component() {
         let mut world = World::new();
@@ -7,12 +7,16 @@
 //! - Priority-based scheduling
 //! - Per-subsystem job budgets
 
+use std::any::Any;
+use std::panic::AssertUnwindSafe;
 use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::Arc;
 
 use crossbeam::deque::{Injector, Stealer, Worker};
 use parking_lot::{Condvar, Mutex};
 
+use crate::memory::SubsystemScope;
+
 /// Job priority levels
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum JobPriority {
@@ -60,6 +64,11 @@ pub trait Job: Send + 'static {
     fn name(&self) -> &str {
         "unnamed_job"
     }
+
+    /// Get the memory subsystem the job's allocations are attributed to
+    fn subsystem(&self) -> Option<usize> {
+        None
+    }
 }
 
 /// Wrapper for closure-based jobs
@@ -216,6 +225,7 @@ impl JobSystem {
             match self.global_queue.steal() {
                 crossbeam::deque::Steal::Success(mut wrapper) => {
                     if wrapper.can_execute() {
+                        let _scope = wrapper.job.subsystem().map(SubsystemScope::enter);
                         wrapper.job.execute();
                         wrapper.completed.store(true, Ordering::Release);
                         processed += 1;
@@ -242,6 +252,42 @@ impl JobSystem {
         }
     }
 
+    /// Run closures that borrow from the caller in parallel and wait for them
+    ///
+    /// The closures never enter the shared queue. The calling thread and up
+    /// to `num_workers - 1` helper threads spawned for this call take them
+    /// from a list local to the call until it is empty. If a closure panics,
+    /// the panic is resumed here after every closure has finished.
+    pub fn scope<'env, F>(&self, name: &'static str, tasks: impl IntoIterator<Item = F>)
+    where
+        F: FnOnce() + Send + 'env,
+    {
+        let tasks: Vec<F> = tasks.into_iter().collect();
+        let helpers = self.num_workers.min(tasks.len()).saturating_sub(1);
+        let tasks = Mutex::new(tasks.into_iter());
+        let panic: Mutex<Option<Box<dyn Any + Send>>> = Mutex::new(None);
+
+        let run = || {
+            while let Some(task) = tasks.lock().next() {
+                if let Err(payload) = std::panic::catch_unwind(AssertUnwindSafe(task)) {
+                    panic.lock().get_or_insert(payload);
+                }
+            }
+        };
+        // The thread scope joins the helpers before the borrows can end
+        std::thread::scope(|scope| {
+            for _ in 0..helpers {
+                // A helper that fails to spawn leaves its share to the other threads
+                let _ = std::thread::Builder::new().name(name.into()).spawn_scoped(scope, run);
+            }
+            run();
+        });
+
+        if let Some(payload) = panic.into_inner() {
+            std::panic::resume_unwind(payload);
+        }
+    }
+
     /// Wait for all submitted jobs to complete
     pub fn wait_all(&self) {
         while !self.global_queue.is_empty() {
@@ -345,6 +391,10 @@ impl Job for BoxedJob {
     fn name(&self) -> &str {
         self.inner.name()
     }
+
+    fn subsystem(&self) -> Option<usize> {
+        self.inner.subsystem()
+    }
 }
 
 #[cfg(test)]
@@ -430,6 +480,39 @@ mod tests {
         assert_eq!(value.load(Ordering::Relaxed), 20);
     }
 
+    #[test]
+    fn test_scope_borrows_and_propagates_panics() {
+        let job_system = JobSystem::new(2);
+        let mut values = [1u32, 2, 3, 4];
+        job_system.scope("double", values.chunks_mut(2).map(|chunk| move || {
+            for value in chunk {
+                *value *= 2;
+            }
+        }));
+        assert_eq!(values, [2, 4, 6, 8]);
+
+        let finished = AtomicU32::new(0);
+        let result = std::panic::catch_unwind(AssertUnwindSafe(|| {
+            job_system.scope("maybe_panic", (0..4).map(|i| {
+                let finished = &finished;
+                move || {
+                    assert_ne!(i, 1, "job failed");
+                    finished.fetch_add(1, Ordering::Relaxed);
+                }
+            }));
+        }));
+        assert!(result.is_err());
+        assert_eq!(finished.load(Ordering::Relaxed), 3);
+
+        // Closures are spread across threads rather than run one by one
+        let threads = Mutex::new(std::collections::HashSet::new());
+        JobSystem::new(4).scope("spread", (0..8).map(|_| || {
+            threads.lock().insert(std::thread::current().id());
+            std::thread::sleep(std::time::Duration::from_millis(20));
+        }));
+        assert!(threads.lock().len() > 1);
+    }
+
     #[test]
     fn test_task_graph() {
         let job_system = JobSystem::new(2);
@@ -452,4 +535,31 @@ mod tests {
 
         assert_eq!(counter.load(Ordering::Relaxed), 3);
     }
+
+    struct TaggedJob {
+        seen: Arc<AtomicU32>,
+    }
+
+    impl Job for TaggedJob {
+        fn execute(&mut self) {
+            let tag = crate::memory::current_subsystem().map_or(u32::MAX, |id| id as u32);
+            self.seen.store(tag, Ordering::Relaxed);
+        }
+
+        fn subsystem(&self) -> Option<usize> {
+            Some(7)
+        }
+    }
+
+    #[test]
+    fn test_job_subsystem_scope() {
+        let job_system = JobSystem::new(1);
+        let seen = Arc::new(AtomicU32::new(0));
+
+        let handle = job_system.submit(TaggedJob { seen: seen.clone() }, JobPriority::Normal);
+        job_system.wait_for(&handle);
+
+        assert_eq!(seen.load(Ordering::Relaxed), 7);
+        assert_eq!(crate::memory::current_subsystem(), None);
+    }
 }
@@ -7,20 +7,31 @@
 //! - **Job System**: Work-stealing thread pool with per-frame task graph
 //! - **Memory**: Frame allocators, arenas, and pool allocators
 //! - **Time**: Variable render step and fixed-step simulation support
+//! - **Schedule**: FixedUpdate, Update, LateUpdate and Render system phases
+//! - **Timer Wheel**: O(1) scheduling of delayed and repeating actions
 //! - **Scene Graph**: Hierarchical transforms, parenting, and prefab support
+//! - **BVH**: Dynamic AABB tree for spatial queries
 
 pub mod ecs;
 pub mod job;
 pub mod memory;
 pub mod time;
+pub mod schedule;
+pub mod timer_wheel;
 pub mod scene;
+pub mod bvh;
 pub mod math;
 
-pub use ecs::{Entity, World, Component};
+pub use ecs::{Entity, World, Component, ComponentRegistry};
 pub use job::{JobSystem, Job, JobHandle};
-pub use memory::{FrameAllocator, ArenaAllocator, PoolAllocator};
-pub use time::{TimeManager, DeltaTime, FixedTimeStep};
-pub use scene::{SceneGraph, Transform, Node};
+pub use memory::{FrameAllocator, StackAllocator, ArenaAllocator, PoolAllocator, PoolHandle, MemoryTracker, TrackingAllocator};
+pub use memory::{Frame, FrameBox, FrameVec, FrameString, BufferedFrameAllocator};
+pub use time::{TimeManager, DeltaTime, FixedTimeStep, Clock, ClockId, TimeDilation};
+pub use schedule::{Phase, PhaseContext, Schedule, System, SystemId};
+pub use timer_wheel::{TimerService, TimerHandle, TimerAction};
+pub use scene::{SceneGraph, Transform, Node, Prefab, PrefabNode, PrefabError, ComponentData};
+pub use scene::{PrefabLibrary, PrefabInstance, PropertyOverride, OverrideProperty};
+pub use bvh::DynamicBvh;
 
 /// Performance tier for mobile and handheld devices
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -52,6 +63,8 @@ pub struct EngineConfig {
     pub temporal_upscaling: bool,
     /// Fixed simulation timestep in seconds
     pub fixed_timestep: f64,
+    /// Maximum fixed updates per frame before the simulation slows down
+    pub max_fixed_updates: u32,
     /// Maximum frame time before slowdown
     pub max_frame_time: f64,
 }
@@ -64,6 +77,7 @@ impl Default for EngineConfig {
             dynamic_resolution: true,
             temporal_upscaling: true,
             fixed_timestep: 1.0 / 60.0,
+            max_fixed_updates: 8,
             max_frame_time: 1.0 / 30.0,
         }
     }
@@ -76,6 +90,7 @@ pub struct Engine {
     job_system: JobSystem,
     time_manager: TimeManager,
     scene_graph: SceneGraph,
+    schedule: Schedule,
 }
 
 impl Engine {
@@ -89,6 +104,7 @@ impl Engine {
             job_system: JobSystem::new(num_threads),
             time_manager: TimeManager::new(),
             scene_graph: SceneGraph::new(),
+            schedule: Schedule::new(),
         }
     }
 
@@ -127,21 +143,74 @@ impl Engine {
         &mut self.scene_graph
     }
 
+    /// Update scene graph world transforms on the engine's job system
+    pub fn update_transforms(&mut self) {
+        self.scene_graph.update_transforms_on(&self.job_system);
+    }
+
+    /// Get the fixed time step configuration
+    pub fn fixed_time_step(&self) -> FixedTimeStep {
+        FixedTimeStep::from_step(self.config.fixed_timestep)
+            .with_max_updates(self.config.max_fixed_updates)
+    }
+
+    /// Register a system to run in a phase
+    pub fn add_system<S: System + 'static>(&mut self, phase: Phase, system: S) -> SystemId {
+        self.schedule.add_system(phase, system)
+    }
+
+    /// Remove a registered system
+    pub fn remove_system(&mut self, id: SystemId) -> bool {
+        self.schedule.remove_system(id)
+    }
+
+    /// Get mutable access to the system schedule
+    pub fn schedule_mut(&mut self) -> &mut Schedule {
+        &mut self.schedule
+    }
+
     /// Update the engine for one frame
+    ///
+    /// Runs FixedUpdate as many times as the accumulated time allows (up to
+    /// `max_fixed_updates`), then Update, LateUpdate and Render.
     pub fn update(&mut self, delta_time: f64) {
         self.time_manager.update(delta_time);
         
         // Run fixed timestep simulation
-        let fixed_dt = self.config.fixed_timestep;
-        while self.time_manager.should_run_fixed_update(fixed_dt) {
-            self.fixed_update(fixed_dt);
-            self.time_manager.consume_fixed_update(fixed_dt);
+        let fixed = self.fixed_time_step();
+        let updates = self.time_manager.fixed_updates_needed(&fixed);
+        for _ in 0..updates {
+            self.fixed_update(fixed.step);
+            self.time_manager.consume_fixed_update(fixed.step);
         }
+        if self.time_manager.should_run_fixed_update(fixed.step) {
+            // Hit the substep cap; drop the backlog rather than spiral
+            self.time_manager.discard_fixed_backlog(fixed.step);
+        }
+
+        let frame_dt = self.time_manager.delta_time().as_secs();
+        self.run_phase(Phase::Update, frame_dt, 0.0);
+        self.run_phase(Phase::LateUpdate, frame_dt, 0.0);
+
+        let alpha = self.time_manager.fixed_interpolation(fixed.step);
+        self.run_phase(Phase::Render, frame_dt, alpha);
     }
 
     /// Fixed timestep update for physics and deterministic simulation
-    fn fixed_update(&mut self, _delta_time: f64) {
-        // Physics and deterministic simulation runs here
+    fn fixed_update(&mut self, delta_time: f64) {
+        self.run_phase(Phase::FixedUpdate, delta_time, 0.0);
+    }
+
+    fn run_phase(&mut self, phase: Phase, delta_time: f64, alpha: f64) {
+        let mut ctx = PhaseContext {
+            phase,
+            world: &mut self.world,
+            scene_graph: &mut self.scene_graph,
+            time: &self.time_manager,
+            delta_time,
+            alpha,
+        };
+        self.schedule.run(&mut ctx);
     }
 }
 
@@ -156,6 +225,46 @@ mod tests {
         assert_eq!(engine.config().target_fps, 60);
     }
 
+    #[test]
+    fn test_engine_phases() {
+        use std::sync::{Arc, Mutex};
+
+        let mut engine = Engine::new(EngineConfig::default());
+        let log = Arc::new(Mutex::new(Vec::new()));
+        for phase in Phase::ALL {
+            let log = log.clone();
+            engine.add_system(phase, move |ctx: &mut PhaseContext<'_>| {
+                log.lock().unwrap().push((ctx.phase, ctx.delta_time, ctx.alpha));
+            });
+        }
+
+        // Two and a half fixed steps
+        engine.update(2.5 / 60.0);
+        let log = log.lock().unwrap();
+        let phases: Vec<_> = log.iter().map(|(phase, _, _)| *phase).collect();
+        assert_eq!(
+            phases,
+            [Phase::FixedUpdate, Phase::FixedUpdate, Phase::Update, Phase::LateUpdate, Phase::Render]
+        );
+        assert_eq!(log[0].1, 1.0 / 60.0);
+        assert!((log[4].2 - 0.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_engine_max_fixed_updates() {
+        let config = EngineConfig {
+            max_fixed_updates: 3,
+            ..EngineConfig::default()
+        };
+        let mut engine = Engine::new(config);
+
+        // A long hitch only runs the capped number of steps and drops the rest
+        engine.update(0.2);
+        assert_eq!(engine.time_manager().fixed_update_count(), 3);
+        engine.update(1.0 / 120.0);
+        assert_eq!(engine.time_manager().fixed_update_count(), 3);
+    }
+
     #[test]
     fn test_performance_tiers() {
         assert_eq!(PerformanceTier::default(), PerformanceTier::Mobile);
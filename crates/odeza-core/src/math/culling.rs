@@ -0,0 +1,247 @@
+//! Batch Frustum Culling
+//!
+//! Tests many bounding boxes against a frustum at once:
+//! - Bounds stored as structure-of-arrays (centers and extents)
+//! - Four boxes per step using SSE/NEON through `wide`, scalar elsewhere
+//! - Results written as a visibility bitmask
+
+use wide::f32x4;
+
+use super::{Aabb, Frustum, Vec3};
+
+/// Number of boxes tested per SIMD step
+const LANES: usize = 4;
+
+/// Bounding boxes in structure-of-arrays layout
+#[derive(Debug, Clone, Default)]
+pub struct AabbSoa {
+    pub center_x: Vec<f32>,
+    pub center_y: Vec<f32>,
+    pub center_z: Vec<f32>,
+    pub extent_x: Vec<f32>,
+    pub extent_y: Vec<f32>,
+    pub extent_z: Vec<f32>,
+}
+
+impl AabbSoa {
+    /// Create empty storage
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create empty storage with room for `capacity` boxes
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            center_x: Vec::with_capacity(capacity),
+            center_y: Vec::with_capacity(capacity),
+            center_z: Vec::with_capacity(capacity),
+            extent_x: Vec::with_capacity(capacity),
+            extent_y: Vec::with_capacity(capacity),
+            extent_z: Vec::with_capacity(capacity),
+        }
+    }
+
+    /// Get the number of boxes
+    pub fn len(&self) -> usize {
+        self.center_x.len()
+    }
+
+    /// Check if there are no boxes
+    pub fn is_empty(&self) -> bool {
+        self.center_x.is_empty()
+    }
+
+    /// Append a box
+    pub fn push(&mut self, aabb: &Aabb) {
+        let center = aabb.center();
+        let extent = aabb.half_extents();
+        self.center_x.push(center.x);
+        self.center_y.push(center.y);
+        self.center_z.push(center.z);
+        self.extent_x.push(extent.x);
+        self.extent_y.push(extent.y);
+        self.extent_z.push(extent.z);
+    }
+
+    /// Get a box
+    pub fn get(&self, index: usize) -> Aabb {
+        Aabb::from_center_half_extents(
+            Vec3::new(self.center_x[index], self.center_y[index], self.center_z[index]),
+            Vec3::new(self.extent_x[index], self.extent_y[index], self.extent_z[index]),
+        )
+    }
+
+    /// Remove all boxes
+    pub fn clear(&mut self) {
+        self.center_x.clear();
+        self.center_y.clear();
+        self.center_z.clear();
+        self.extent_x.clear();
+        self.extent_y.clear();
+        self.extent_z.clear();
+    }
+}
+
+impl FromIterator<Aabb> for AabbSoa {
+    fn from_iter<I: IntoIterator<Item = Aabb>>(iter: I) -> Self {
+        let mut soa = Self::new();
+        for aabb in iter {
+            soa.push(&aabb);
+        }
+        soa
+    }
+}
+
+/// Check whether bit `index` is set in a visibility mask
+pub fn is_visible(mask: &[u64], index: usize) -> bool {
+    mask[index / 64] & (1 << (index % 64)) != 0
+}
+
+/// Iterate the indices set in a visibility mask
+pub fn visible_indices(mask: &[u64]) -> impl Iterator<Item = usize> + '_ {
+    mask.iter().enumerate().flat_map(|(word_index, &word)| {
+        let mut bits = word;
+        std::iter::from_fn(move || {
+            if bits == 0 {
+                return None;
+            }
+            let bit = bits.trailing_zeros() as usize;
+            bits &= bits - 1;
+            Some(word_index * 64 + bit)
+        })
+    })
+}
+
+/// Split values into full SIMD lanes, leaving out the remainder
+fn lanes(values: &[f32]) -> impl Iterator<Item = f32x4> + '_ {
+    values
+        .chunks_exact(LANES)
+        .map(|chunk| f32x4::from(<[f32; LANES]>::try_from(chunk).unwrap()))
+}
+
+/// Reset a mask to hold `count` cleared bits
+fn reset_mask(mask: &mut Vec<u64>, count: usize) {
+    mask.clear();
+    mask.resize(count.div_ceil(64), 0);
+}
+
+impl Frustum {
+    /// Cull a batch of boxes, setting bit i of `mask` when box i is visible
+    ///
+    /// Gives exactly the same results as [`Frustum::cull_aabbs_scalar`].
+    pub fn cull_aabbs(&self, bounds: &AabbSoa, mask: &mut Vec<u64>) {
+        let count = bounds.len();
+        reset_mask(mask, count);
+
+        let planes = self.planes.map(|plane| {
+            (
+                f32x4::splat(plane.normal.x),
+                f32x4::splat(plane.normal.y),
+                f32x4::splat(plane.normal.z),
+                f32x4::splat(plane.distance),
+                f32x4::splat(plane.normal.x.abs()),
+                f32x4::splat(plane.normal.y.abs()),
+                f32x4::splat(plane.normal.z.abs()),
+            )
+        });
+        let centers = lanes(&bounds.center_x).zip(lanes(&bounds.center_y)).zip(lanes(&bounds.center_z));
+        let extents = lanes(&bounds.extent_x).zip(lanes(&bounds.extent_y)).zip(lanes(&bounds.extent_z));
+
+        for (chunk_index, (((cx, cy), cz), ((ex, ey), ez))) in centers.zip(extents).enumerate() {
+            // Lanes stay set while the box is on the inner side of every plane
+            let mut visible = u32::MAX;
+            for &(nx, ny, nz, d, ax, ay, az) in &planes {
+                let center = nx * cx + ny * cy + nz * cz + d;
+                let radius = ax * ex + ay * ey + az * ez;
+                visible &= (center + radius).simd_ge(f32x4::ZERO).to_bitmask();
+                if visible == 0 {
+                    break;
+                }
+            }
+
+            let start = chunk_index * LANES;
+            mask[start / 64] |= u64::from(visible & 0xF) << (start % 64);
+        }
+
+        let simd_count = count - count % LANES;
+        for index in simd_count..count {
+            if self.intersects_soa(bounds, index) {
+                mask[index / 64] |= 1 << (index % 64);
+            }
+        }
+    }
+
+    /// Scalar version of [`Frustum::cull_aabbs`]
+    pub fn cull_aabbs_scalar(&self, bounds: &AabbSoa, mask: &mut Vec<u64>) {
+        reset_mask(mask, bounds.len());
+        for index in 0..bounds.len() {
+            if self.intersects_soa(bounds, index) {
+                mask[index / 64] |= 1 << (index % 64);
+            }
+        }
+    }
+
+    /// Test one box of a batch, matching the SIMD path operation for operation
+    fn intersects_soa(&self, bounds: &AabbSoa, index: usize) -> bool {
+        let (cx, cy, cz) = (bounds.center_x[index], bounds.center_y[index], bounds.center_z[index]);
+        let (ex, ey, ez) = (bounds.extent_x[index], bounds.extent_y[index], bounds.extent_z[index]);
+        self.planes.iter().all(|plane| {
+            let n = plane.normal;
+            let center = n.x * cx + n.y * cy + n.z * cz + plane.distance;
+            let radius = n.x.abs() * ex + n.y.abs() * ey + n.z.abs() * ez;
+            center + radius >= 0.0
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::Mat4;
+
+    /// Boxes scattered deterministically around the origin
+    fn scattered_boxes(count: usize) -> Vec<Aabb> {
+        let mut state = 0x2545_f491_u32;
+        let mut next = move || {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            (state as f32 / u32::MAX as f32) * 2.0 - 1.0
+        };
+        (0..count)
+            .map(|_| {
+                let center = Vec3::new(next() * 100.0, next() * 100.0, next() * 100.0);
+                let extent = Vec3::new(next().abs() * 3.0, next().abs() * 3.0, next().abs() * 3.0);
+                Aabb::from_center_half_extents(center, extent)
+            })
+            .collect()
+    }
+
+    fn test_frustum() -> Frustum {
+        let view = Mat4::look_at_rh(Vec3::new(0.0, 0.0, 50.0), Vec3::ZERO, Vec3::Y);
+        let proj = Mat4::perspective_rh(60f32.to_radians(), 16.0 / 9.0, 0.1, 100.0);
+        Frustum::from_matrix(proj * view)
+    }
+
+    #[test]
+    fn test_batch_matches_scalar() {
+        let frustum = test_frustum();
+        // Odd count exercises the scalar tail
+        let boxes = scattered_boxes(1003);
+        let soa: AabbSoa = boxes.iter().copied().collect();
+        assert_eq!(soa.len(), 1003);
+
+        let mut simd = Vec::new();
+        let mut scalar = Vec::new();
+        frustum.cull_aabbs(&soa, &mut simd);
+        frustum.cull_aabbs_scalar(&soa, &mut scalar);
+        assert_eq!(simd, scalar);
+        assert_eq!(simd.len(), 16);
+
+        let visible: Vec<usize> = visible_indices(&simd).collect();
+        assert!(!visible.is_empty() && visible.len() < boxes.len());
+        for (index, aabb) in boxes.iter().enumerate() {
+            assert_eq!(is_visible(&simd, index), frustum.intersects_aabb(aabb), "box {}", index);
+        }
+    }
+}
@@ -0,0 +1,600 @@
+//! Curves and Splines
+//!
+//! Piecewise cubic curves for camera rails, patrol paths and animation:
+//! - Cubic Bezier, Catmull-Rom, Hermite and uniform B-spline
+//! - Works over `f32`, `Vec3` and `Quat`
+//! - Arc-length tables for constant-speed motion
+//! - Closest point queries and rotation-minimizing frames
+//! - Standard easing functions for tweens
+
+use std::ops::{Add, Mul, Sub};
+
+use super::{Quat, Vec3};
+
+/// Value a spline can interpolate
+pub trait CurveValue: Copy + Add<Output = Self> + Sub<Output = Self> + Mul<f32, Output = Self> {
+    /// Fix up a blended value (e.g. renormalize a quaternion)
+    fn finish(self) -> Self {
+        self
+    }
+}
+
+impl CurveValue for f32 {}
+
+impl CurveValue for Vec3 {}
+
+/// Quaternion controls should lie in the same hemisphere (consecutive dot products >= 0)
+impl CurveValue for Quat {
+    fn finish(self) -> Self {
+        self.normalize()
+    }
+}
+
+/// Spline type
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SplineKind {
+    /// Segments of four points sharing end points: p0 c0 c1 p1 c2 c3 p2 ...
+    CubicBezier,
+    /// Passes through every point (uniform, end points repeated)
+    CatmullRom,
+    /// Alternating points and tangents: p0 m0 p1 m1 ...
+    Hermite,
+    /// Uniform cubic B-spline, approximates the points with C2 continuity
+    BSpline,
+}
+
+impl SplineKind {
+    /// Blend weights of the segment controls and their first two derivatives at u
+    fn weights(self, u: f32) -> [[f32; 4]; 3] {
+        let (u2, u3) = (u * u, u * u * u);
+        let v = 1.0 - u;
+        match self {
+            SplineKind::CubicBezier => [
+                [v * v * v, 3.0 * u * v * v, 3.0 * u2 * v, u3],
+                [-3.0 * v * v, 3.0 - 12.0 * u + 9.0 * u2, 6.0 * u - 9.0 * u2, 3.0 * u2],
+                [6.0 * v, -12.0 + 18.0 * u, 6.0 - 18.0 * u, 6.0 * u],
+            ],
+            SplineKind::CatmullRom => [
+                [
+                    0.5 * (-u3 + 2.0 * u2 - u),
+                    0.5 * (3.0 * u3 - 5.0 * u2 + 2.0),
+                    0.5 * (-3.0 * u3 + 4.0 * u2 + u),
+                    0.5 * (u3 - u2),
+                ],
+                [
+                    0.5 * (-3.0 * u2 + 4.0 * u - 1.0),
+                    0.5 * (9.0 * u2 - 10.0 * u),
+                    0.5 * (-9.0 * u2 + 8.0 * u + 1.0),
+                    0.5 * (3.0 * u2 - 2.0 * u),
+                ],
+                [
+                    0.5 * (-6.0 * u + 4.0),
+                    0.5 * (18.0 * u - 10.0),
+                    0.5 * (-18.0 * u + 8.0),
+                    0.5 * (6.0 * u - 2.0),
+                ],
+            ],
+            // Controls are ordered p0 m0 p1 m1
+            SplineKind::Hermite => [
+                [2.0 * u3 - 3.0 * u2 + 1.0, u3 - 2.0 * u2 + u, -2.0 * u3 + 3.0 * u2, u3 - u2],
+                [6.0 * u2 - 6.0 * u, 3.0 * u2 - 4.0 * u + 1.0, -6.0 * u2 + 6.0 * u, 3.0 * u2 - 2.0 * u],
+                [12.0 * u - 6.0, 6.0 * u - 4.0, -12.0 * u + 6.0, 6.0 * u - 2.0],
+            ],
+            SplineKind::BSpline => {
+                let s = 1.0 / 6.0;
+                [
+                    [s * v * v * v, s * (3.0 * u3 - 6.0 * u2 + 4.0), s * (-3.0 * u3 + 3.0 * u2 + 3.0 * u + 1.0), s * u3],
+                    [s * -3.0 * v * v, s * (9.0 * u2 - 12.0 * u), s * (-9.0 * u2 + 6.0 * u + 3.0), s * 3.0 * u2],
+                    [s * 6.0 * v, s * (18.0 * u - 12.0), s * (-18.0 * u + 6.0), s * 6.0 * u],
+                ]
+            }
+        }
+    }
+}
+
+/// Blend four controls with weights
+fn blend<T: CurveValue>(controls: &[T; 4], weights: &[f32; 4]) -> T {
+    controls[0] * weights[0] + controls[1] * weights[1] + controls[2] * weights[2] + controls[3] * weights[3]
+}
+
+/// Piecewise cubic spline parameterized over [0, 1]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Spline<T: CurveValue> {
+    /// Spline type
+    pub kind: SplineKind,
+    /// Control points, laid out as described by the kind
+    pub points: Vec<T>,
+}
+
+impl<T: CurveValue> Spline<T> {
+    /// Create a new spline
+    pub fn new(kind: SplineKind, points: Vec<T>) -> Self {
+        Self { kind, points }
+    }
+
+    /// Get the number of cubic segments
+    pub fn segment_count(&self) -> usize {
+        let n = self.points.len();
+        match self.kind {
+            SplineKind::CubicBezier => n.saturating_sub(1) / 3,
+            SplineKind::CatmullRom => n.saturating_sub(1),
+            SplineKind::Hermite => (n / 2).saturating_sub(1),
+            SplineKind::BSpline => n.saturating_sub(3),
+        }
+    }
+
+    /// Get the four controls of a segment
+    fn segment(&self, index: usize) -> [T; 4] {
+        let p = &self.points;
+        match self.kind {
+            SplineKind::CubicBezier => [p[3 * index], p[3 * index + 1], p[3 * index + 2], p[3 * index + 3]],
+            SplineKind::CatmullRom => {
+                let last = p.len() - 1;
+                [p[index.saturating_sub(1)], p[index], p[index + 1], p[(index + 2).min(last)]]
+            }
+            SplineKind::Hermite => [p[2 * index], p[2 * index + 1], p[2 * index + 2], p[2 * index + 3]],
+            SplineKind::BSpline => [p[index], p[index + 1], p[index + 2], p[index + 3]],
+        }
+    }
+
+    /// Map a spline parameter to (segment, local parameter)
+    fn locate(&self, t: f32) -> (usize, f32) {
+        let count = self.segment_count();
+        assert!(count > 0, "Spline has no segments");
+        let scaled = t.clamp(0.0, 1.0) * count as f32;
+        let index = (scaled as usize).min(count - 1);
+        (index, scaled - index as f32)
+    }
+
+    /// Evaluate the spline at t in [0, 1]
+    ///
+    /// Panics if the spline has no segments.
+    pub fn sample(&self, t: f32) -> T {
+        let (index, u) = self.locate(t);
+        blend(&self.segment(index), &self.kind.weights(u)[0]).finish()
+    }
+
+    /// Get the derivative with respect to t
+    ///
+    /// Panics if the spline has no segments.
+    pub fn derivative(&self, t: f32) -> T {
+        let (index, u) = self.locate(t);
+        blend(&self.segment(index), &self.kind.weights(u)[1]) * self.segment_count() as f32
+    }
+
+    /// Get the second derivative with respect to t
+    ///
+    /// Panics if the spline has no segments.
+    pub fn second_derivative(&self, t: f32) -> T {
+        let (index, u) = self.locate(t);
+        let scale = self.segment_count() as f32;
+        blend(&self.segment(index), &self.kind.weights(u)[2]) * (scale * scale)
+    }
+}
+
+impl Spline<Vec3> {
+    /// Get the unit tangent at t
+    ///
+    /// Panics if the spline has no segments.
+    pub fn tangent(&self, t: f32) -> Vec3 {
+        self.derivative(t).normalize_or_zero()
+    }
+
+    /// Build an arc-length table with `samples_per_segment` chords per segment
+    ///
+    /// Panics if the spline has no segments.
+    pub fn arc_length_table(&self, samples_per_segment: usize) -> ArcLengthTable {
+        ArcLengthTable::new(self, samples_per_segment)
+    }
+
+    /// Find the parameter of the closest point on the spline
+    ///
+    /// Samples the curve coarsely, then refines with Newton iterations.
+    /// Panics if the spline has no segments.
+    pub fn closest_parameter(&self, point: Vec3, samples_per_segment: usize) -> f32 {
+        let samples = (self.segment_count() * samples_per_segment.max(1)).max(1);
+        let distance_sq = |t: f32| (self.sample(t) - point).length_squared();
+
+        let mut best = 0.0;
+        let mut best_distance = f32::MAX;
+        for i in 0..=samples {
+            let t = i as f32 / samples as f32;
+            let d = distance_sq(t);
+            if d < best_distance {
+                best = t;
+                best_distance = d;
+            }
+        }
+
+        // Newton's method on (C(t) - p) . C'(t) = 0
+        let mut t = best;
+        for _ in 0..8 {
+            let offset = self.sample(t) - point;
+            let d1 = self.derivative(t);
+            let numerator = offset.dot(d1);
+            let denominator = d1.dot(d1) + offset.dot(self.second_derivative(t));
+            if denominator.abs() < f32::EPSILON {
+                break;
+            }
+            t = (t - numerator / denominator).clamp(0.0, 1.0);
+            let d = distance_sq(t);
+            if d < best_distance {
+                best = t;
+                best_distance = d;
+            }
+        }
+        best
+    }
+
+    /// Get the closest point on the spline
+    ///
+    /// Panics if the spline has no segments.
+    pub fn closest_point(&self, point: Vec3, samples_per_segment: usize) -> Vec3 {
+        self.sample(self.closest_parameter(point, samples_per_segment))
+    }
+
+    /// Compute `count` rotation-minimizing frames spaced evenly in t
+    ///
+    /// Uses the double reflection method, so normals don't twist around the
+    /// curve the way Frenet frames do at inflection points. Panics if
+    /// `count` is non-zero and the spline has no segments.
+    pub fn rotation_minimizing_frames(&self, count: usize) -> Vec<CurveFrame> {
+        let mut frames: Vec<CurveFrame> = Vec::with_capacity(count);
+        for i in 0..count {
+            let t = if count > 1 { i as f32 / (count - 1) as f32 } else { 0.0 };
+            let position = self.sample(t);
+            let tangent = self.tangent(t);
+
+            let normal = match frames.last() {
+                None => tangent.any_orthonormal_vector(),
+                Some(prev) => {
+                    let v1 = position - prev.position;
+                    let c1 = v1.dot(v1);
+                    if c1 < f32::EPSILON {
+                        prev.normal
+                    } else {
+                        let reflected_normal = prev.normal - v1 * (2.0 / c1 * v1.dot(prev.normal));
+                        let reflected_tangent = prev.tangent - v1 * (2.0 / c1 * v1.dot(prev.tangent));
+                        let v2 = tangent - reflected_tangent;
+                        let c2 = v2.dot(v2);
+                        if c2 < f32::EPSILON {
+                            reflected_normal
+                        } else {
+                            reflected_normal - v2 * (2.0 / c2 * v2.dot(reflected_normal))
+                        }
+                    }
+                }
+            };
+
+            frames.push(CurveFrame {
+                position,
+                tangent,
+                normal,
+                binormal: tangent.cross(normal),
+            });
+        }
+        frames
+    }
+}
+
+/// Orthonormal frame along a curve
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CurveFrame {
+    /// Point on the curve
+    pub position: Vec3,
+    /// Direction of travel
+    pub tangent: Vec3,
+    /// Normal (e.g. camera up)
+    pub normal: Vec3,
+    /// Tangent cross normal
+    pub binormal: Vec3,
+}
+
+/// Cumulative arc lengths for constant-speed travel along a spline
+#[derive(Debug, Clone)]
+pub struct ArcLengthTable {
+    /// Spline parameter of each sample
+    parameters: Vec<f32>,
+    /// Distance along the curve at each sample
+    lengths: Vec<f32>,
+}
+
+impl ArcLengthTable {
+    /// Build a table with `samples_per_segment` chords per segment
+    ///
+    /// Panics if the spline has no segments.
+    pub fn new(spline: &Spline<Vec3>, samples_per_segment: usize) -> Self {
+        let samples = (spline.segment_count() * samples_per_segment.max(1)).max(1);
+        let mut parameters = Vec::with_capacity(samples + 1);
+        let mut lengths = Vec::with_capacity(samples + 1);
+
+        let mut previous = spline.sample(0.0);
+        let mut length = 0.0;
+        for i in 0..=samples {
+            let t = i as f32 / samples as f32;
+            let position = spline.sample(t);
+            length += (position - previous).length();
+            previous = position;
+            parameters.push(t);
+            lengths.push(length);
+        }
+
+        Self { parameters, lengths }
+    }
+
+    /// Get the total curve length
+    pub fn length(&self) -> f32 {
+        *self.lengths.last().unwrap()
+    }
+
+    /// Get the spline parameter at a distance along the curve
+    pub fn parameter_at_distance(&self, distance: f32) -> f32 {
+        let distance = distance.clamp(0.0, self.length());
+        let upper = self.lengths.partition_point(|&l| l < distance).clamp(1, self.lengths.len() - 1);
+        let (l0, l1) = (self.lengths[upper - 1], self.lengths[upper]);
+        let (t0, t1) = (self.parameters[upper - 1], self.parameters[upper]);
+        if (l1 - l0).abs() < f32::EPSILON {
+            t0
+        } else {
+            t0 + (t1 - t0) * (distance - l0) / (l1 - l0)
+        }
+    }
+
+    /// Get the spline parameter at a fraction (0 to 1) of the total length
+    pub fn parameter_at_fraction(&self, fraction: f32) -> f32 {
+        self.parameter_at_distance(fraction * self.length())
+    }
+
+    /// Get the distance along the curve at a spline parameter
+    pub fn distance_at_parameter(&self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        let upper = self.parameters.partition_point(|&p| p < t).clamp(1, self.parameters.len() - 1);
+        let (t0, t1) = (self.parameters[upper - 1], self.parameters[upper]);
+        let (l0, l1) = (self.lengths[upper - 1], self.lengths[upper]);
+        l0 + (l1 - l0) * (t - t0) / (t1 - t0)
+    }
+}
+
+/// Standard easing functions mapping [0, 1] to [0, 1]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Easing {
+    Linear,
+    QuadIn,
+    QuadOut,
+    QuadInOut,
+    CubicIn,
+    CubicOut,
+    CubicInOut,
+    QuartIn,
+    QuartOut,
+    QuartInOut,
+    SineIn,
+    SineOut,
+    SineInOut,
+    ExpoIn,
+    ExpoOut,
+    ExpoInOut,
+    CircIn,
+    CircOut,
+    CircInOut,
+    /// Overshoots slightly at the start
+    BackIn,
+    /// Overshoots slightly at the end
+    BackOut,
+    BackInOut,
+    ElasticIn,
+    ElasticOut,
+    BounceIn,
+    BounceOut,
+}
+
+impl Easing {
+    /// All easing functions
+    pub const ALL: [Easing; 26] = [
+        Easing::Linear,
+        Easing::QuadIn,
+        Easing::QuadOut,
+        Easing::QuadInOut,
+        Easing::CubicIn,
+        Easing::CubicOut,
+        Easing::CubicInOut,
+        Easing::QuartIn,
+        Easing::QuartOut,
+        Easing::QuartInOut,
+        Easing::SineIn,
+        Easing::SineOut,
+        Easing::SineInOut,
+        Easing::ExpoIn,
+        Easing::ExpoOut,
+        Easing::ExpoInOut,
+        Easing::CircIn,
+        Easing::CircOut,
+        Easing::CircInOut,
+        Easing::BackIn,
+        Easing::BackOut,
+        Easing::BackInOut,
+        Easing::ElasticIn,
+        Easing::ElasticOut,
+        Easing::BounceIn,
+        Easing::BounceOut,
+    ];
+
+    /// Apply the easing to t (clamped to [0, 1])
+    pub fn apply(self, t: f32) -> f32 {
+        use std::f32::consts::{FRAC_PI_2, PI};
+        const BACK: f32 = 1.70158;
+        const BACK_IN_OUT: f32 = BACK * 1.525;
+
+        let t = t.clamp(0.0, 1.0);
+        // Mirror an ease-in for the second half of an in-out curve
+        let in_out = |f: fn(f32) -> f32| if t < 0.5 { f(2.0 * t) * 0.5 } else { 1.0 - f(2.0 - 2.0 * t) * 0.5 };
+
+        match self {
+            Easing::Linear => t,
+            Easing::QuadIn => t * t,
+            Easing::QuadOut => 1.0 - (1.0 - t) * (1.0 - t),
+            Easing::QuadInOut => in_out(|x| x * x),
+            Easing::CubicIn => t * t * t,
+            Easing::CubicOut => 1.0 - (1.0 - t).powi(3),
+            Easing::CubicInOut => in_out(|x| x * x * x),
+            Easing::QuartIn => t.powi(4),
+            Easing::QuartOut => 1.0 - (1.0 - t).powi(4),
+            Easing::QuartInOut => in_out(|x| x.powi(4)),
+            Easing::SineIn => 1.0 - (t * FRAC_PI_2).cos(),
+            Easing::SineOut => (t * FRAC_PI_2).sin(),
+            Easing::SineInOut => 0.5 * (1.0 - (t * PI).cos()),
+            Easing::ExpoIn => Self::expo_in(t),
+            Easing::ExpoOut => 1.0 - Self::expo_in(1.0 - t),
+            Easing::ExpoInOut => in_out(Self::expo_in),
+            Easing::CircIn => 1.0 - (1.0 - t * t).sqrt(),
+            Easing::CircOut => (1.0 - (1.0 - t) * (1.0 - t)).sqrt(),
+            Easing::CircInOut => in_out(|x| 1.0 - (1.0 - x * x).sqrt()),
+            Easing::BackIn => t * t * ((BACK + 1.0) * t - BACK),
+            Easing::BackOut => {
+                let x = 1.0 - t;
+                1.0 - x * x * ((BACK + 1.0) * x - BACK)
+            }
+            Easing::BackInOut => in_out(|x| x * x * ((BACK_IN_OUT + 1.0) * x - BACK_IN_OUT)),
+            Easing::ElasticIn => 1.0 - Self::elastic_out(1.0 - t),
+            Easing::ElasticOut => Self::elastic_out(t),
+            Easing::BounceIn => 1.0 - Self::bounce_out(1.0 - t),
+            Easing::BounceOut => Self::bounce_out(t),
+        }
+    }
+
+    fn expo_in(t: f32) -> f32 {
+        if t <= 0.0 { 0.0 } else { 2f32.powf(10.0 * (t - 1.0)) }
+    }
+
+    fn elastic_out(t: f32) -> f32 {
+        if t <= 0.0 || t >= 1.0 {
+            return t;
+        }
+        let period = 2.0 * std::f32::consts::PI / 3.0;
+        2f32.powf(-10.0 * t) * ((t * 10.0 - 0.75) * period).sin() + 1.0
+    }
+
+    fn bounce_out(t: f32) -> f32 {
+        const N: f32 = 7.5625;
+        const D: f32 = 2.75;
+        if t < 1.0 / D {
+            N * t * t
+        } else if t < 2.0 / D {
+            let t = t - 1.5 / D;
+            N * t * t + 0.75
+        } else if t < 2.5 / D {
+            let t = t - 2.25 / D;
+            N * t * t + 0.9375
+        } else {
+            let t = t - 2.625 / D;
+            N * t * t + 0.984375
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn path() -> Vec<Vec3> {
+        vec![
+            Vec3::ZERO,
+            Vec3::new(10.0, 0.0, 0.0),
+            Vec3::new(10.0, 0.0, 10.0),
+            Vec3::new(20.0, 5.0, 10.0),
+            Vec3::new(30.0, 0.0, 0.0),
+        ]
+    }
+
+    #[test]
+    fn test_spline_kinds() {
+        let points = path();
+        let catmull = Spline::new(SplineKind::CatmullRom, points.clone());
+        assert_eq!(catmull.segment_count(), 4);
+        for (i, &p) in points.iter().enumerate() {
+            assert!((catmull.sample(i as f32 / 4.0) - p).length() < 1e-4);
+        }
+
+        let bezier = Spline::new(SplineKind::CubicBezier, vec![Vec3::ZERO, Vec3::Y, Vec3::new(1.0, 1.0, 0.0), Vec3::X]);
+        assert_eq!(bezier.sample(0.0), Vec3::ZERO);
+        assert_eq!(bezier.sample(1.0), Vec3::X);
+        assert!((bezier.tangent(0.0) - Vec3::Y).length() < 1e-5);
+
+        let hermite = Spline::new(SplineKind::Hermite, vec![0.0, 0.0, 1.0, 0.0]);
+        assert_eq!(hermite.sample(0.5), 0.5);
+        assert_eq!(hermite.derivative(0.0), 0.0);
+
+        // A B-spline over collinear, evenly spaced points is a straight line
+        let bspline = Spline::new(SplineKind::BSpline, vec![0.0, 1.0, 2.0, 3.0, 4.0]);
+        assert_eq!(bspline.segment_count(), 2);
+        assert!((bspline.sample(0.0) - 1.0).abs() < 1e-5);
+        assert!((bspline.sample(0.75) - 2.5).abs() < 1e-5);
+
+        let rotations = Spline::new(
+            SplineKind::CatmullRom,
+            vec![Quat::IDENTITY, Quat::from_rotation_y(1.0), Quat::from_rotation_y(2.0)],
+        );
+        let q = rotations.sample(0.25);
+        assert!((q.length() - 1.0).abs() < 1e-5);
+        assert!(q.angle_between(Quat::from_rotation_y(0.5)) < 0.1);
+    }
+
+    #[test]
+    fn test_empty_spline_panics() {
+        let empty = Spline::new(SplineKind::CatmullRom, vec![Vec3::ZERO]);
+        assert_eq!(empty.segment_count(), 0);
+        fn message<T>(result: std::thread::Result<T>) -> &'static str {
+            *result.err().unwrap().downcast::<&str>().unwrap()
+        }
+        assert_eq!(message(std::panic::catch_unwind(|| empty.sample(0.5))), "Spline has no segments");
+        assert_eq!(message(std::panic::catch_unwind(|| empty.closest_parameter(Vec3::ONE, 4))), "Spline has no segments");
+        assert!(empty.rotation_minimizing_frames(0).is_empty());
+    }
+
+    #[test]
+    fn test_arc_length_constant_speed() {
+        // Bunched control points make t non-uniform along the curve
+        let spline = Spline::new(SplineKind::CubicBezier, vec![Vec3::ZERO, Vec3::splat(0.1), Vec3::splat(0.2), Vec3::new(10.0, 0.0, 0.0)]);
+        let table = spline.arc_length_table(64);
+
+        let steps = 10;
+        let step_length = table.length() / steps as f32;
+        let mut previous = spline.sample(0.0);
+        for i in 1..=steps {
+            let t = table.parameter_at_distance(i as f32 * step_length);
+            let position = spline.sample(t);
+            assert!(((position - previous).length() - step_length).abs() < step_length * 0.02);
+            assert!((table.distance_at_parameter(t) - i as f32 * step_length).abs() < 1e-3);
+            previous = position;
+        }
+    }
+
+    #[test]
+    fn test_closest_point_and_frames() {
+        let spline = Spline::new(SplineKind::CatmullRom, path());
+        let target = spline.sample(0.6);
+        let query = target + spline.tangent(0.6).any_orthonormal_vector() * 0.5;
+        let t = spline.closest_parameter(query, 8);
+        assert!((spline.sample(t) - query).length() <= 0.5 + 1e-3);
+        assert!((t - 0.6).abs() < 0.01);
+
+        let frames = spline.rotation_minimizing_frames(64);
+        for pair in frames.windows(2) {
+            let frame = pair[1];
+            assert!(frame.tangent.dot(frame.normal).abs() < 1e-2);
+            assert!((frame.normal.length() - 1.0).abs() < 1e-2);
+            // Normals turn smoothly between neighbouring frames
+            assert!(pair[0].normal.dot(frame.normal) > 0.9);
+        }
+    }
+
+    #[test]
+    fn test_easing_end_points() {
+        for easing in Easing::ALL {
+            assert!(easing.apply(0.0).abs() < 1e-5, "{:?}", easing);
+            assert!((easing.apply(1.0) - 1.0).abs() < 1e-5, "{:?}", easing);
+        }
+        assert_eq!(Easing::QuadInOut.apply(0.5), 0.5);
+        assert!(Easing::BackIn.apply(0.2) < 0.0);
+    }
+}
@@ -0,0 +1,1002 @@
+//! Fixed-Point Math
+//!
+//! Deterministic arithmetic for lockstep simulation:
+//! - `Fixed32` (Q16.16) and `Fixed64` (Q32.32) scalars
+//! - Vector, quaternion, AABB, ray and transform types mirroring the float API
+//! - Trig and sqrt computed with integer arithmetic only
+//! - Conversions to and from `glam` for rendering
+//!
+//! Every operation is integer-based, so results are bit-identical on every
+//! platform. Arithmetic overflow behaves like integer math: it panics in
+//! debug builds and wraps in release builds. Products and quotients are
+//! computed at double width and checked when narrowed back, and vector
+//! lengths are accumulated at double width so only the final result has to fit.
+
+use std::fmt;
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+use serde::{Deserialize, Serialize};
+
+use super::{Aabb, Quat, Ray, Vec3};
+use crate::scene::Transform;
+
+/// Q32.32 one, the precision trig is evaluated at
+const Q_ONE: i64 = 1 << 32;
+const Q_PI: i64 = 13_493_037_705;
+const Q_HALF_PI: i64 = 6_746_518_852;
+const Q_QUARTER_PI: i64 = 3_373_259_426;
+const Q_TAU: i64 = 26_986_075_409;
+const Q_TAN_PI_8: i64 = 1_779_033_704;
+
+fn q_mul(a: i64, b: i64) -> i64 {
+    ((a as i128 * b as i128) >> 32) as i64
+}
+
+fn q_div(a: i64, b: i64) -> i64 {
+    (((a as i128) << 32) / b as i128) as i64
+}
+
+/// Sine of a Q32.32 angle
+fn q_sin(angle: i64) -> i64 {
+    // Reduce to (-pi, pi], then fold into [-pi/2, pi/2]
+    let mut x = angle.rem_euclid(Q_TAU);
+    if x > Q_PI {
+        x -= Q_TAU;
+    }
+    if x > Q_HALF_PI {
+        x = Q_PI - x;
+    } else if x < -Q_HALF_PI {
+        x = -Q_PI - x;
+    }
+
+    // Taylor series to x^13 in Horner form
+    let x2 = q_mul(x, x);
+    let mut r = Q_ONE;
+    for d in [156, 110, 72, 42, 20, 6] {
+        r = Q_ONE - q_mul(x2, r) / d;
+    }
+    q_mul(x, r)
+}
+
+/// Arctangent of a Q32.32 value in [0, 1]
+fn q_atan_unit(t: i64) -> i64 {
+    // atan(t) = pi/4 + atan((t - 1) / (t + 1)) keeps the series argument small
+    let (offset, t) = if t > Q_TAN_PI_8 {
+        (Q_QUARTER_PI, q_div(t - Q_ONE, t + Q_ONE))
+    } else {
+        (0, t)
+    };
+    let t2 = q_mul(t, t);
+    let mut r = 0;
+    for k in (0..8).rev() {
+        r = Q_ONE / (2 * k + 1) - q_mul(t2, r);
+    }
+    offset + q_mul(t, r)
+}
+
+/// Four-quadrant arctangent of Q32.32 values
+fn q_atan2(y: i64, x: i64) -> i64 {
+    if x == 0 && y == 0 {
+        return 0;
+    }
+    let (ax, ay) = (x.abs(), y.abs());
+    let mut angle = if ay <= ax {
+        q_atan_unit(q_div(ay, ax))
+    } else {
+        Q_HALF_PI - q_atan_unit(q_div(ax, ay))
+    };
+    if x < 0 {
+        angle = Q_PI - angle;
+    }
+    if y < 0 { -angle } else { angle }
+}
+
+/// Scalar usable by the fixed-point vector types
+pub trait Fixed:
+    Copy
+    + Ord
+    + Default
+    + fmt::Debug
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Neg<Output = Self>
+    + AddAssign
+    + SubAssign
+{
+    const ZERO: Self;
+    const ONE: Self;
+    const HALF: Self;
+
+    /// Convert from a float (rounded to nearest)
+    fn from_f32(value: f32) -> Self;
+    /// Convert to a float
+    fn to_f32(self) -> f32;
+    /// Absolute value
+    fn abs(self) -> Self;
+    /// Square root (zero for negative values)
+    fn sqrt(self) -> Self;
+    /// Sine and cosine of an angle in radians
+    fn sin_cos(self) -> (Self, Self);
+    /// Sum of squares of the components, accumulated at double width
+    fn length_squared_of(components: &[Self]) -> Self;
+    /// Square root of the sum of squares of the components, accumulated at double width
+    fn length_of(components: &[Self]) -> Self;
+}
+
+macro_rules! fixed_scalar {
+    ($(#[$meta:meta])* $name:ident, $raw:ty, $wide:ty, $frac:expr) => {
+        $(#[$meta])*
+        #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize)]
+        pub struct $name($raw);
+
+        impl $name {
+            /// Number of fractional bits
+            pub const FRAC_BITS: u32 = $frac;
+            pub const ZERO: Self = Self(0);
+            pub const ONE: Self = Self(1 << $frac);
+            pub const HALF: Self = Self(1 << ($frac - 1));
+            /// Smallest positive value
+            pub const EPSILON: Self = Self(1);
+            pub const MIN: Self = Self(<$raw>::MIN);
+            pub const MAX: Self = Self(<$raw>::MAX);
+            pub const PI: Self = Self::from_q32(Q_PI);
+            pub const FRAC_PI_2: Self = Self::from_q32(Q_HALF_PI);
+            pub const TAU: Self = Self::from_q32(Q_TAU);
+
+            /// Create from the raw bit representation
+            pub const fn from_raw(raw: $raw) -> Self {
+                Self(raw)
+            }
+
+            /// Get the raw bit representation
+            pub const fn to_raw(self) -> $raw {
+                self.0
+            }
+
+            /// Create from an integer
+            pub const fn from_int(value: i32) -> Self {
+                Self((value as $raw) << $frac)
+            }
+
+            /// Convert from a float (rounded to nearest)
+            pub fn from_f32(value: f32) -> Self {
+                Self::from_f64(value as f64)
+            }
+
+            /// Convert from a double (rounded to nearest)
+            pub fn from_f64(value: f64) -> Self {
+                Self((value * (1u64 << $frac) as f64).round() as $raw)
+            }
+
+            /// Convert to a float
+            pub fn to_f32(self) -> f32 {
+                self.to_f64() as f32
+            }
+
+            /// Convert to a double
+            pub fn to_f64(self) -> f64 {
+                self.0 as f64 / (1u64 << $frac) as f64
+            }
+
+            /// Convert from Q32.32 (rounded to nearest)
+            const fn from_q32(q: i64) -> Self {
+                let shift = 32 - $frac;
+                let half = (1i64 << shift) >> 1;
+                Self(((q + half) >> shift) as $raw)
+            }
+
+            /// Convert to Q32.32
+            fn to_q32(self) -> i64 {
+                (self.0 as i64) << (32 - $frac)
+            }
+
+            /// Narrow a double-width raw value, panicking on overflow in debug
+            /// builds and wrapping in release builds like integer arithmetic
+            fn from_wide(value: $wide) -> Self {
+                match <$raw>::try_from(value) {
+                    Ok(raw) => Self(raw),
+                    Err(_) if cfg!(debug_assertions) => panic!("{} arithmetic overflow", stringify!($name)),
+                    Err(_) => Self(value as $raw),
+                }
+            }
+
+            /// Sum of the squared raw components (saturating, never reached in practice)
+            fn raw_sum_squares(components: &[Self]) -> u128 {
+                components
+                    .iter()
+                    .fold(0u128, |sum, c| sum.saturating_add((c.0.unsigned_abs() as u128).pow(2)))
+            }
+
+            /// Absolute value
+            pub fn abs(self) -> Self {
+                Self(self.0.abs())
+            }
+
+            /// Largest integer value less than or equal to self
+            pub fn floor(self) -> Self {
+                Self(self.0 & !((1 << $frac) - 1))
+            }
+
+            /// Smallest integer value greater than or equal to self
+            pub fn ceil(self) -> Self {
+                (self + Self(((1 as $raw) << $frac) - 1)).floor()
+            }
+
+            /// Fractional part (always non-negative)
+            pub fn fract(self) -> Self {
+                self - self.floor()
+            }
+
+            /// Sign as -1, 0 or 1
+            pub fn signum(self) -> Self {
+                Self::from_int(self.0.signum() as i32)
+            }
+
+            /// Linear interpolation
+            pub fn lerp(self, other: Self, t: Self) -> Self {
+                self + (other - self) * t
+            }
+
+            /// Square root (zero for negative values)
+            pub fn sqrt(self) -> Self {
+                if self.0 <= 0 {
+                    return Self::ZERO;
+                }
+                Self(((self.0 as u128) << $frac).isqrt() as $raw)
+            }
+
+            /// Sine of an angle in radians
+            pub fn sin(self) -> Self {
+                Self::from_q32(q_sin(self.to_q32()))
+            }
+
+            /// Cosine of an angle in radians
+            pub fn cos(self) -> Self {
+                Self::from_q32(q_sin(self.to_q32() + Q_HALF_PI))
+            }
+
+            /// Sine and cosine of an angle in radians
+            pub fn sin_cos(self) -> (Self, Self) {
+                (self.sin(), self.cos())
+            }
+
+            /// Four-quadrant arctangent of self (y) and x, in radians
+            pub fn atan2(self, x: Self) -> Self {
+                Self::from_q32(q_atan2(self.to_q32(), x.to_q32()))
+            }
+        }
+
+        impl Fixed for $name {
+            const ZERO: Self = Self::ZERO;
+            const ONE: Self = Self::ONE;
+            const HALF: Self = Self::HALF;
+
+            fn from_f32(value: f32) -> Self {
+                Self::from_f32(value)
+            }
+
+            fn to_f32(self) -> f32 {
+                self.to_f32()
+            }
+
+            fn abs(self) -> Self {
+                self.abs()
+            }
+
+            fn sqrt(self) -> Self {
+                self.sqrt()
+            }
+
+            fn sin_cos(self) -> (Self, Self) {
+                self.sin_cos()
+            }
+
+            fn length_squared_of(components: &[Self]) -> Self {
+                Self::from_wide((Self::raw_sum_squares(components) >> $frac) as $wide)
+            }
+
+            fn length_of(components: &[Self]) -> Self {
+                Self::from_wide(Self::raw_sum_squares(components).isqrt() as $wide)
+            }
+        }
+
+        impl Add for $name {
+            type Output = Self;
+            fn add(self, rhs: Self) -> Self {
+                Self(self.0 + rhs.0)
+            }
+        }
+
+        impl Sub for $name {
+            type Output = Self;
+            fn sub(self, rhs: Self) -> Self {
+                Self(self.0 - rhs.0)
+            }
+        }
+
+        impl Mul for $name {
+            type Output = Self;
+            fn mul(self, rhs: Self) -> Self {
+                Self::from_wide((self.0 as $wide * rhs.0 as $wide) >> $frac)
+            }
+        }
+
+        impl Div for $name {
+            type Output = Self;
+            /// Panics when dividing by zero
+            fn div(self, rhs: Self) -> Self {
+                Self::from_wide(((self.0 as $wide) << $frac) / rhs.0 as $wide)
+            }
+        }
+
+        impl Neg for $name {
+            type Output = Self;
+            fn neg(self) -> Self {
+                Self(-self.0)
+            }
+        }
+
+        impl AddAssign for $name {
+            fn add_assign(&mut self, rhs: Self) {
+                *self = *self + rhs;
+            }
+        }
+
+        impl SubAssign for $name {
+            fn sub_assign(&mut self, rhs: Self) {
+                *self = *self - rhs;
+            }
+        }
+
+        impl MulAssign for $name {
+            fn mul_assign(&mut self, rhs: Self) {
+                *self = *self * rhs;
+            }
+        }
+
+        impl DivAssign for $name {
+            fn div_assign(&mut self, rhs: Self) {
+                *self = *self / rhs;
+            }
+        }
+
+        impl From<i32> for $name {
+            fn from(value: i32) -> Self {
+                Self::from_int(value)
+            }
+        }
+
+        impl fmt::Debug for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "{}({})", stringify!($name), self.to_f64())
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                fmt::Display::fmt(&self.to_f64(), f)
+            }
+        }
+    };
+}
+
+fixed_scalar!(
+    /// Q16.16 fixed-point number (range about ±32768, precision 1.5e-5)
+    Fixed32, i32, i64, 16
+);
+
+fixed_scalar!(
+    /// Q32.32 fixed-point number (range about ±2.1e9, precision 2.3e-10)
+    Fixed64, i64, i128, 32
+);
+
+/// Fixed-point 3D vector
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub struct FixedVec3<T = Fixed64> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+impl<T: Fixed> FixedVec3<T> {
+    pub const ZERO: Self = Self::splat(T::ZERO);
+    pub const ONE: Self = Self::splat(T::ONE);
+    pub const X: Self = Self::new(T::ONE, T::ZERO, T::ZERO);
+    pub const Y: Self = Self::new(T::ZERO, T::ONE, T::ZERO);
+    pub const Z: Self = Self::new(T::ZERO, T::ZERO, T::ONE);
+
+    /// Create a new vector
+    pub const fn new(x: T, y: T, z: T) -> Self {
+        Self { x, y, z }
+    }
+
+    /// Create a vector with all components equal
+    pub const fn splat(value: T) -> Self {
+        Self::new(value, value, value)
+    }
+
+    /// Convert from a float vector
+    pub fn from_vec3(v: Vec3) -> Self {
+        Self::new(T::from_f32(v.x), T::from_f32(v.y), T::from_f32(v.z))
+    }
+
+    /// Convert to a float vector
+    pub fn to_vec3(self) -> Vec3 {
+        Vec3::new(self.x.to_f32(), self.y.to_f32(), self.z.to_f32())
+    }
+
+    /// Dot product
+    pub fn dot(self, other: Self) -> T {
+        self.x * other.x + self.y * other.y + self.z * other.z
+    }
+
+    /// Cross product
+    pub fn cross(self, other: Self) -> Self {
+        Self::new(
+            self.y * other.z - self.z * other.y,
+            self.z * other.x - self.x * other.z,
+            self.x * other.y - self.y * other.x,
+        )
+    }
+
+    /// Squared length
+    pub fn length_squared(self) -> T {
+        T::length_squared_of(&[self.x, self.y, self.z])
+    }
+
+    /// Length
+    pub fn length(self) -> T {
+        T::length_of(&[self.x, self.y, self.z])
+    }
+
+    /// Distance to another point
+    pub fn distance(self, other: Self) -> T {
+        (other - self).length()
+    }
+
+    /// Unit vector in the same direction, or zero for a zero vector
+    pub fn normalize_or_zero(self) -> Self {
+        let length = self.length();
+        if length == T::ZERO { Self::ZERO } else { self / length }
+    }
+
+    /// Component-wise minimum
+    pub fn min(self, other: Self) -> Self {
+        Self::new(self.x.min(other.x), self.y.min(other.y), self.z.min(other.z))
+    }
+
+    /// Component-wise maximum
+    pub fn max(self, other: Self) -> Self {
+        Self::new(self.x.max(other.x), self.y.max(other.y), self.z.max(other.z))
+    }
+
+    /// Component-wise product
+    pub fn mul_elements(self, other: Self) -> Self {
+        Self::new(self.x * other.x, self.y * other.y, self.z * other.z)
+    }
+
+    /// Linear interpolation
+    pub fn lerp(self, other: Self, t: T) -> Self {
+        self + (other - self) * t
+    }
+}
+
+impl<T: Fixed> Add for FixedVec3<T> {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        Self::new(self.x + rhs.x, self.y + rhs.y, self.z + rhs.z)
+    }
+}
+
+impl<T: Fixed> Sub for FixedVec3<T> {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self {
+        Self::new(self.x - rhs.x, self.y - rhs.y, self.z - rhs.z)
+    }
+}
+
+impl<T: Fixed> Mul<T> for FixedVec3<T> {
+    type Output = Self;
+    fn mul(self, rhs: T) -> Self {
+        Self::new(self.x * rhs, self.y * rhs, self.z * rhs)
+    }
+}
+
+impl<T: Fixed> Div<T> for FixedVec3<T> {
+    type Output = Self;
+    fn div(self, rhs: T) -> Self {
+        Self::new(self.x / rhs, self.y / rhs, self.z / rhs)
+    }
+}
+
+impl<T: Fixed> Neg for FixedVec3<T> {
+    type Output = Self;
+    fn neg(self) -> Self {
+        Self::new(-self.x, -self.y, -self.z)
+    }
+}
+
+impl<T: Fixed> AddAssign for FixedVec3<T> {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl<T: Fixed> SubAssign for FixedVec3<T> {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl<T: Fixed> From<Vec3> for FixedVec3<T> {
+    fn from(v: Vec3) -> Self {
+        Self::from_vec3(v)
+    }
+}
+
+impl<T: Fixed> From<FixedVec3<T>> for Vec3 {
+    fn from(v: FixedVec3<T>) -> Self {
+        v.to_vec3()
+    }
+}
+
+/// Fixed-point rotation quaternion
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct FixedQuat<T = Fixed64> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+    pub w: T,
+}
+
+impl<T: Fixed> FixedQuat<T> {
+    pub const IDENTITY: Self = Self::from_xyzw(T::ZERO, T::ZERO, T::ZERO, T::ONE);
+
+    /// Create from components
+    pub const fn from_xyzw(x: T, y: T, z: T, w: T) -> Self {
+        Self { x, y, z, w }
+    }
+
+    /// Create a rotation around a unit axis
+    pub fn from_axis_angle(axis: FixedVec3<T>, angle: T) -> Self {
+        let (sin, cos) = (angle * T::HALF).sin_cos();
+        Self::from_xyzw(axis.x * sin, axis.y * sin, axis.z * sin, cos)
+    }
+
+    /// Create a rotation around the X axis
+    pub fn from_rotation_x(angle: T) -> Self {
+        Self::from_axis_angle(FixedVec3::X, angle)
+    }
+
+    /// Create a rotation around the Y axis
+    pub fn from_rotation_y(angle: T) -> Self {
+        Self::from_axis_angle(FixedVec3::Y, angle)
+    }
+
+    /// Create a rotation around the Z axis
+    pub fn from_rotation_z(angle: T) -> Self {
+        Self::from_axis_angle(FixedVec3::Z, angle)
+    }
+
+    /// Convert from a float quaternion
+    pub fn from_quat(q: Quat) -> Self {
+        Self::from_xyzw(T::from_f32(q.x), T::from_f32(q.y), T::from_f32(q.z), T::from_f32(q.w))
+    }
+
+    /// Convert to a float quaternion
+    pub fn to_quat(self) -> Quat {
+        Quat::from_xyzw(self.x.to_f32(), self.y.to_f32(), self.z.to_f32(), self.w.to_f32())
+    }
+
+    fn vector(self) -> FixedVec3<T> {
+        FixedVec3::new(self.x, self.y, self.z)
+    }
+
+    /// Dot product
+    pub fn dot(self, other: Self) -> T {
+        self.x * other.x + self.y * other.y + self.z * other.z + self.w * other.w
+    }
+
+    /// Length
+    pub fn length(self) -> T {
+        T::length_of(&[self.x, self.y, self.z, self.w])
+    }
+
+    /// Rescale to unit length, correcting accumulated rounding
+    pub fn normalize(self) -> Self {
+        let length = self.length();
+        if length == T::ZERO {
+            return Self::IDENTITY;
+        }
+        Self::from_xyzw(self.x / length, self.y / length, self.z / length, self.w / length)
+    }
+
+    /// Inverse of a unit quaternion
+    pub fn conjugate(self) -> Self {
+        Self::from_xyzw(-self.x, -self.y, -self.z, self.w)
+    }
+
+    /// Rotate a vector
+    pub fn mul_vec3(self, v: FixedVec3<T>) -> FixedVec3<T> {
+        // v + 2w(q x v) + 2(q x (q x v))
+        let q = self.vector();
+        let t = q.cross(v);
+        let t = t + t;
+        v + t * self.w + q.cross(t)
+    }
+
+    /// Normalized linear interpolation along the shortest arc
+    pub fn nlerp(self, other: Self, t: T) -> Self {
+        let other = if self.dot(other) < T::ZERO { -other } else { other };
+        Self::from_xyzw(
+            self.x + (other.x - self.x) * t,
+            self.y + (other.y - self.y) * t,
+            self.z + (other.z - self.z) * t,
+            self.w + (other.w - self.w) * t,
+        )
+        .normalize()
+    }
+}
+
+impl<T: Fixed> Mul for FixedQuat<T> {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self {
+        Self::from_xyzw(
+            self.w * rhs.x + self.x * rhs.w + self.y * rhs.z - self.z * rhs.y,
+            self.w * rhs.y - self.x * rhs.z + self.y * rhs.w + self.z * rhs.x,
+            self.w * rhs.z + self.x * rhs.y - self.y * rhs.x + self.z * rhs.w,
+            self.w * rhs.w - self.x * rhs.x - self.y * rhs.y - self.z * rhs.z,
+        )
+    }
+}
+
+impl<T: Fixed> Mul<FixedVec3<T>> for FixedQuat<T> {
+    type Output = FixedVec3<T>;
+    fn mul(self, rhs: FixedVec3<T>) -> FixedVec3<T> {
+        self.mul_vec3(rhs)
+    }
+}
+
+impl<T: Fixed> Neg for FixedQuat<T> {
+    type Output = Self;
+    fn neg(self) -> Self {
+        Self::from_xyzw(-self.x, -self.y, -self.z, -self.w)
+    }
+}
+
+impl<T: Fixed> Default for FixedQuat<T> {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+/// Fixed-point axis-aligned bounding box
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub struct FixedAabb<T = Fixed64> {
+    pub min: FixedVec3<T>,
+    pub max: FixedVec3<T>,
+}
+
+impl<T: Fixed> FixedAabb<T> {
+    /// Create a new AABB
+    pub fn new(min: FixedVec3<T>, max: FixedVec3<T>) -> Self {
+        Self { min, max }
+    }
+
+    /// Create an AABB from center and half extents
+    pub fn from_center_half_extents(center: FixedVec3<T>, half_extents: FixedVec3<T>) -> Self {
+        Self::new(center - half_extents, center + half_extents)
+    }
+
+    /// Convert from a float AABB
+    pub fn from_aabb(aabb: &Aabb) -> Self {
+        Self::new(aabb.min.into(), aabb.max.into())
+    }
+
+    /// Convert to a float AABB
+    pub fn to_aabb(&self) -> Aabb {
+        Aabb::new(self.min.into(), self.max.into())
+    }
+
+    /// Get the center
+    pub fn center(&self) -> FixedVec3<T> {
+        (self.min + self.max) * T::HALF
+    }
+
+    /// Get the half extents
+    pub fn half_extents(&self) -> FixedVec3<T> {
+        (self.max - self.min) * T::HALF
+    }
+
+    /// Get the size
+    pub fn size(&self) -> FixedVec3<T> {
+        self.max - self.min
+    }
+
+    /// Check if a point is inside
+    pub fn contains_point(&self, point: FixedVec3<T>) -> bool {
+        point.x >= self.min.x && point.x <= self.max.x
+            && point.y >= self.min.y && point.y <= self.max.y
+            && point.z >= self.min.z && point.z <= self.max.z
+    }
+
+    /// Check if this AABB intersects another
+    pub fn intersects(&self, other: &Self) -> bool {
+        self.min.x <= other.max.x && self.max.x >= other.min.x
+            && self.min.y <= other.max.y && self.max.y >= other.min.y
+            && self.min.z <= other.max.z && self.max.z >= other.min.z
+    }
+
+    /// Expand to include a point
+    pub fn expand_to_include(&mut self, point: FixedVec3<T>) {
+        self.min = self.min.min(point);
+        self.max = self.max.max(point);
+    }
+
+    /// Merge with another AABB
+    pub fn merge(&self, other: &Self) -> Self {
+        Self::new(self.min.min(other.min), self.max.max(other.max))
+    }
+}
+
+/// Fixed-point ray
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FixedRay<T = Fixed64> {
+    /// Ray origin
+    pub origin: FixedVec3<T>,
+    /// Ray direction (normalized)
+    pub direction: FixedVec3<T>,
+}
+
+impl<T: Fixed> FixedRay<T> {
+    /// Create a new ray
+    pub fn new(origin: FixedVec3<T>, direction: FixedVec3<T>) -> Self {
+        Self {
+            origin,
+            direction: direction.normalize_or_zero(),
+        }
+    }
+
+    /// Convert from a float ray
+    pub fn from_ray(ray: &Ray) -> Self {
+        Self::new(ray.origin.into(), ray.direction.into())
+    }
+
+    /// Get a point along the ray at distance t
+    pub fn at(&self, t: T) -> FixedVec3<T> {
+        self.origin + self.direction * t
+    }
+
+    /// Intersect with an AABB, returns (t_min, t_max) if hit
+    pub fn intersect_aabb(&self, aabb: &FixedAabb<T>) -> Option<(T, T)> {
+        let axes = [
+            (self.origin.x, self.direction.x, aabb.min.x, aabb.max.x),
+            (self.origin.y, self.direction.y, aabb.min.y, aabb.max.y),
+            (self.origin.z, self.direction.z, aabb.min.z, aabb.max.z),
+        ];
+
+        let mut t_enter: Option<T> = None;
+        let mut t_exit: Option<T> = None;
+        for (origin, direction, min, max) in axes {
+            if direction == T::ZERO {
+                // Parallel to the slab: must start inside it
+                if origin < min || origin > max {
+                    return None;
+                }
+                continue;
+            }
+            let t1 = (min - origin) / direction;
+            let t2 = (max - origin) / direction;
+            let (near, far) = if t1 <= t2 { (t1, t2) } else { (t2, t1) };
+            t_enter = Some(t_enter.map_or(near, |t| t.max(near)));
+            t_exit = Some(t_exit.map_or(far, |t| t.min(far)));
+        }
+
+        match (t_enter, t_exit) {
+            (Some(enter), Some(exit)) if enter <= exit && exit >= T::ZERO => Some((enter.max(T::ZERO), exit)),
+            (Some(_), Some(_)) => None,
+            // Zero direction: hit only if the origin is inside
+            _ => Some((T::ZERO, T::ZERO)),
+        }
+    }
+}
+
+/// Fixed-point transform mirroring [`Transform`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct FixedTransform<T = Fixed64> {
+    pub position: FixedVec3<T>,
+    pub rotation: FixedQuat<T>,
+    pub scale: FixedVec3<T>,
+}
+
+impl<T: Fixed> FixedTransform<T> {
+    pub const IDENTITY: Self = Self {
+        position: FixedVec3::ZERO,
+        rotation: FixedQuat::IDENTITY,
+        scale: FixedVec3::ONE,
+    };
+
+    /// Create a new transform
+    pub fn new(position: FixedVec3<T>, rotation: FixedQuat<T>, scale: FixedVec3<T>) -> Self {
+        Self {
+            position,
+            rotation,
+            scale,
+        }
+    }
+
+    /// Create a transform from position only
+    pub fn from_position(position: FixedVec3<T>) -> Self {
+        Self {
+            position,
+            ..Self::IDENTITY
+        }
+    }
+
+    /// Convert from a float transform
+    pub fn from_transform(transform: &Transform) -> Self {
+        Self::new(
+            transform.position.into(),
+            FixedQuat::from_quat(transform.rotation),
+            transform.scale.into(),
+        )
+    }
+
+    /// Convert to a float transform for rendering
+    pub fn to_transform(&self) -> Transform {
+        Transform::new(self.position.into(), self.rotation.to_quat(), self.scale.into())
+    }
+
+    /// Get the forward direction (negative Z in local space)
+    pub fn forward(&self) -> FixedVec3<T> {
+        self.rotation * -FixedVec3::Z
+    }
+
+    /// Get the right direction (positive X in local space)
+    pub fn right(&self) -> FixedVec3<T> {
+        self.rotation * FixedVec3::X
+    }
+
+    /// Get the up direction (positive Y in local space)
+    pub fn up(&self) -> FixedVec3<T> {
+        self.rotation * FixedVec3::Y
+    }
+
+    /// Translate the transform
+    pub fn translate(&mut self, delta: FixedVec3<T>) {
+        self.position += delta;
+    }
+
+    /// Transform a point from local to parent space
+    pub fn transform_point(&self, point: FixedVec3<T>) -> FixedVec3<T> {
+        self.position + self.rotation * self.scale.mul_elements(point)
+    }
+
+    /// Interpolate between two transforms (rotation uses nlerp)
+    pub fn lerp(&self, other: &Self, t: T) -> Self {
+        Self {
+            position: self.position.lerp(other.position, t),
+            rotation: self.rotation.nlerp(other.rotation, t),
+            scale: self.scale.lerp(other.scale, t),
+        }
+    }
+
+    /// Compose with a child transform (`self` is the parent)
+    pub fn mul_transform(&self, child: &Self) -> Self {
+        Self {
+            position: self.transform_point(child.position),
+            rotation: self.rotation * child.rotation,
+            scale: self.scale.mul_elements(child.scale),
+        }
+    }
+}
+
+impl<T: Fixed> Default for FixedTransform<T> {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Raw results of the computations in `test_fixed_golden_values`
+    const GOLDEN_64: [i64; 5] = [6_074_000_999, 3_614_090_361, 2_320_580_734, -8_737_870_172, -2_174_823_868];
+    const GOLDEN_32: [i32; 5] = [92_681, 55_147, 35_409, -133_329, 152_917];
+    const GOLDEN_ROTATED: [i64; 3] = [-1, 8_589_934_592, -4_294_967_297];
+
+    #[test]
+    fn test_fixed_golden_values() {
+        // Raw outputs pinned so any platform or refactor divergence fails here
+        let two = Fixed64::from_int(2);
+        let one = Fixed64::ONE;
+        let raw64 = [
+            two.sqrt().to_raw(),
+            one.sin().to_raw(),
+            one.cos().to_raw(),
+            (-two).atan2(-one).to_raw(),
+            Fixed64::from_int(100).sin().to_raw(),
+        ];
+        assert_eq!(raw64, GOLDEN_64);
+
+        let raw32 = [
+            Fixed32::from_int(2).sqrt().to_raw(),
+            Fixed32::ONE.sin().to_raw(),
+            Fixed32::ONE.cos().to_raw(),
+            Fixed32::from_int(-2).atan2(-Fixed32::ONE).to_raw(),
+            (Fixed32::from_int(7) / Fixed32::from_int(3)).to_raw(),
+        ];
+        assert_eq!(raw32, GOLDEN_32);
+
+        let rotated = FixedQuat::from_rotation_y(Fixed64::FRAC_PI_2) * FixedVec3::new(one, two, Fixed64::ZERO);
+        assert_eq!([rotated.x.to_raw(), rotated.y.to_raw(), rotated.z.to_raw()], GOLDEN_ROTATED);
+    }
+
+    #[test]
+    fn test_fixed_accuracy() {
+        for i in -400..400 {
+            let angle = i as f64 * 0.0431;
+            let fixed = Fixed64::from_f64(angle);
+            assert!((fixed.sin().to_f64() - angle.sin()).abs() < 1e-8, "sin {}", angle);
+            assert!((fixed.cos().to_f64() - angle.cos()).abs() < 1e-8, "cos {}", angle);
+            let fixed = Fixed32::from_f64(angle);
+            assert!((fixed.sin().to_f64() - fixed.to_f64().sin()).abs() < 1e-4, "sin32 {}", angle);
+
+            let (y, x) = (angle.sin() * 3.0, (angle * 1.7).cos() * 2.0);
+            let atan = Fixed64::from_f64(y).atan2(Fixed64::from_f64(x)).to_f64();
+            assert!((atan - y.atan2(x)).abs() < 1e-7, "atan2 {} {}", y, x);
+
+            let value = (i + 400) as f64 * 0.37;
+            assert!((Fixed64::from_f64(value).sqrt().to_f64() - value.sqrt()).abs() < 1e-8);
+        }
+    }
+
+    #[test]
+    fn test_fixed_overflow() {
+        // Lengths are accumulated at double width, so only the result has to fit
+        let long = FixedVec3::new(Fixed32::from_int(200), Fixed32::ZERO, Fixed32::ZERO);
+        assert_eq!(long.length(), Fixed32::from_int(200));
+        let long = FixedVec3::new(Fixed32::from_int(300), Fixed32::from_int(400), Fixed32::ZERO);
+        assert_eq!(long.length(), Fixed32::from_int(500));
+        assert!((long.normalize_or_zero().x.to_f64() - 0.6).abs() < 1e-4);
+        let half = FixedVec3::splat(Fixed32::from_int(100));
+        assert_eq!(half.length_squared(), Fixed32::from_int(30_000));
+
+        // Results that don't fit panic in debug builds instead of wrapping
+        if cfg!(debug_assertions) {
+            let big = Fixed32::from_int(300);
+            assert!(std::panic::catch_unwind(|| big * big).is_err());
+            assert!(std::panic::catch_unwind(|| big / Fixed32::from_f64(0.001)).is_err());
+            let long = FixedVec3::new(Fixed32::from_int(200), Fixed32::ZERO, Fixed32::ZERO);
+            assert!(std::panic::catch_unwind(|| long.length_squared()).is_err());
+        }
+    }
+
+    #[test]
+    fn test_fixed_geometry() {
+        let transform = Transform::new(
+            Vec3::new(1.0, 2.0, 3.0),
+            Quat::from_rotation_y(0.7),
+            Vec3::splat(2.0),
+        );
+        let fixed: FixedTransform = FixedTransform::from_transform(&transform);
+        let child = FixedTransform::from_position(FixedVec3::from_vec3(Vec3::new(0.5, 0.0, -1.0)));
+        let composed = fixed.mul_transform(&child).to_transform();
+        let expected = transform.mul_transform(&Transform::from_position(Vec3::new(0.5, 0.0, -1.0)));
+        assert!((composed.position - expected.position).length() < 1e-5);
+        assert!(composed.rotation.angle_between(expected.rotation) < 1e-3);
+
+        let aabb = FixedAabb::<Fixed32>::from_aabb(&Aabb::new(Vec3::ZERO, Vec3::ONE));
+        assert_eq!(aabb.center(), FixedVec3::splat(Fixed32::HALF));
+        let ray = FixedRay::new(
+            FixedVec3::new(Fixed32::from_int(-5), Fixed32::HALF, Fixed32::HALF),
+            FixedVec3::X,
+        );
+        assert_eq!(ray.intersect_aabb(&aabb), Some((Fixed32::from_int(5), Fixed32::from_int(6))));
+        let miss = FixedRay::new(FixedVec3::new(Fixed32::from_int(-5), Fixed32::from_int(2), Fixed32::HALF), FixedVec3::X);
+        assert_eq!(miss.intersect_aabb(&aabb), None);
+    }
+}
@@ -9,6 +9,20 @@ pub use glam::{
     Affine3A,
 };
 
+pub mod culling;
+pub mod curve;
+pub mod fixed;
+pub mod noise;
+pub mod primitives;
+pub mod random;
+
+pub use culling::AabbSoa;
+pub use curve::{Spline, SplineKind, CurveValue, ArcLengthTable, CurveFrame, Easing};
+pub use fixed::{Fixed, Fixed32, Fixed64, FixedVec3, FixedQuat, FixedAabb, FixedRay, FixedTransform};
+pub use noise::{Noise, NoiseKind, Fractal};
+pub use primitives::{Obb, Capsule, Triangle, Cone, Segment};
+pub use random::Pcg32;
+
 /// Axis-aligned bounding box
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub struct Aabb {
@@ -91,6 +105,25 @@ impl Aabb {
         }
     }
 
+    /// Check if this AABB fully contains another
+    pub fn contains_aabb(&self, other: &Aabb) -> bool {
+        self.min.cmple(other.min).all() && self.max.cmpge(other.max).all()
+    }
+
+    /// Grow the AABB by a margin on every side
+    pub fn expanded(&self, margin: f32) -> Aabb {
+        Aabb {
+            min: self.min - Vec3::splat(margin),
+            max: self.max + Vec3::splat(margin),
+        }
+    }
+
+    /// Get the surface area
+    pub fn surface_area(&self) -> f32 {
+        let size = self.size();
+        2.0 * (size.x * size.y + size.y * size.z + size.z * size.x)
+    }
+
     /// Transform the AABB by a matrix
     pub fn transform(&self, matrix: Mat4) -> Aabb {
         let corners = [
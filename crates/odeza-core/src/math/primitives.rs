@@ -0,0 +1,1346 @@
+//! Geometric primitives
+//!
+//! Oriented boxes, capsules, triangles, cones and segments, with the
+//! closest-point and intersection routines used by physics, picking and culling.
+//!
+//! Supported pairs:
+//! - Segments, triangles, boxes (AABB and OBB), capsules and spheres against
+//!   each other; a segment is tested as a zero-radius capsule
+//! - Cones against spheres and boxes
+//! - Rays against every shape
+//!
+//! Cones against triangles, capsules and other cones are not supported.
+
+use super::{Aabb, BoundingSphere, Mat3, Mat4, Quat, Ray, Vec2, Vec3};
+
+/// Tolerance for parallel and degenerate cases
+const EPSILON: f32 = 1e-6;
+
+/// Iteration cap for GJK before it gives up and reports an overlap
+const GJK_ITERATIONS: usize = 64;
+
+/// Line segment between two points
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Segment {
+    /// Start point
+    pub start: Vec3,
+    /// End point
+    pub end: Vec3,
+}
+
+impl Segment {
+    /// Create a new segment
+    pub fn new(start: Vec3, end: Vec3) -> Self {
+        Self { start, end }
+    }
+
+    /// Get the segment length
+    pub fn length(&self) -> f32 {
+        (self.end - self.start).length()
+    }
+
+    /// Get the point at parameter t (0 = start, 1 = end)
+    pub fn at(&self, t: f32) -> Vec3 {
+        self.start + (self.end - self.start) * t
+    }
+
+    /// Get the parameter of the closest point to `point`, in [0, 1]
+    pub fn closest_parameter(&self, point: Vec3) -> f32 {
+        let d = self.end - self.start;
+        let length_sq = d.length_squared();
+        if length_sq < EPSILON {
+            return 0.0;
+        }
+        ((point - self.start).dot(d) / length_sq).clamp(0.0, 1.0)
+    }
+
+    /// Get the closest point on the segment to `point`
+    pub fn closest_point(&self, point: Vec3) -> Vec3 {
+        self.at(self.closest_parameter(point))
+    }
+
+    /// Get the squared distance from the segment to a point
+    pub fn distance_squared_to_point(&self, point: Vec3) -> f32 {
+        (self.closest_point(point) - point).length_squared()
+    }
+
+    /// Get the closest pair of points between two segments
+    ///
+    /// Returns (point on self, point on other).
+    pub fn closest_points(&self, other: &Segment) -> (Vec3, Vec3) {
+        let d1 = self.end - self.start;
+        let d2 = other.end - other.start;
+        let r = self.start - other.start;
+        let a = d1.length_squared();
+        let e = d2.length_squared();
+        let f = d2.dot(r);
+
+        let (s, t) = if a < EPSILON && e < EPSILON {
+            (0.0, 0.0)
+        } else if a < EPSILON {
+            (0.0, (f / e).clamp(0.0, 1.0))
+        } else {
+            let c = d1.dot(r);
+            if e < EPSILON {
+                ((-c / a).clamp(0.0, 1.0), 0.0)
+            } else {
+                let b = d1.dot(d2);
+                let denom = a * e - b * b;
+                // Parallel segments pick an arbitrary s and let t fix it up
+                let mut s = if denom > EPSILON { ((b * f - c * e) / denom).clamp(0.0, 1.0) } else { 0.0 };
+                let mut t = (b * s + f) / e;
+                if t < 0.0 {
+                    t = 0.0;
+                    s = (-c / a).clamp(0.0, 1.0);
+                } else if t > 1.0 {
+                    t = 1.0;
+                    s = ((b - c) / a).clamp(0.0, 1.0);
+                }
+                (s, t)
+            }
+        };
+
+        (self.at(s), other.at(t))
+    }
+
+    /// Intersect with a triangle, returns the parameter of the hit point
+    pub fn intersect_triangle(&self, triangle: &Triangle) -> Option<f32> {
+        let direction = self.end - self.start;
+        let edge1 = triangle.b - triangle.a;
+        let edge2 = triangle.c - triangle.a;
+        let p = direction.cross(edge2);
+        let det = edge1.dot(p);
+        if det.abs() < EPSILON {
+            return None;
+        }
+
+        let inv_det = 1.0 / det;
+        let s = self.start - triangle.a;
+        let u = s.dot(p) * inv_det;
+        if !(0.0..=1.0).contains(&u) {
+            return None;
+        }
+
+        let q = s.cross(edge1);
+        let v = direction.dot(q) * inv_det;
+        if v < 0.0 || u + v > 1.0 {
+            return None;
+        }
+
+        let t = edge2.dot(q) * inv_det;
+        (0.0..=1.0).contains(&t).then_some(t)
+    }
+
+    /// Get the closest pair of points between the segment and a triangle
+    ///
+    /// Returns (point on self, point on triangle).
+    pub fn closest_points_triangle(&self, triangle: &Triangle) -> (Vec3, Vec3) {
+        if let Some(t) = self.intersect_triangle(triangle) {
+            let p = self.at(t);
+            return (p, p);
+        }
+
+        // Otherwise the closest pair involves an endpoint or a triangle edge
+        let mut best = (self.start, triangle.closest_point(self.start));
+        let mut consider = |pair: (Vec3, Vec3)| {
+            if (pair.0 - pair.1).length_squared() < (best.0 - best.1).length_squared() {
+                best = pair;
+            }
+        };
+        consider((self.end, triangle.closest_point(self.end)));
+        for edge in triangle.edges() {
+            consider(self.closest_points(&edge));
+        }
+        best
+    }
+
+    /// Check if the segment intersects an oriented box
+    pub fn intersects_obb(&self, obb: &Obb) -> bool {
+        let start = obb.local_point(self.start).to_array();
+        let delta = (obb.rotation.inverse() * (self.end - self.start)).to_array();
+        let extents = obb.half_extents.to_array();
+
+        let (mut t_min, mut t_max) = (0.0f32, 1.0f32);
+        for axis in 0..3 {
+            if delta[axis].abs() < EPSILON {
+                if start[axis].abs() > extents[axis] {
+                    return false;
+                }
+                continue;
+            }
+            let inv = 1.0 / delta[axis];
+            let t1 = (-extents[axis] - start[axis]) * inv;
+            let t2 = (extents[axis] - start[axis]) * inv;
+            t_min = t_min.max(t1.min(t2));
+            t_max = t_max.min(t1.max(t2));
+            if t_min > t_max {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Get the closest pair of points between the segment and an oriented box
+    ///
+    /// Returns (point on self, point in box).
+    pub fn closest_points_obb(&self, obb: &Obb) -> (Vec3, Vec3) {
+        // Distance from the box is convex along the segment, so a ternary search finds its minimum
+        let distance_sq = |t: f32| {
+            let p = self.at(t);
+            (obb.closest_point(p) - p).length_squared()
+        };
+        let (mut lo, mut hi) = (0.0f32, 1.0f32);
+        for _ in 0..32 {
+            let m1 = lo + (hi - lo) / 3.0;
+            let m2 = hi - (hi - lo) / 3.0;
+            if distance_sq(m1) < distance_sq(m2) {
+                hi = m2;
+            } else {
+                lo = m1;
+            }
+        }
+        let p = self.at((lo + hi) * 0.5);
+        (p, obb.closest_point(p))
+    }
+}
+
+/// Triangle defined by three vertices
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Triangle {
+    pub a: Vec3,
+    pub b: Vec3,
+    pub c: Vec3,
+}
+
+impl Triangle {
+    /// Create a new triangle
+    pub fn new(a: Vec3, b: Vec3, c: Vec3) -> Self {
+        Self { a, b, c }
+    }
+
+    /// Get the unit normal (counter-clockwise winding)
+    pub fn normal(&self) -> Vec3 {
+        (self.b - self.a).cross(self.c - self.a).normalize_or_zero()
+    }
+
+    /// Get the triangle area
+    pub fn area(&self) -> f32 {
+        (self.b - self.a).cross(self.c - self.a).length() * 0.5
+    }
+
+    /// Get the bounding box
+    pub fn bounds(&self) -> Aabb {
+        Aabb::new(self.a.min(self.b).min(self.c), self.a.max(self.b).max(self.c))
+    }
+
+    /// Get the edges ab, bc and ca
+    pub fn edges(&self) -> [Segment; 3] {
+        [
+            Segment::new(self.a, self.b),
+            Segment::new(self.b, self.c),
+            Segment::new(self.c, self.a),
+        ]
+    }
+
+    /// Get the barycentric coordinates (v, w) of a point on the triangle's plane
+    ///
+    /// The point equals `a * (1 - v - w) + b * v + c * w`.
+    pub fn barycentric(&self, point: Vec3) -> Vec2 {
+        let v0 = self.b - self.a;
+        let v1 = self.c - self.a;
+        let v2 = point - self.a;
+        let d00 = v0.dot(v0);
+        let d01 = v0.dot(v1);
+        let d11 = v1.dot(v1);
+        let d20 = v2.dot(v0);
+        let d21 = v2.dot(v1);
+        let denom = d00 * d11 - d01 * d01;
+        if denom.abs() < EPSILON {
+            return Vec2::ZERO;
+        }
+        Vec2::new((d11 * d20 - d01 * d21) / denom, (d00 * d21 - d01 * d20) / denom)
+    }
+
+    /// Get the closest point on the triangle to `point`
+    pub fn closest_point(&self, point: Vec3) -> Vec3 {
+        let (a, b, c) = (self.a, self.b, self.c);
+        let ab = b - a;
+        let ac = c - a;
+
+        // Vertex region A
+        let ap = point - a;
+        let d1 = ab.dot(ap);
+        let d2 = ac.dot(ap);
+        if d1 <= 0.0 && d2 <= 0.0 {
+            return a;
+        }
+
+        // Vertex region B
+        let bp = point - b;
+        let d3 = ab.dot(bp);
+        let d4 = ac.dot(bp);
+        if d3 >= 0.0 && d4 <= d3 {
+            return b;
+        }
+
+        // Edge region AB
+        let vc = d1 * d4 - d3 * d2;
+        if vc <= 0.0 && d1 >= 0.0 && d3 <= 0.0 {
+            return a + ab * (d1 / (d1 - d3));
+        }
+
+        // Vertex region C
+        let cp = point - c;
+        let d5 = ab.dot(cp);
+        let d6 = ac.dot(cp);
+        if d6 >= 0.0 && d5 <= d6 {
+            return c;
+        }
+
+        // Edge region AC
+        let vb = d5 * d2 - d1 * d6;
+        if vb <= 0.0 && d2 >= 0.0 && d6 <= 0.0 {
+            return a + ac * (d2 / (d2 - d6));
+        }
+
+        // Edge region BC
+        let va = d3 * d6 - d5 * d4;
+        if va <= 0.0 && (d4 - d3) >= 0.0 && (d5 - d6) >= 0.0 {
+            return b + (c - b) * ((d4 - d3) / ((d4 - d3) + (d5 - d6)));
+        }
+
+        // Face region
+        let denom = 1.0 / (va + vb + vc);
+        a + ab * (vb * denom) + ac * (vc * denom)
+    }
+
+    /// Check if this triangle intersects a sphere
+    pub fn intersects_sphere(&self, sphere: &BoundingSphere) -> bool {
+        sphere.contains_point(self.closest_point(sphere.center))
+    }
+
+    /// Check if this triangle intersects an AABB (separating axis test)
+    pub fn intersects_aabb(&self, aabb: &Aabb) -> bool {
+        let center = aabb.center();
+        let extents = aabb.half_extents();
+        let verts = [self.a - center, self.b - center, self.c - center];
+        let edges = [verts[1] - verts[0], verts[2] - verts[1], verts[0] - verts[2]];
+
+        let separated = |axis: Vec3| {
+            if axis.length_squared() < EPSILON {
+                return false;
+            }
+            let p = [axis.dot(verts[0]), axis.dot(verts[1]), axis.dot(verts[2])];
+            let r = extents.dot(axis.abs());
+            p[0].min(p[1]).min(p[2]) > r || p[0].max(p[1]).max(p[2]) < -r
+        };
+
+        let box_axes = [Vec3::X, Vec3::Y, Vec3::Z];
+        if box_axes.iter().any(|&axis| separated(axis)) {
+            return false;
+        }
+        if separated(edges[0].cross(edges[1])) {
+            return false;
+        }
+        !box_axes
+            .iter()
+            .any(|&axis| edges.iter().any(|&edge| separated(axis.cross(edge))))
+    }
+
+    /// Check if this triangle intersects an oriented box
+    pub fn intersects_obb(&self, obb: &Obb) -> bool {
+        let local = Triangle::new(obb.local_point(self.a), obb.local_point(self.b), obb.local_point(self.c));
+        local.intersects_aabb(&Aabb::new(-obb.half_extents, obb.half_extents))
+    }
+
+    /// Check if this triangle intersects a capsule
+    pub fn intersects_capsule(&self, capsule: &Capsule) -> bool {
+        let (p, q) = capsule.segment().closest_points_triangle(self);
+        (p - q).length_squared() <= capsule.radius * capsule.radius
+    }
+
+    /// Check if this triangle intersects another (separating axis test)
+    ///
+    /// Touching triangles count as intersecting.
+    pub fn intersects(&self, other: &Triangle) -> bool {
+        let verts_a = [self.a, self.b, self.c];
+        let verts_b = [other.a, other.b, other.c];
+        let edges_a = [self.b - self.a, self.c - self.b, self.a - self.c];
+        let edges_b = [other.b - other.a, other.c - other.b, other.a - other.c];
+        let normal_a = edges_a[0].cross(edges_a[1]);
+        let normal_b = edges_b[0].cross(edges_b[1]);
+        let scale = verts_a.iter().chain(&verts_b).fold(1.0f32, |scale, v| scale.max(v.abs().max_element()));
+
+        let project = |verts: &[Vec3; 3], axis: Vec3| {
+            let p = [axis.dot(verts[0]), axis.dot(verts[1]), axis.dot(verts[2])];
+            (p[0].min(p[1]).min(p[2]), p[0].max(p[1]).max(p[2]))
+        };
+        let separated = |axis: Vec3| {
+            if axis.length_squared() < EPSILON {
+                return false;
+            }
+            let (min_a, max_a) = project(&verts_a, axis);
+            let (min_b, max_b) = project(&verts_b, axis);
+            // Touching counts as intersecting, so allow for rounding in the projections
+            let slack = 1e-5 * axis.length() * scale;
+            min_a > max_b + slack || min_b > max_a + slack
+        };
+
+        if separated(normal_a) || separated(normal_b) {
+            return false;
+        }
+        if edges_a.iter().any(|&ea| edges_b.iter().any(|&eb| separated(ea.cross(eb)))) {
+            return false;
+        }
+        // In-plane edge normals separate coplanar triangles
+        !edges_a
+            .iter()
+            .map(|&edge| normal_a.cross(edge))
+            .chain(edges_b.iter().map(|&edge| normal_b.cross(edge)))
+            .any(separated)
+    }
+}
+
+/// Oriented bounding box
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Obb {
+    /// Center of the box
+    pub center: Vec3,
+    /// Half size along each local axis
+    pub half_extents: Vec3,
+    /// Orientation of the local axes
+    pub rotation: Quat,
+}
+
+impl Obb {
+    /// Create a new oriented box
+    pub fn new(center: Vec3, half_extents: Vec3, rotation: Quat) -> Self {
+        Self {
+            center,
+            half_extents,
+            rotation,
+        }
+    }
+
+    /// Create an unrotated box matching an AABB
+    pub fn from_aabb(aabb: &Aabb) -> Self {
+        Self::new(aabb.center(), aabb.half_extents(), Quat::IDENTITY)
+    }
+
+    /// Transform an AABB into an oriented box (shear is ignored)
+    pub fn from_transformed_aabb(aabb: &Aabb, matrix: Mat4) -> Self {
+        let (scale, rotation, _) = matrix.to_scale_rotation_translation();
+        Self::new(
+            matrix.transform_point3(aabb.center()),
+            aabb.half_extents() * scale.abs(),
+            rotation,
+        )
+    }
+
+    /// Get the local axes in world space
+    pub fn axes(&self) -> [Vec3; 3] {
+        let m = Mat3::from_quat(self.rotation);
+        [m.x_axis, m.y_axis, m.z_axis]
+    }
+
+    /// Get the eight corners
+    pub fn corners(&self) -> [Vec3; 8] {
+        let [x, y, z] = self.axes();
+        let (x, y, z) = (x * self.half_extents.x, y * self.half_extents.y, z * self.half_extents.z);
+        std::array::from_fn(|i| {
+            let sx = if i & 1 == 0 { -1.0 } else { 1.0 };
+            let sy = if i & 2 == 0 { -1.0 } else { 1.0 };
+            let sz = if i & 4 == 0 { -1.0 } else { 1.0 };
+            self.center + x * sx + y * sy + z * sz
+        })
+    }
+
+    /// Get the enclosing AABB
+    pub fn bounds(&self) -> Aabb {
+        let m = Mat3::from_quat(self.rotation);
+        let extent = m.x_axis.abs() * self.half_extents.x
+            + m.y_axis.abs() * self.half_extents.y
+            + m.z_axis.abs() * self.half_extents.z;
+        Aabb::new(self.center - extent, self.center + extent)
+    }
+
+    /// Get the farthest point of the box along a direction
+    fn support(&self, direction: Vec3) -> Vec3 {
+        self.axes()
+            .iter()
+            .zip(self.half_extents.to_array())
+            .fold(self.center, |point, (&axis, extent)| {
+                point + axis * extent.copysign(axis.dot(direction))
+            })
+    }
+
+    /// Convert a world point to the box's local frame
+    fn local_point(&self, point: Vec3) -> Vec3 {
+        self.rotation.inverse() * (point - self.center)
+    }
+
+    /// Check if a point is inside the box
+    pub fn contains_point(&self, point: Vec3) -> bool {
+        let local = self.local_point(point).abs();
+        local.cmple(self.half_extents).all()
+    }
+
+    /// Get the closest point in the box to `point`
+    pub fn closest_point(&self, point: Vec3) -> Vec3 {
+        let local = self.local_point(point).clamp(-self.half_extents, self.half_extents);
+        self.center + self.rotation * local
+    }
+
+    /// Check if this box intersects a sphere
+    pub fn intersects_sphere(&self, sphere: &BoundingSphere) -> bool {
+        sphere.contains_point(self.closest_point(sphere.center))
+    }
+
+    /// Check if this box intersects an AABB
+    pub fn intersects_aabb(&self, aabb: &Aabb) -> bool {
+        self.intersects(&Obb::from_aabb(aabb))
+    }
+
+    /// Check if this box intersects another (separating axis test)
+    pub fn intersects(&self, other: &Obb) -> bool {
+        let a_axes = self.axes();
+        let b_axes = other.axes();
+        let ea = self.half_extents.to_array();
+        let eb = other.half_extents.to_array();
+
+        // Rotation of other expressed in self's frame, padded so that
+        // near-parallel edges don't produce a degenerate cross axis
+        let mut r = [[0.0f32; 3]; 3];
+        let mut abs_r = [[0.0f32; 3]; 3];
+        for i in 0..3 {
+            for j in 0..3 {
+                r[i][j] = a_axes[i].dot(b_axes[j]);
+                abs_r[i][j] = r[i][j].abs() + EPSILON;
+            }
+        }
+
+        let d = other.center - self.center;
+        let t = [d.dot(a_axes[0]), d.dot(a_axes[1]), d.dot(a_axes[2])];
+
+        // Self's face axes
+        for i in 0..3 {
+            let rb = eb[0] * abs_r[i][0] + eb[1] * abs_r[i][1] + eb[2] * abs_r[i][2];
+            if t[i].abs() > ea[i] + rb {
+                return false;
+            }
+        }
+
+        // Other's face axes
+        for j in 0..3 {
+            let ra = ea[0] * abs_r[0][j] + ea[1] * abs_r[1][j] + ea[2] * abs_r[2][j];
+            let tb = t[0] * r[0][j] + t[1] * r[1][j] + t[2] * r[2][j];
+            if tb.abs() > ra + eb[j] {
+                return false;
+            }
+        }
+
+        // Edge cross products
+        for i in 0..3 {
+            let (i1, i2) = ((i + 1) % 3, (i + 2) % 3);
+            for j in 0..3 {
+                let (j1, j2) = ((j + 1) % 3, (j + 2) % 3);
+                let ra = ea[i1] * abs_r[i2][j] + ea[i2] * abs_r[i1][j];
+                let rb = eb[j1] * abs_r[i][j2] + eb[j2] * abs_r[i][j1];
+                let tl = t[i2] * r[i1][j] - t[i1] * r[i2][j];
+                if tl.abs() > ra + rb {
+                    return false;
+                }
+            }
+        }
+
+        true
+    }
+}
+
+/// Capsule: a segment swept by a sphere
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Capsule {
+    /// Center of the first cap
+    pub start: Vec3,
+    /// Center of the second cap
+    pub end: Vec3,
+    /// Radius
+    pub radius: f32,
+}
+
+impl Capsule {
+    /// Create a new capsule
+    pub fn new(start: Vec3, end: Vec3, radius: f32) -> Self {
+        Self { start, end, radius }
+    }
+
+    /// Get the axis segment
+    pub fn segment(&self) -> Segment {
+        Segment::new(self.start, self.end)
+    }
+
+    /// Get the bounding box
+    pub fn bounds(&self) -> Aabb {
+        let r = Vec3::splat(self.radius);
+        Aabb::new(self.start.min(self.end) - r, self.start.max(self.end) + r)
+    }
+
+    /// Check if a point is inside the capsule
+    pub fn contains_point(&self, point: Vec3) -> bool {
+        self.segment().distance_squared_to_point(point) <= self.radius * self.radius
+    }
+
+    /// Get the closest point in the capsule to `point`
+    pub fn closest_point(&self, point: Vec3) -> Vec3 {
+        let on_axis = self.segment().closest_point(point);
+        let offset = point - on_axis;
+        if offset.length_squared() <= self.radius * self.radius {
+            point
+        } else {
+            on_axis + offset.normalize() * self.radius
+        }
+    }
+
+    /// Check if this capsule intersects a sphere
+    pub fn intersects_sphere(&self, sphere: &BoundingSphere) -> bool {
+        let r = self.radius + sphere.radius;
+        self.segment().distance_squared_to_point(sphere.center) <= r * r
+    }
+
+    /// Check if this capsule intersects another
+    pub fn intersects(&self, other: &Capsule) -> bool {
+        let (p, q) = self.segment().closest_points(&other.segment());
+        let r = self.radius + other.radius;
+        (p - q).length_squared() <= r * r
+    }
+
+    /// Check if this capsule intersects an oriented box
+    pub fn intersects_obb(&self, obb: &Obb) -> bool {
+        let inflated = Obb::new(obb.center, obb.half_extents + Vec3::splat(self.radius), obb.rotation);
+        if !inflated.intersects(&Obb::from_aabb(&self.bounds())) {
+            return false;
+        }
+        let (p, q) = self.segment().closest_points_obb(obb);
+        (p - q).length_squared() <= self.radius * self.radius
+    }
+
+    /// Check if this capsule intersects an AABB
+    pub fn intersects_aabb(&self, aabb: &Aabb) -> bool {
+        self.intersects_obb(&Obb::from_aabb(aabb))
+    }
+}
+
+/// Finite cone, e.g. a spot light's volume
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Cone {
+    /// Tip of the cone
+    pub apex: Vec3,
+    /// Axis direction (normalized)
+    pub direction: Vec3,
+    /// Length along the axis
+    pub height: f32,
+    /// Half of the opening angle in radians (at most 90 degrees)
+    pub half_angle: f32,
+}
+
+impl Cone {
+    /// Create a new cone
+    pub fn new(apex: Vec3, direction: Vec3, height: f32, half_angle: f32) -> Self {
+        Self {
+            apex,
+            direction: direction.normalize(),
+            height,
+            half_angle,
+        }
+    }
+
+    /// Get the radius of the base
+    pub fn base_radius(&self) -> f32 {
+        self.height * self.half_angle.tan()
+    }
+
+    /// Get the bounding sphere
+    pub fn bounding_sphere(&self) -> BoundingSphere {
+        let radius = self.base_radius();
+        // Wide cones are best bounded around the base, narrow ones need the apex too
+        if self.half_angle > std::f32::consts::FRAC_PI_4 {
+            BoundingSphere::new(self.apex + self.direction * self.height, radius)
+        } else {
+            let r = self.height / (2.0 * self.half_angle.cos().powi(2));
+            BoundingSphere::new(self.apex + self.direction * r, r)
+        }
+    }
+
+    /// Check if a point is inside the cone
+    pub fn contains_point(&self, point: Vec3) -> bool {
+        let v = point - self.apex;
+        let along = v.dot(self.direction);
+        if along < 0.0 || along > self.height {
+            return false;
+        }
+        let radial = (v.length_squared() - along * along).max(0.0).sqrt();
+        radial <= along * self.half_angle.tan()
+    }
+
+    /// Check if this cone may intersect a sphere
+    ///
+    /// Tests the sphere against the cone's sides, cap and apex plane. May
+    /// report spheres near the cap's rim as intersecting, never the reverse.
+    pub fn intersects_sphere(&self, sphere: &BoundingSphere) -> bool {
+        let v = sphere.center - self.apex;
+        let along = v.dot(self.direction);
+        let radial = (v.length_squared() - along * along).max(0.0).sqrt();
+        let (sin, cos) = self.half_angle.sin_cos();
+        let side_distance = cos * radial - along * sin;
+
+        side_distance <= sphere.radius && along <= self.height + sphere.radius && along >= -sphere.radius
+    }
+
+    /// Get the farthest point of the cone along a direction
+    pub fn support(&self, direction: Vec3) -> Vec3 {
+        // Along the axis the whole base is equally far; snap so rounding can't pick a side
+        let radial = direction - self.direction * direction.dot(self.direction);
+        let radial = if radial.length_squared() > EPSILON * direction.length_squared() {
+            radial.normalize()
+        } else {
+            Vec3::ZERO
+        };
+        let rim = self.apex + self.direction * self.height + radial * self.base_radius();
+        if rim.dot(direction) >= self.apex.dot(direction) {
+            rim
+        } else {
+            self.apex
+        }
+    }
+
+    /// Check if this cone intersects an oriented box
+    ///
+    /// Exact up to float precision, unlike [`Cone::intersects_sphere`].
+    pub fn intersects_obb(&self, obb: &Obb) -> bool {
+        gjk_intersects(|d| self.support(d), |d| obb.support(d), obb.center - self.apex)
+    }
+
+    /// Check if this cone intersects an AABB
+    pub fn intersects_aabb(&self, aabb: &Aabb) -> bool {
+        self.intersects_obb(&Obb::from_aabb(aabb))
+    }
+}
+
+/// Check if two convex shapes overlap, given their support functions (GJK)
+///
+/// `direction` is the initial search direction, ideally from one shape
+/// towards the other. Reports an overlap if it fails to converge.
+fn gjk_intersects(
+    support_a: impl Fn(Vec3) -> Vec3,
+    support_b: impl Fn(Vec3) -> Vec3,
+    direction: Vec3,
+) -> bool {
+    // Support of the Minkowski difference A - B; it contains the origin iff the shapes overlap
+    let support = |d: Vec3| support_a(d) - support_b(-d);
+
+    let mut c = support(if direction.length_squared() > EPSILON { direction } else { Vec3::X });
+    let mut direction = -c;
+    let mut b = support(direction);
+    if b.dot(direction) < 0.0 {
+        return false;
+    }
+    direction = (c - b).cross(-b).cross(c - b);
+    if direction.length_squared() < EPSILON {
+        // Origin lies on the line; any perpendicular will do
+        direction = (c - b).any_orthogonal_vector();
+    }
+
+    let mut d = Vec3::ZERO;
+    let mut dimension = 2;
+    for _ in 0..GJK_ITERATIONS {
+        let a = support(direction);
+        if a.dot(direction) < 0.0 {
+            return false;
+        }
+        if dimension == 2 {
+            dimension = gjk_triangle(a, &mut b, &mut c, &mut d, &mut direction);
+        } else if gjk_tetrahedron(a, &mut b, &mut c, &mut d, &mut direction) {
+            return true;
+        }
+    }
+    true
+}
+
+/// Reduce triangle (a, b, c) to the feature nearest the origin
+///
+/// Returns the new simplex size; a line is kept in (b, c) and a triangle in
+/// (b, c, d), wound so `direction` faces the origin.
+fn gjk_triangle(a: Vec3, b: &mut Vec3, c: &mut Vec3, d: &mut Vec3, direction: &mut Vec3) -> usize {
+    let (ab, ac, ao) = (*b - a, *c - a, -a);
+    let normal = ab.cross(ac);
+
+    if ab.cross(normal).dot(ao) > 0.0 {
+        *c = a;
+        *direction = ab.cross(ao).cross(ab);
+        return 2;
+    }
+    if normal.cross(ac).dot(ao) > 0.0 {
+        *b = a;
+        *direction = ac.cross(ao).cross(ac);
+        return 2;
+    }
+
+    if normal.dot(ao) > 0.0 {
+        *d = *c;
+        *c = *b;
+        *b = a;
+        *direction = normal;
+    } else {
+        *d = *b;
+        *b = a;
+        *direction = -normal;
+    }
+    3
+}
+
+/// Check if tetrahedron (a, b, c, d) contains the origin, otherwise reduce
+/// it to the face facing the origin
+fn gjk_tetrahedron(a: Vec3, b: &mut Vec3, c: &mut Vec3, d: &mut Vec3, direction: &mut Vec3) -> bool {
+    let (ab, ac, ad, ao) = (*b - a, *c - a, *d - a, -a);
+    let abc = ab.cross(ac);
+    let acd = ac.cross(ad);
+    let adb = ad.cross(ab);
+
+    if abc.dot(ao) > 0.0 {
+        *d = *c;
+        *c = *b;
+        *b = a;
+        *direction = abc;
+        return false;
+    }
+    if acd.dot(ao) > 0.0 {
+        *b = a;
+        *direction = acd;
+        return false;
+    }
+    if adb.dot(ao) > 0.0 {
+        *c = *d;
+        *d = *b;
+        *b = a;
+        *direction = adb;
+        return false;
+    }
+    true
+}
+
+impl Ray {
+    /// Intersect with a triangle (Möller–Trumbore), returns t if hit
+    ///
+    /// Both faces are hit.
+    pub fn intersect_triangle(&self, triangle: &Triangle) -> Option<f32> {
+        let edge1 = triangle.b - triangle.a;
+        let edge2 = triangle.c - triangle.a;
+        let p = self.direction.cross(edge2);
+        let det = edge1.dot(p);
+        if det.abs() < EPSILON {
+            return None;
+        }
+
+        let inv_det = 1.0 / det;
+        let s = self.origin - triangle.a;
+        let u = s.dot(p) * inv_det;
+        if !(0.0..=1.0).contains(&u) {
+            return None;
+        }
+
+        let q = s.cross(edge1);
+        let v = self.direction.dot(q) * inv_det;
+        if v < 0.0 || u + v > 1.0 {
+            return None;
+        }
+
+        let t = edge2.dot(q) * inv_det;
+        (t >= 0.0).then_some(t)
+    }
+
+    /// Intersect with a capsule, returns the entry distance if hit
+    ///
+    /// Rays starting inside the capsule hit at 0.
+    pub fn intersect_capsule(&self, capsule: &Capsule) -> Option<f32> {
+        if capsule.contains_point(self.origin) {
+            return Some(0.0);
+        }
+
+        let mut nearest: Option<f32> = None;
+        let mut consider = |t: f32| {
+            if t >= 0.0 && nearest.is_none_or(|n| t < n) {
+                nearest = Some(t);
+            }
+        };
+
+        // Cylinder body
+        let ba = capsule.end - capsule.start;
+        let oa = self.origin - capsule.start;
+        let baba = ba.length_squared();
+        let bard = ba.dot(self.direction);
+        let baoa = ba.dot(oa);
+        let a = baba - bard * bard;
+        if a > EPSILON {
+            let b = baba * oa.dot(self.direction) - baoa * bard;
+            let c = baba * oa.length_squared() - baoa * baoa - capsule.radius * capsule.radius * baba;
+            let h = b * b - a * c;
+            if h >= 0.0 {
+                let t = (-b - h.sqrt()) / a;
+                let y = baoa + t * bard;
+                if (0.0..=baba).contains(&y) {
+                    consider(t);
+                }
+            }
+        }
+
+        // End caps
+        for center in [capsule.start, capsule.end] {
+            if let Some((t, _)) = self.intersect_sphere(&BoundingSphere::new(center, capsule.radius)) {
+                consider(t);
+            }
+        }
+
+        nearest
+    }
+
+    /// Intersect with an oriented box, returns (t_min, t_max) if hit
+    pub fn intersect_obb(&self, obb: &Obb) -> Option<(f32, f32)> {
+        let local = Ray {
+            origin: obb.local_point(self.origin),
+            direction: obb.rotation.inverse() * self.direction,
+        };
+        local.intersect_aabb(&Aabb::new(-obb.half_extents, obb.half_extents))
+    }
+
+    /// Intersect with a cone, returns the entry distance if hit
+    ///
+    /// Rays starting inside the cone hit at 0.
+    pub fn intersect_cone(&self, cone: &Cone) -> Option<f32> {
+        if cone.contains_point(self.origin) {
+            return Some(0.0);
+        }
+
+        let mut nearest: Option<f32> = None;
+        let mut consider = |t: f32| {
+            if t >= 0.0 && nearest.is_none_or(|n| t < n) {
+                nearest = Some(t);
+            }
+        };
+
+        // Side: (p . axis)^2 = cos^2 * |p|^2, on the nappe between apex and base.
+        // Solved from the ray point nearest the apex to keep the coefficients small.
+        let v = self.origin - cone.apex;
+        let du = self.direction.dot(cone.direction);
+        let t0 = -v.dot(self.direction);
+        let w = v + self.direction * t0;
+        let dw = w.dot(cone.direction);
+        let cos_sq = cone.half_angle.cos().powi(2);
+        let a = du * du - cos_sq;
+        let half_b = du * dw;
+        let c = dw * dw - cos_sq * w.length_squared();
+        let mut side = |s: f32| {
+            if (0.0..=cone.height).contains(&(dw + s * du)) {
+                consider(t0 + s);
+            }
+        };
+        if a.abs() > EPSILON {
+            let h = half_b * half_b - a * c;
+            if h >= 0.0 {
+                // Numerically stable pair of roots
+                let q = -(half_b + h.sqrt().copysign(half_b));
+                side(q / a);
+                if q != 0.0 {
+                    side(c / q);
+                }
+            }
+        } else if half_b.abs() > EPSILON {
+            // Ray parallel to the side
+            side(-c / (2.0 * half_b));
+        }
+
+        // Base cap
+        if du.abs() > EPSILON {
+            let t = (cone.height - v.dot(cone.direction)) / du;
+            let base = cone.apex + cone.direction * cone.height;
+            if (self.at(t) - base).length_squared() <= cone.base_radius().powi(2) {
+                consider(t);
+            }
+        }
+
+        nearest
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    fn vec3(range: f32) -> impl Strategy<Value = Vec3> {
+        (-range..range, -range..range, -range..range).prop_map(|(x, y, z)| Vec3::new(x, y, z))
+    }
+
+    fn unit_vec3() -> impl Strategy<Value = Vec3> {
+        vec3(1.0).prop_filter("non-zero", |v| v.length_squared() > 0.01).prop_map(Vec3::normalize)
+    }
+
+    fn rotation() -> impl Strategy<Value = Quat> {
+        (unit_vec3(), -3.1f32..3.1).prop_map(|(axis, angle)| Quat::from_axis_angle(axis, angle))
+    }
+
+    fn obb() -> impl Strategy<Value = Obb> {
+        (vec3(5.0), (0.1f32..3.0, 0.1f32..3.0, 0.1f32..3.0), rotation())
+            .prop_map(|(center, (x, y, z), rotation)| Obb::new(center, Vec3::new(x, y, z), rotation))
+    }
+
+    fn triangle() -> impl Strategy<Value = Triangle> {
+        (vec3(5.0), vec3(5.0), vec3(5.0))
+            .prop_map(|(a, b, c)| Triangle::new(a, b, c))
+            .prop_filter("non-degenerate", |t| t.area() > 0.1)
+    }
+
+    fn capsule() -> impl Strategy<Value = Capsule> {
+        (vec3(5.0), vec3(5.0), 0.1f32..2.0).prop_map(|(a, b, r)| Capsule::new(a, b, r))
+    }
+
+    /// Point inside `cone` at fraction `s` of its height and `f` of its radius there
+    fn cone_point(cone: &Cone, s: f32, f: f32, side: Vec3) -> Vec3 {
+        let along = s * cone.height;
+        let radial = (side - cone.direction * side.dot(cone.direction)).normalize_or_zero();
+        cone.apex + cone.direction * along + radial * (f * along * cone.half_angle.tan())
+    }
+
+    /// Point inside `obb` at a fraction of its half extents along each axis
+    fn obb_point(obb: &Obb, fraction: Vec3) -> Vec3 {
+        obb.center + obb.rotation * (fraction * obb.half_extents)
+    }
+
+    #[test]
+    fn test_obb_intersection() {
+        let a = Obb::new(Vec3::ZERO, Vec3::ONE, Quat::IDENTITY);
+        let rotated = Quat::from_rotation_z(std::f32::consts::FRAC_PI_4);
+        // Corner of the rotated box reaches sqrt(2) along X
+        assert!(a.intersects(&Obb::new(Vec3::new(2.3, 0.0, 0.0), Vec3::ONE, rotated)));
+        assert!(!a.intersects(&Obb::new(Vec3::new(2.5, 0.0, 0.0), Vec3::ONE, rotated)));
+        assert!(!a.intersects(&Obb::new(Vec3::new(2.5, 2.5, 0.0), Vec3::ONE, rotated)));
+    }
+
+    #[test]
+    fn test_ray_primitives() {
+        let ray = Ray::new(Vec3::new(0.25, 0.25, 5.0), Vec3::NEG_Z);
+        let triangle = Triangle::new(Vec3::ZERO, Vec3::X, Vec3::Y);
+        assert_eq!(ray.intersect_triangle(&triangle), Some(5.0));
+        assert_eq!(Ray::new(Vec3::new(2.0, 2.0, 5.0), Vec3::NEG_Z).intersect_triangle(&triangle), None);
+
+        let capsule = Capsule::new(Vec3::new(0.0, -2.0, 0.0), Vec3::new(0.0, 2.0, 0.0), 1.0);
+        let side = Ray::new(Vec3::new(-5.0, 1.5, 0.0), Vec3::X);
+        assert!((side.intersect_capsule(&capsule).unwrap() - 4.0).abs() < 1e-4);
+        let top = Ray::new(Vec3::new(0.0, 10.0, 0.0), Vec3::NEG_Y);
+        assert!((top.intersect_capsule(&capsule).unwrap() - 7.0).abs() < 1e-4);
+        assert_eq!(Ray::new(Vec3::new(-5.0, 3.5, 0.0), Vec3::X).intersect_capsule(&capsule), None);
+    }
+
+    #[test]
+    fn test_spot_light_cone() {
+        let cone = Cone::new(Vec3::ZERO, Vec3::NEG_Z, 10.0, 30f32.to_radians());
+        assert!(cone.contains_point(Vec3::new(0.0, 0.0, -5.0)));
+        assert!(!cone.contains_point(Vec3::new(0.0, 5.0, -5.0)));
+        assert!(cone.intersects_sphere(&BoundingSphere::new(Vec3::new(0.0, 5.0, -5.0), 2.0)));
+        assert!(!cone.intersects_sphere(&BoundingSphere::new(Vec3::new(0.0, 0.0, 3.0), 1.0)));
+        assert!(!cone.intersects_sphere(&BoundingSphere::new(Vec3::new(0.0, 0.0, -12.0), 1.0)));
+        assert!(!cone.intersects_sphere(&BoundingSphere::new(Vec3::new(0.0, 8.0, -2.0), 1.0)));
+    }
+
+    proptest! {
+        #[test]
+        fn prop_segment_closest_point(start in vec3(10.0), end in vec3(10.0), p in vec3(10.0), t in 0.0f32..1.0) {
+            let segment = Segment::new(start, end);
+            let closest = segment.closest_point(p);
+            prop_assert!((closest - p).length() <= (segment.at(t) - p).length() + 1e-3);
+        }
+
+        #[test]
+        fn prop_segment_closest_points(a in vec3(10.0), b in vec3(10.0), c in vec3(10.0), d in vec3(10.0),
+                                       s in 0.0f32..1.0, t in 0.0f32..1.0) {
+            let (first, second) = (Segment::new(a, b), Segment::new(c, d));
+            let (p, q) = first.closest_points(&second);
+            prop_assert!((p - q).length() <= (first.at(s) - second.at(t)).length() + 1e-3);
+        }
+
+        #[test]
+        fn prop_triangle_closest_point(tri in triangle(), p in vec3(10.0), u in 0.0f32..1.0, v in 0.0f32..1.0) {
+            let closest = tri.closest_point(p);
+            let bary = tri.barycentric(closest);
+            prop_assert!(bary.x >= -1e-3 && bary.y >= -1e-3 && bary.x + bary.y <= 1.0 + 1e-3);
+
+            let (u, v) = if u + v > 1.0 { (1.0 - u, 1.0 - v) } else { (u, v) };
+            let sample = tri.a + (tri.b - tri.a) * u + (tri.c - tri.a) * v;
+            prop_assert!((closest - p).length() <= (sample - p).length() + 1e-3);
+        }
+
+        #[test]
+        fn prop_triangle_aabb(tri in triangle(), center in vec3(5.0), size in 0.1f32..3.0,
+                              u in 0.0f32..1.0, v in 0.0f32..1.0) {
+            let aabb = Aabb::from_center_half_extents(center, Vec3::splat(size));
+            let (u, v) = if u + v > 1.0 { (1.0 - u, 1.0 - v) } else { (u, v) };
+            let sample = tri.a + (tri.b - tri.a) * u + (tri.c - tri.a) * v;
+            if aabb.contains_point(sample) {
+                prop_assert!(tri.intersects_aabb(&aabb));
+            }
+            // The closest point on the triangle decides a sphere around the box
+            let sphere = BoundingSphere::from_aabb(&aabb);
+            if !tri.intersects_sphere(&sphere) {
+                prop_assert!(!tri.intersects_aabb(&aabb));
+            }
+        }
+
+        #[test]
+        fn prop_obb_sat(a in obb(), b in obb(), p in vec3(5.0)) {
+            prop_assert_eq!(a.intersects(&b), b.intersects(&a));
+            if a.contains_point(p) && b.contains_point(p) {
+                prop_assert!(a.intersects(&b));
+            }
+            let (ra, rb) = (a.half_extents.length(), b.half_extents.length());
+            if (a.center - b.center).length() > ra + rb {
+                prop_assert!(!a.intersects(&b));
+            }
+            // Touching boxes always report an overlap at their closest points
+            let on_b = b.closest_point(a.closest_point(b.center));
+            if a.contains_point(on_b) {
+                prop_assert!(a.intersects(&b));
+            }
+        }
+
+        #[test]
+        fn prop_obb_closest_point(a in obb(), p in vec3(10.0)) {
+            let closest = a.closest_point(p);
+            let padded = Obb::new(a.center, a.half_extents + Vec3::splat(1e-3), a.rotation);
+            prop_assert!(padded.contains_point(closest));
+            for corner in a.corners() {
+                prop_assert!((closest - p).length() <= (corner - p).length() + 1e-3);
+            }
+        }
+
+        #[test]
+        fn prop_ray_triangle(tri in triangle(), origin in vec3(10.0), u in 0.01f32..0.98, v in 0.01f32..0.98) {
+            let (u, v) = if u + v > 0.99 { (0.99 - u, 0.99 - v) } else { (u, v) };
+            let target = tri.a + (tri.b - tri.a) * u + (tri.c - tri.a) * v;
+            let to_target = target - origin;
+            prop_assume!(to_target.length() > 0.1);
+            prop_assume!(to_target.normalize().dot(tri.normal()).abs() > 0.05);
+
+            let ray = Ray::new(origin, to_target);
+            let t = ray.intersect_triangle(&tri);
+            prop_assert!(t.is_some());
+            prop_assert!((ray.at(t.unwrap()) - target).length() < 1e-2);
+
+            let away = Ray::new(origin, -to_target);
+            prop_assert!(away.intersect_triangle(&tri).is_none());
+        }
+
+        #[test]
+        fn prop_ray_capsule(cap in capsule(), origin in vec3(10.0), t in 0.0f32..1.0) {
+            prop_assume!(!cap.contains_point(origin));
+            let target = cap.segment().at(t);
+            let ray = Ray::new(origin, target - origin);
+
+            let hit = ray.intersect_capsule(&cap);
+            prop_assert!(hit.is_some());
+            let hit = hit.unwrap();
+            prop_assert!(hit <= (target - origin).length() + 1e-3);
+            let surface = cap.segment().distance_squared_to_point(ray.at(hit)).sqrt();
+            prop_assert!((surface - cap.radius).abs() < 1e-2);
+        }
+
+        #[test]
+        fn prop_capsule_intersections(a in capsule(), b in capsule(), sphere_center in vec3(5.0), r in 0.1f32..2.0,
+                                      s in 0.0f32..1.0, t in 0.0f32..1.0) {
+            prop_assert_eq!(a.intersects(&b), b.intersects(&a));
+            let sample_distance = (a.segment().at(s) - b.segment().at(t)).length();
+            if sample_distance <= a.radius + b.radius {
+                prop_assert!(a.intersects(&b));
+            }
+
+            let sphere = BoundingSphere::new(sphere_center, r);
+            prop_assert_eq!(
+                a.intersects_sphere(&sphere),
+                a.intersects(&Capsule::new(sphere_center, sphere_center, r))
+            );
+        }
+
+        #[test]
+        fn prop_capsule_obb(cap in capsule(), b in obb(), t in 0.0f32..1.0) {
+            let on_axis = cap.segment().at(t);
+            if (b.closest_point(on_axis) - on_axis).length() <= cap.radius * 0.999 {
+                prop_assert!(cap.intersects_obb(&b));
+            }
+            if !b.bounds().intersects(&cap.bounds()) {
+                prop_assert!(!cap.intersects_obb(&b));
+            }
+        }
+
+        #[test]
+        fn prop_sphere_cone(dir in unit_vec3(), height in 1.0f32..20.0, angle in 0.1f32..1.4,
+                            center in vec3(20.0), r in 0.1f32..5.0, p in vec3(20.0)) {
+            let cone = Cone::new(Vec3::ZERO, dir, height, angle);
+            let sphere = BoundingSphere::new(center, r);
+            // A point shared by both volumes must never be culled
+            if cone.contains_point(p) && sphere.contains_point(p) {
+                prop_assert!(cone.intersects_sphere(&sphere));
+            }
+            if cone.contains_point(center) {
+                prop_assert!(cone.intersects_sphere(&sphere));
+            }
+            if cone.contains_point(p) {
+                prop_assert!(cone.bounding_sphere().radius + 1e-3 >= (p - cone.bounding_sphere().center).length());
+            }
+        }
+
+        #[test]
+        fn prop_segment_triangle(tri in triangle(), start in vec3(10.0), u in 0.01f32..0.98, v in 0.01f32..0.98,
+                                 s in 0.05f32..0.95, sample in 0.0f32..1.0) {
+            prop_assume!(u + v < 0.99);
+            let target = tri.a + (tri.b - tri.a) * u + (tri.c - tri.a) * v;
+            prop_assume!((target - start).length() > 0.1);
+            // Grazing segments have no well-conditioned hit point
+            prop_assume!(tri.normal().dot((target - start).normalize()).abs() > 0.1);
+            let segment = Segment::new(start, start + (target - start) / s);
+
+            let (p, q) = segment.closest_points_triangle(&tri);
+            prop_assert!((p - q).length() < 1e-2);
+            if let Some(t) = segment.intersect_triangle(&tri) {
+                prop_assert!((tri.closest_point(segment.at(t)) - segment.at(t)).length() < 1e-2);
+            }
+            prop_assert!(tri.intersects_capsule(&Capsule::new(segment.start, segment.end, 0.05)));
+
+            // The closest pair is never beaten by any other point on the segment
+            let shifted = Segment::new(segment.start + Vec3::Y * 3.0, segment.end + Vec3::Y * 3.0);
+            let (p, q) = shifted.closest_points_triangle(&tri);
+            let other = shifted.at(sample);
+            prop_assert!((p - q).length() <= (tri.closest_point(other) - other).length() + 1e-3);
+        }
+
+        #[test]
+        fn prop_segment_obb(b in obb(), fraction in vec3(0.99), start in vec3(10.0), end in vec3(10.0)) {
+            let inside = obb_point(&b, fraction);
+            prop_assert!(Segment::new(start, inside).intersects_obb(&b));
+            let (p, q) = Segment::new(inside, end).closest_points_obb(&b);
+            prop_assert!((p - q).length() < 1e-2);
+
+            let segment = Segment::new(start, end);
+            if !Aabb::new(start.min(end), start.max(end)).intersects(&b.bounds()) {
+                prop_assert!(!segment.intersects_obb(&b));
+            }
+            if segment.intersects_obb(&b) {
+                let (p, q) = segment.closest_points_obb(&b);
+                prop_assert!((p - q).length() < 1e-2);
+            }
+        }
+
+        #[test]
+        fn prop_triangle_obb(tri in triangle(), b in obb(), fraction in vec3(0.99), u in 0.0f32..1.0, v in 0.0f32..1.0) {
+            prop_assume!(u + v <= 1.0);
+            let on_triangle = tri.a + (tri.b - tri.a) * u + (tri.c - tri.a) * v;
+            // Move the box so the triangle point lies inside it
+            let moved = Obb::new(on_triangle + (b.center - obb_point(&b, fraction)), b.half_extents, b.rotation);
+            prop_assert!(tri.intersects_obb(&moved));
+
+            if !tri.bounds().intersects(&b.bounds()) {
+                prop_assert!(!tri.intersects_obb(&b));
+            }
+            if b.rotation == Quat::IDENTITY {
+                prop_assert_eq!(tri.intersects_obb(&b), tri.intersects_aabb(&b.bounds()));
+            }
+        }
+
+        #[test]
+        fn prop_triangle_capsule(tri in triangle(), cap in capsule(), u in 0.0f32..1.0, v in 0.0f32..1.0) {
+            prop_assume!(u + v <= 1.0);
+            let on_triangle = tri.a + (tri.b - tri.a) * u + (tri.c - tri.a) * v;
+            if cap.contains_point(on_triangle) {
+                prop_assert!(tri.intersects_capsule(&cap));
+            }
+            if cap.segment().intersect_triangle(&tri).is_some() {
+                prop_assert!(tri.intersects_capsule(&cap));
+            }
+            if !tri.bounds().intersects(&cap.bounds()) {
+                prop_assert!(!tri.intersects_capsule(&cap));
+            }
+        }
+
+        #[test]
+        fn prop_triangle_triangle(a in triangle(), b in triangle(), u in 0.0f32..1.0, v in 0.0f32..1.0) {
+            prop_assert_eq!(a.intersects(&b), b.intersects(&a));
+
+            // Any edge of one crossing the other is an intersection
+            let crossing = a.edges().iter().any(|edge| edge.intersect_triangle(&b).is_some())
+                || b.edges().iter().any(|edge| edge.intersect_triangle(&a).is_some());
+            if crossing {
+                prop_assert!(a.intersects(&b));
+            }
+            if !a.bounds().intersects(&b.bounds()) {
+                prop_assert!(!a.intersects(&b));
+            }
+
+            // Sharing a point is an intersection
+            prop_assume!(u + v <= 1.0);
+            let shared = a.a + (a.b - a.a) * u + (a.c - a.a) * v;
+            prop_assert!(a.intersects(&Triangle::new(shared, b.b, b.c)));
+        }
+
+        #[test]
+        fn prop_cone_obb(dir in unit_vec3(), height in 1.0f32..20.0, angle in 0.1f32..1.4, b in obb(),
+                         s in 0.05f32..1.0, f in 0.0f32..0.95, side in unit_vec3(), fraction in vec3(0.99)) {
+            let cone = Cone::new(Vec3::ZERO, dir, height, angle);
+            let inside = cone_point(&cone, s, f, side);
+            // Move the box so the cone point lies inside it
+            let moved = Obb::new(inside + (b.center - obb_point(&b, fraction)), b.half_extents, b.rotation);
+            prop_assert!(cone.intersects_obb(&moved));
+
+            // A separating axis means no intersection
+            let corners = b.corners();
+            for axis in b.axes().into_iter().chain([dir, -dir]) {
+                let box_min = corners.iter().map(|c| c.dot(axis)).fold(f32::INFINITY, f32::min);
+                if cone.support(axis).dot(axis) < box_min - 1e-3 {
+                    prop_assert!(!cone.intersects_obb(&b));
+                }
+            }
+            if !b.intersects_sphere(&cone.bounding_sphere()) {
+                prop_assert!(!cone.intersects_obb(&b));
+            }
+            prop_assert_eq!(cone.intersects_aabb(&b.bounds()), cone.intersects_obb(&Obb::from_aabb(&b.bounds())));
+        }
+
+        #[test]
+        fn prop_ray_cone(dir in unit_vec3(), height in 1.0f32..20.0, angle in 0.1f32..1.4, origin in vec3(30.0),
+                         s in 0.05f32..1.0, f in 0.0f32..0.95, side in unit_vec3()) {
+            let cone = Cone::new(Vec3::ZERO, dir, height, angle);
+            let target = cone_point(&cone, s, f, side);
+            prop_assume!((target - origin).length() > 0.1);
+            let ray = Ray::new(origin, target - origin);
+
+            let t = ray.intersect_cone(&cone);
+            prop_assert!(t.is_some());
+            let t = t.unwrap();
+            prop_assert!(t <= (target - origin).length() + 1e-3);
+            if cone.contains_point(origin) {
+                prop_assert_eq!(t, 0.0);
+            } else {
+                // The entry point lies on the side or the base cap
+                let hit = ray.at(t);
+                let along = hit.dot(dir);
+                let radial = (hit - dir * along).length();
+                let tolerance = 1e-3 * (1.0 + cone.base_radius());
+                prop_assert!(
+                    (along - height).abs() < tolerance || (radial - along * angle.tan()).abs() < tolerance,
+                    "hit {:?} is not on the cone surface", hit
+                );
+            }
+
+            let away = Ray::new(origin, origin - cone.bounding_sphere().center);
+            if (origin - cone.bounding_sphere().center).length() > cone.bounding_sphere().radius {
+                prop_assert!(away.intersect_cone(&cone).is_none());
+            }
+        }
+    }
+}
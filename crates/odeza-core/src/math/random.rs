@@ -0,0 +1,214 @@
+//! Deterministic Random Numbers
+//!
+//! PCG32 generator with independent streams. Every output is derived from
+//! integer arithmetic and exact float conversions, so a seed produces the
+//! same sequence on every platform and engine version.
+
+use super::Vec3;
+
+/// Default stream used by [`Pcg32::new`]
+const DEFAULT_STREAM: u64 = 0xda3e_39cb_94b9_5bdb;
+
+/// LCG multiplier from the PCG reference implementation
+const MULTIPLIER: u64 = 6_364_136_223_846_793_005;
+
+/// PCG32 (XSH RR) random number generator
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pcg32 {
+    state: u64,
+    increment: u64,
+}
+
+impl Pcg32 {
+    /// Create a generator on the default stream
+    pub fn new(seed: u64) -> Self {
+        Self::with_stream(seed, DEFAULT_STREAM)
+    }
+
+    /// Create a generator on a specific stream
+    ///
+    /// Generators with the same seed but different streams produce
+    /// independent sequences.
+    pub fn with_stream(seed: u64, stream: u64) -> Self {
+        let mut rng = Self {
+            state: 0,
+            increment: (stream << 1) | 1,
+        };
+        rng.step();
+        rng.state = rng.state.wrapping_add(seed);
+        rng.step();
+        rng
+    }
+
+    /// Derive an independent generator for a sub-system (e.g. one per cell)
+    pub fn fork(&mut self, stream: u64) -> Pcg32 {
+        Self::with_stream(self.next_u64(), stream)
+    }
+
+    fn step(&mut self) {
+        self.state = self.state.wrapping_mul(MULTIPLIER).wrapping_add(self.increment);
+    }
+
+    /// Get the next 32 random bits
+    pub fn next_u32(&mut self) -> u32 {
+        let old = self.state;
+        self.step();
+        let xorshifted = (((old >> 18) ^ old) >> 27) as u32;
+        let rotation = (old >> 59) as u32;
+        xorshifted.rotate_right(rotation)
+    }
+
+    /// Get the next 64 random bits
+    pub fn next_u64(&mut self) -> u64 {
+        let high = u64::from(self.next_u32());
+        (high << 32) | u64::from(self.next_u32())
+    }
+
+    /// Get a float in [0, 1)
+    pub fn next_f32(&mut self) -> f32 {
+        // 24 bits fit the mantissa exactly
+        (self.next_u32() >> 8) as f32 * (1.0 / 16_777_216.0)
+    }
+
+    /// Get a double in [0, 1)
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 * (1.0 / 9_007_199_254_740_992.0)
+    }
+
+    /// Get an integer in [0, bound) without modulo bias
+    ///
+    /// Returns 0 when `bound` is 0.
+    pub fn below(&mut self, bound: u32) -> u32 {
+        if bound == 0 {
+            return 0;
+        }
+        let threshold = bound.wrapping_neg() % bound;
+        loop {
+            let value = self.next_u32();
+            if value >= threshold {
+                return value % bound;
+            }
+        }
+    }
+
+    /// Get an integer in [min, max)
+    pub fn range_i32(&mut self, min: i32, max: i32) -> i32 {
+        if max <= min {
+            return min;
+        }
+        let span = max.wrapping_sub(min) as u32;
+        min.wrapping_add(self.below(span) as i32)
+    }
+
+    /// Get a float in [min, max)
+    pub fn range_f32(&mut self, min: f32, max: f32) -> f32 {
+        min + (max - min) * self.next_f32()
+    }
+
+    /// Get a random bool
+    pub fn next_bool(&mut self) -> bool {
+        self.next_u32() >> 31 == 1
+    }
+
+    /// Return true with the given probability
+    pub fn chance(&mut self, probability: f32) -> bool {
+        self.next_f32() < probability
+    }
+
+    /// Get a uniformly distributed unit vector
+    ///
+    /// Uses rejection sampling rather than trigonometry, which isn't
+    /// guaranteed to round identically across platforms.
+    pub fn unit_vector(&mut self) -> Vec3 {
+        loop {
+            let v = Vec3::new(
+                self.range_f32(-1.0, 1.0),
+                self.range_f32(-1.0, 1.0),
+                self.range_f32(-1.0, 1.0),
+            );
+            let length_sq = v.length_squared();
+            if length_sq > 1e-4 && length_sq <= 1.0 {
+                return v / length_sq.sqrt();
+            }
+        }
+    }
+
+    /// Get a uniformly distributed point inside the unit sphere
+    pub fn in_unit_sphere(&mut self) -> Vec3 {
+        loop {
+            let v = Vec3::new(
+                self.range_f32(-1.0, 1.0),
+                self.range_f32(-1.0, 1.0),
+                self.range_f32(-1.0, 1.0),
+            );
+            if v.length_squared() <= 1.0 {
+                return v;
+            }
+        }
+    }
+
+    /// Shuffle a slice in place (Fisher-Yates)
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            let j = self.below(i as u32 + 1) as usize;
+            items.swap(i, j);
+        }
+    }
+
+    /// Pick a random element
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> Option<&'a T> {
+        if items.is_empty() {
+            None
+        } else {
+            Some(&items[self.below(items.len() as u32) as usize])
+        }
+    }
+}
+
+impl Default for Pcg32 {
+    fn default() -> Self {
+        Self::new(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pcg32_reference_output() {
+        // Published output of the PCG reference demo (seed 42, sequence 54)
+        let mut rng = Pcg32::with_stream(42, 54);
+        let expected = [0xa15c02b7, 0x7b47f409, 0xba1d3330, 0x83d2f293, 0xbfa4784b, 0xcbed606e];
+        for value in expected {
+            assert_eq!(rng.next_u32(), value);
+        }
+    }
+
+    #[test]
+    fn test_streams_and_ranges() {
+        let mut a = Pcg32::with_stream(7, 1);
+        let mut b = Pcg32::with_stream(7, 2);
+        let a_values: Vec<u32> = (0..8).map(|_| a.next_u32()).collect();
+        let b_values: Vec<u32> = (0..8).map(|_| b.next_u32()).collect();
+        assert_ne!(a_values, b_values);
+        assert_eq!(Pcg32::with_stream(7, 1).next_u32(), a_values[0]);
+
+        let mut rng = Pcg32::new(123);
+        for _ in 0..1000 {
+            let f = rng.next_f32();
+            assert!((0.0..1.0).contains(&f));
+            assert!((-5..5).contains(&rng.range_i32(-5, 5)));
+            assert!(rng.below(3) < 3);
+            assert!((rng.unit_vector().length() - 1.0).abs() < 1e-5);
+        }
+
+        let mut items: Vec<u32> = (0..10).collect();
+        Pcg32::new(1).shuffle(&mut items);
+        let mut again: Vec<u32> = (0..10).collect();
+        Pcg32::new(1).shuffle(&mut again);
+        assert_eq!(items, again);
+        items.sort();
+        assert_eq!(items, (0..10).collect::<Vec<_>>());
+    }
+}
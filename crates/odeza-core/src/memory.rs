@@ -2,14 +2,22 @@
 //!
 //! Custom allocators optimized for game engine workloads:
 //! - Frame allocator for per-frame temporary data
+//! - Typed frame-scoped collections and N-buffered frame allocation
 //! - Arena allocator for grouped allocations
+//! - Stack allocator with markers for nested temporary scopes
 //! - Pool allocator for fixed-size objects
+//! - Debug-build poisoning of freed memory and leak reports for typed frame
+//!   objects, stack allocations, undropped arena values and pool values
 //! - Memory tracking and budget enforcement
+//! - Global allocator wrapper for per-subsystem attribution
 
-use std::alloc::{alloc, dealloc, Layout};
-use std::cell::UnsafeCell;
+use std::alloc::{alloc, dealloc, GlobalAlloc, Layout};
+use std::cell::{Cell, UnsafeCell};
+use std::marker::PhantomData;
+use std::mem::MaybeUninit;
+use std::panic::Location;
 use std::ptr::NonNull;
-use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::atomic::{AtomicU32, AtomicU8, AtomicUsize, Ordering};
 
 use parking_lot::Mutex;
 
@@ -44,16 +52,51 @@ pub struct MemoryStats {
 
 impl MemoryStats {
     /// Create new memory stats
-    pub fn new() -> Self {
-        Self::default()
+    pub const fn new() -> Self {
+        Self {
+            allocated: AtomicUsize::new(0),
+            peak: AtomicUsize::new(0),
+            allocation_count: AtomicUsize::new(0),
+        }
     }
 
     /// Record an allocation
     pub fn record_alloc(&self, size: usize) {
-        let new_size = self.allocated.fetch_add(size, Ordering::Relaxed) + size;
         self.allocation_count.fetch_add(1, Ordering::Relaxed);
-        
-        // Update peak if needed
+        self.grow(size);
+    }
+
+    /// Record an allocation unless it would take the total above `limit`
+    ///
+    /// The check and the update are one atomic step, so concurrent callers
+    /// can't overshoot the limit together. Returns the current total if the
+    /// allocation was refused.
+    fn try_record_alloc(&self, size: usize, limit: usize) -> Result<(), usize> {
+        let mut used = self.allocated.load(Ordering::Relaxed);
+        loop {
+            let new_size = used.checked_add(size).filter(|&new_size| new_size <= limit).ok_or(used)?;
+            match self
+                .allocated
+                .compare_exchange_weak(used, new_size, Ordering::Relaxed, Ordering::Relaxed)
+            {
+                Ok(_) => {
+                    self.allocation_count.fetch_add(1, Ordering::Relaxed);
+                    self.raise_peak(new_size);
+                    return Ok(());
+                }
+                Err(current) => used = current,
+            }
+        }
+    }
+
+    /// Grow the allocated byte count without counting a new allocation
+    fn grow(&self, size: usize) {
+        let new_size = self.allocated.fetch_add(size, Ordering::Relaxed) + size;
+        self.raise_peak(new_size);
+    }
+
+    /// Raise the peak to `new_size` if it is higher
+    fn raise_peak(&self, new_size: usize) {
         let mut peak = self.peak.load(Ordering::Relaxed);
         while new_size > peak {
             match self.peak.compare_exchange_weak(
@@ -96,10 +139,123 @@ impl MemoryStats {
     }
 }
 
+/// Byte written over freed allocator memory in debug builds
+pub const POISON_BYTE: u8 = 0xDD;
+
+/// Allocation still live when its allocator was reset or dropped
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OutstandingAllocation {
+    /// Address of the allocation
+    pub address: usize,
+    /// Size in bytes
+    pub size: usize,
+    /// Call site that made the allocation
+    pub location: &'static Location<'static>,
+}
+
+/// Live allocations and their call sites, tracked in debug builds only
+///
+/// Raw frame and arena `alloc` calls are not recorded: that memory is never
+/// freed individually, so it could only ever be reported as outstanding.
+#[derive(Default)]
+struct AllocationLog {
+    #[cfg(debug_assertions)]
+    live: Mutex<ahash::AHashMap<usize, OutstandingAllocation>>,
+}
+
+#[cfg_attr(not(debug_assertions), allow(unused_variables))]
+impl AllocationLog {
+    fn record(&self, address: usize, size: usize, location: &'static Location<'static>) {
+        #[cfg(debug_assertions)]
+        self.live.lock().insert(address, OutstandingAllocation { address, size, location });
+    }
+
+    fn resize(&self, address: usize, size: usize) {
+        #[cfg(debug_assertions)]
+        if let Some(record) = self.live.lock().get_mut(&address) {
+            record.size = size;
+        }
+    }
+
+    fn remove(&self, address: usize) {
+        #[cfg(debug_assertions)]
+        self.live.lock().remove(&address);
+    }
+
+    /// Forget every allocation at or above `address`
+    fn remove_from(&self, address: usize) {
+        #[cfg(debug_assertions)]
+        self.live.lock().retain(|&live, _| live < address);
+    }
+
+    /// Get the live allocations, ordered by address
+    fn outstanding(&self) -> Vec<OutstandingAllocation> {
+        #[cfg(debug_assertions)]
+        {
+            let mut live: Vec<_> = self.live.lock().values().copied().collect();
+            live.sort_unstable_by_key(|record| record.address);
+            live
+        }
+        #[cfg(not(debug_assertions))]
+        Vec::new()
+    }
+
+    /// Log and forget all live allocations
+    fn report(&self, allocator: &str, event: &str) {
+        #[cfg(debug_assertions)]
+        {
+            let live = self.outstanding();
+            self.live.lock().clear();
+            if live.is_empty() {
+                return;
+            }
+            log::warn!(
+                "{} {} with {} outstanding allocation(s)",
+                allocator,
+                event,
+                live.len()
+            );
+            for record in &live {
+                log::warn!(
+                    "  {} bytes at {:#x} allocated at {}",
+                    record.size,
+                    record.address,
+                    record.location
+                );
+            }
+        }
+    }
+}
+
+/// Offset of the first `align`-aligned address at or after `base + offset`
+///
+/// Blocks are only 16-byte aligned, so over-aligned types need the address
+/// aligned rather than the offset.
+#[inline]
+fn aligned_offset(base: NonNull<u8>, offset: usize, align: usize) -> usize {
+    let address = base.as_ptr() as usize + offset;
+    offset + (address.next_multiple_of(align) - address)
+}
+
+/// Fill freed memory with [`POISON_BYTE`] in debug builds
+///
+/// # Safety
+/// `ptr` must be valid for writes of `len` bytes.
+#[inline]
+#[cfg_attr(not(debug_assertions), allow(unused_variables))]
+unsafe fn poison(ptr: *mut u8, len: usize) {
+    #[cfg(debug_assertions)]
+    unsafe {
+        std::ptr::write_bytes(ptr, POISON_BYTE, len);
+    }
+}
+
 /// Frame allocator for per-frame temporary allocations
 ///
 /// Allocations from this allocator are valid only for the current frame.
 /// At the end of each frame, the allocator is reset, freeing all memory at once.
+/// In debug builds, reset poisons the used memory and reports typed frame
+/// objects that were leaked with `mem::forget`.
 pub struct FrameAllocator {
     /// Base pointer to the memory block
     base: NonNull<u8>,
@@ -109,6 +265,8 @@ pub struct FrameAllocator {
     offset: AtomicUsize,
     /// Memory statistics
     stats: MemoryStats,
+    /// Live typed frame objects
+    debug: AllocationLog,
 }
 
 impl FrameAllocator {
@@ -123,6 +281,7 @@ impl FrameAllocator {
             capacity,
             offset: AtomicUsize::new(0),
             stats: MemoryStats::new(),
+            debug: AllocationLog::default(),
         }
     }
 
@@ -134,9 +293,7 @@ impl FrameAllocator {
         
         loop {
             let current_offset = self.offset.load(Ordering::Relaxed);
-            
-            // Calculate aligned offset
-            let aligned_offset = (current_offset + align - 1) & !(align - 1);
+            let aligned_offset = aligned_offset(self.base, current_offset, align);
             let new_offset = aligned_offset + size;
             
             if new_offset > self.capacity {
@@ -169,14 +326,47 @@ impl FrameAllocator {
         Some(ptr)
     }
 
+    /// Try to extend the most recent allocation in place
+    ///
+    /// Succeeds only if the block ending at `end` is still the last one handed out.
+    fn try_grow_in_place(&self, end: usize, additional: usize) -> bool {
+        let new_end = end + additional;
+        if new_end > self.capacity {
+            return false;
+        }
+        let grown = self
+            .offset
+            .compare_exchange(end, new_end, Ordering::Relaxed, Ordering::Relaxed)
+            .is_ok();
+        if grown {
+            self.stats.grow(additional);
+        }
+        grown
+    }
+
     /// Reset the allocator for the next frame
     ///
-    /// This invalidates all previous allocations!
-    pub fn reset(&self) {
+    /// This invalidates all previous allocations! Typed collections created
+    /// through [`frame`](Self::frame) borrow the allocator, so they cannot be
+    /// alive at this point.
+    pub fn reset(&mut self) {
+        self.debug.report("FrameAllocator", "reset");
+        let used = self.used();
+        unsafe { poison(self.base.as_ptr(), used) };
         self.offset.store(0, Ordering::Relaxed);
         self.stats.reset();
     }
 
+    /// Get typed frame objects that are still live (debug builds only)
+    pub fn outstanding_allocations(&self) -> Vec<OutstandingAllocation> {
+        self.debug.outstanding()
+    }
+
+    /// Get a token for creating frame-scoped collections
+    pub fn frame(&self) -> Frame<'_> {
+        Frame { allocator: self }
+    }
+
     /// Get the current usage
     pub fn used(&self) -> usize {
         self.offset.load(Ordering::Relaxed)
@@ -200,6 +390,7 @@ impl FrameAllocator {
 
 impl Drop for FrameAllocator {
     fn drop(&mut self) {
+        self.debug.report("FrameAllocator", "dropped");
         let layout = Layout::from_size_align(self.capacity, 16).expect("Invalid layout");
         unsafe {
             dealloc(self.base.as_ptr(), layout);
@@ -211,280 +402,1403 @@ impl Drop for FrameAllocator {
 unsafe impl Send for FrameAllocator {}
 unsafe impl Sync for FrameAllocator {}
 
-/// Arena allocator for grouped allocations
+/// Token for allocating typed data from a [`FrameAllocator`]
 ///
-/// All allocations from an arena are freed together when the arena is dropped or reset.
-pub struct ArenaAllocator {
-    /// Memory blocks
-    blocks: Mutex<Vec<ArenaBlock>>,
-    /// Current block index
-    current_block: AtomicUsize,
-    /// Block size
-    block_size: usize,
-    /// Memory statistics
-    stats: MemoryStats,
+/// Everything created from a token borrows the allocator for `'f`, so the
+/// borrow checker rejects [`FrameAllocator::reset`] while any of it is alive.
+#[derive(Clone, Copy)]
+pub struct Frame<'f> {
+    allocator: &'f FrameAllocator,
 }
 
-struct ArenaBlock {
-    base: NonNull<u8>,
-    capacity: usize,
-    offset: usize,
-}
+impl<'f> Frame<'f> {
+    /// Get the underlying allocator
+    pub fn allocator(self) -> &'f FrameAllocator {
+        self.allocator
+    }
 
-impl ArenaBlock {
-    fn new(size: usize) -> Self {
-        let layout = Layout::from_size_align(size, 16).expect("Invalid layout");
-        let ptr = unsafe { alloc(layout) };
-        let base = NonNull::new(ptr).expect("Allocation failed");
-        
-        Self {
-            base,
-            capacity: size,
-            offset: 0,
-        }
+    /// Move a value into frame memory
+    ///
+    /// Returns None if the allocator is exhausted.
+    #[track_caller]
+    pub fn alloc<T>(self, value: T) -> Option<FrameBox<'f, T>> {
+        FrameBox::new_in(value, self)
     }
 
-    fn alloc(&mut self, size: usize, align: usize) -> Option<NonNull<u8>> {
-        let aligned_offset = (self.offset + align - 1) & !(align - 1);
-        let new_offset = aligned_offset + size;
-        
-        if new_offset > self.capacity {
-            return None;
-        }
-        
-        self.offset = new_offset;
-        let ptr = unsafe { self.base.as_ptr().add(aligned_offset) };
-        NonNull::new(ptr)
+    /// Create an empty frame vector
+    pub fn vec<T>(self) -> FrameVec<'f, T> {
+        FrameVec::new_in(self)
     }
 
-    fn reset(&mut self) {
-        self.offset = 0;
+    /// Create an empty frame string
+    pub fn string(self) -> FrameString<'f> {
+        FrameString::new_in(self)
     }
-}
 
-impl Drop for ArenaBlock {
-    fn drop(&mut self) {
-        let layout = Layout::from_size_align(self.capacity, 16).expect("Invalid layout");
+    /// Copy a slice into frame memory
+    ///
+    /// Returns None if the allocator is exhausted.
+    pub fn alloc_slice_copy<T: Copy>(self, values: &[T]) -> Option<&'f mut [T]> {
+        if std::mem::size_of::<T>() == 0 {
+            let ptr = NonNull::<T>::dangling().as_ptr();
+            return Some(unsafe { std::slice::from_raw_parts_mut(ptr, values.len()) });
+        }
+        let size = std::mem::size_of_val(values);
+        let ptr = self.allocator.alloc(size, std::mem::align_of::<T>())?.cast::<T>();
         unsafe {
-            dealloc(self.base.as_ptr(), layout);
+            std::ptr::copy_nonoverlapping(values.as_ptr(), ptr.as_ptr(), values.len());
+            Some(std::slice::from_raw_parts_mut(ptr.as_ptr(), values.len()))
         }
     }
 }
 
-impl ArenaAllocator {
-    /// Create a new arena allocator with the given block size
-    pub fn new(block_size: usize) -> Self {
-        let initial_block = ArenaBlock::new(block_size);
-        
-        Self {
-            blocks: Mutex::new(vec![initial_block]),
-            current_block: AtomicUsize::new(0),
-            block_size,
-            stats: MemoryStats::new(),
-        }
+/// Owned value living in frame memory
+///
+/// The value is dropped normally; its memory is reclaimed when the frame resets.
+pub struct FrameBox<'f, T> {
+    ptr: NonNull<T>,
+    allocator: &'f FrameAllocator,
+    _marker: PhantomData<T>,
+}
+
+impl<'f, T> FrameBox<'f, T> {
+    /// Move a value into frame memory
+    ///
+    /// Returns None if the allocator is exhausted.
+    #[track_caller]
+    pub fn new_in(value: T, frame: Frame<'f>) -> Option<Self> {
+        let size = std::mem::size_of::<T>();
+        let ptr = if size == 0 {
+            NonNull::dangling()
+        } else {
+            let ptr = frame
+                .allocator
+                .alloc(size, std::mem::align_of::<T>())?
+                .cast::<T>();
+            frame.allocator.debug.record(ptr.as_ptr() as usize, size, Location::caller());
+            ptr
+        };
+        unsafe { std::ptr::write(ptr.as_ptr(), value) };
+        Some(Self {
+            ptr,
+            allocator: frame.allocator,
+            _marker: PhantomData,
+        })
     }
+}
 
-    /// Allocate memory from the arena
-    pub fn alloc(&self, size: usize, align: usize) -> Option<NonNull<u8>> {
-        let mut blocks = self.blocks.lock();
-        let current = self.current_block.load(Ordering::Relaxed);
-        
-        // Try to allocate from current block
-        if let Some(block) = blocks.get_mut(current) {
-            if let Some(ptr) = block.alloc(size, align) {
-                self.stats.record_alloc(size);
-                return Some(ptr);
-            }
-        }
-        
-        // Need a new block
-        let new_block_size = self.block_size.max(size);
-        let mut new_block = ArenaBlock::new(new_block_size);
-        let ptr = new_block.alloc(size, align);
-        blocks.push(new_block);
-        self.current_block.store(blocks.len() - 1, Ordering::Relaxed);
-        
-        self.stats.record_alloc(size);
-        ptr
+impl<T> std::ops::Deref for FrameBox<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        unsafe { self.ptr.as_ref() }
     }
+}
 
-    /// Allocate and initialize with a value
-    pub fn alloc_init<T>(&self, value: T) -> Option<&mut T> {
-        let size = std::mem::size_of::<T>();
-        let align = std::mem::align_of::<T>();
-        let ptr = self.alloc(size, align)?;
-        
-        unsafe {
-            let typed_ptr = ptr.as_ptr() as *mut T;
-            std::ptr::write(typed_ptr, value);
-            Some(&mut *typed_ptr)
-        }
+impl<T> std::ops::DerefMut for FrameBox<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        unsafe { self.ptr.as_mut() }
     }
+}
 
-    /// Reset the arena, freeing all allocations
-    pub fn reset(&self) {
-        let mut blocks = self.blocks.lock();
-        for block in blocks.iter_mut() {
-            block.reset();
+impl<T> Drop for FrameBox<'_, T> {
+    fn drop(&mut self) {
+        unsafe { std::ptr::drop_in_place(self.ptr.as_ptr()) };
+        if std::mem::size_of::<T>() != 0 {
+            self.allocator.debug.remove(self.ptr.as_ptr() as usize);
         }
-        // Keep only the first block
-        blocks.truncate(1);
-        self.current_block.store(0, Ordering::Relaxed);
-        self.stats.reset();
     }
+}
 
-    /// Get memory statistics
-    pub fn stats(&self) -> &MemoryStats {
-        &self.stats
+impl<T: std::fmt::Debug> std::fmt::Debug for FrameBox<'_, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        (**self).fmt(f)
     }
 }
 
-/// Pool allocator for fixed-size objects
-///
-/// Efficient allocation and deallocation of objects of a single size.
-pub struct PoolAllocator<T> {
-    /// Storage for objects
-    storage: Mutex<PoolStorage<T>>,
-    /// Memory statistics
-    stats: MemoryStats,
-}
+// Safety: FrameBox owns its value like Box does
+unsafe impl<T: Send> Send for FrameBox<'_, T> {}
+unsafe impl<T: Sync> Sync for FrameBox<'_, T> {}
 
-struct PoolStorage<T> {
-    /// Allocated chunks
-    chunks: Vec<Box<[UnsafeCell<std::mem::MaybeUninit<T>>]>>,
-    /// Free list
-    free_list: Vec<*mut T>,
-    /// Chunk size
-    chunk_size: usize,
+/// Growable array living in frame memory
+///
+/// Growing allocates a larger block from the frame allocator (extending in
+/// place when the vector owns the most recent allocation); the old block is
+/// reclaimed when the frame resets.
+pub struct FrameVec<'f, T> {
+    ptr: NonNull<T>,
+    len: usize,
+    capacity: usize,
+    allocator: &'f FrameAllocator,
+    _marker: PhantomData<T>,
 }
 
-impl<T> PoolAllocator<T> {
-    /// Create a new pool allocator with the given chunk size
-    pub fn new(chunk_size: usize) -> Self {
-        let chunk_size = chunk_size.max(16);
-        
+impl<'f, T> FrameVec<'f, T> {
+    /// Create an empty vector that allocates from the given frame
+    pub fn new_in(frame: Frame<'f>) -> Self {
+        let capacity = if std::mem::size_of::<T>() == 0 { usize::MAX } else { 0 };
         Self {
-            storage: Mutex::new(PoolStorage {
-                chunks: Vec::new(),
-                free_list: Vec::with_capacity(chunk_size),
-                chunk_size,
-            }),
-            stats: MemoryStats::new(),
+            ptr: NonNull::dangling(),
+            len: 0,
+            capacity,
+            allocator: frame.allocator,
+            _marker: PhantomData,
         }
     }
 
-    /// Allocate an object from the pool
-    pub fn alloc(&self) -> Option<NonNull<T>> {
-        let mut storage = self.storage.lock();
-        
-        // Try to get from free list
-        if let Some(ptr) = storage.free_list.pop() {
-            self.stats.record_alloc(std::mem::size_of::<T>());
-            return NonNull::new(ptr);
-        }
-        
-        // Allocate new chunk
-        let chunk: Box<[UnsafeCell<std::mem::MaybeUninit<T>>]> = (0..storage.chunk_size)
-            .map(|_| UnsafeCell::new(std::mem::MaybeUninit::uninit()))
-            .collect();
-        
-        // Add all slots to free list (except the first which we return)
-        for slot in chunk.iter().skip(1) {
-            storage.free_list.push(slot.get() as *mut T);
-        }
-        
-        let first_ptr = chunk[0].get() as *mut T;
-        storage.chunks.push(chunk);
-        
-        self.stats.record_alloc(std::mem::size_of::<T>());
-        NonNull::new(first_ptr)
+    /// Create a vector with room for `capacity` elements
+    ///
+    /// Returns None if the allocator is exhausted.
+    #[track_caller]
+    pub fn with_capacity_in(capacity: usize, frame: Frame<'f>) -> Option<Self> {
+        let mut vec = Self::new_in(frame);
+        vec.try_reserve(capacity).then_some(vec)
     }
 
-    /// Allocate and initialize an object
-    pub fn alloc_init(&self, value: T) -> Option<&mut T> {
-        let ptr = self.alloc()?;
-        unsafe {
-            std::ptr::write(ptr.as_ptr(), value);
-            Some(&mut *ptr.as_ptr())
-        }
+    /// Get the number of elements
+    pub fn len(&self) -> usize {
+        self.len
     }
 
-    /// Return an object to the pool
-    ///
-    /// # Safety
-    /// The pointer must have been allocated from this pool
-    pub unsafe fn dealloc(&self, ptr: NonNull<T>) {
-        let mut storage = self.storage.lock();
-        storage.free_list.push(ptr.as_ptr());
-        self.stats.record_dealloc(std::mem::size_of::<T>());
+    /// Check if the vector is empty
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
     }
 
-    /// Get memory statistics
-    pub fn stats(&self) -> &MemoryStats {
-        &self.stats
+    /// Get the number of elements the vector can hold without growing
+    pub fn capacity(&self) -> usize {
+        self.capacity
     }
-}
 
-// Safety: Pool uses mutex for synchronization
-unsafe impl<T: Send> Send for PoolAllocator<T> {}
-unsafe impl<T: Send> Sync for PoolAllocator<T> {}
+    /// Get a raw pointer to the elements (e.g. for GPU upload)
+    pub fn as_ptr(&self) -> *const T {
+        self.ptr.as_ptr()
+    }
 
-/// Global memory tracker for all subsystems
-pub struct MemoryTracker {
-    subsystems: Mutex<Vec<(String, MemoryStats, MemoryBudget)>>,
-}
+    /// Reserve room for `additional` more elements
+    ///
+    /// Returns false if the allocator is exhausted.
+    #[track_caller]
+    pub fn try_reserve(&mut self, additional: usize) -> bool {
+        let Some(required) = self.len.checked_add(additional) else {
+            return false;
+        };
+        if required <= self.capacity {
+            return true;
+        }
 
-impl MemoryTracker {
-    /// Create a new memory tracker
-    pub fn new() -> Self {
-        Self {
-            subsystems: Mutex::new(Vec::new()),
+        let elem_size = std::mem::size_of::<T>();
+        let new_capacity = required.max(self.capacity * 2).max(4);
+        let Some(new_size) = new_capacity.checked_mul(elem_size) else {
+            return false;
+        };
+
+        // Extend in place if nothing was allocated after us
+        if self.capacity > 0 {
+            let base = self.allocator.base.as_ptr() as usize;
+            let end = self.ptr.as_ptr() as usize - base + self.capacity * elem_size;
+            if self.allocator.try_grow_in_place(end, new_size - self.capacity * elem_size) {
+                self.allocator.debug.resize(self.ptr.as_ptr() as usize, new_size);
+                self.capacity = new_capacity;
+                return true;
+            }
+        }
+
+        let Some(new_ptr) = self.allocator.alloc(new_size, std::mem::align_of::<T>()) else {
+            return false;
+        };
+        let new_ptr = new_ptr.cast::<T>();
+        unsafe {
+            std::ptr::copy_nonoverlapping(self.ptr.as_ptr(), new_ptr.as_ptr(), self.len);
         }
+        if self.capacity > 0 {
+            self.allocator.debug.remove(self.ptr.as_ptr() as usize);
+        }
+        self.allocator.debug.record(new_ptr.as_ptr() as usize, new_size, Location::caller());
+        self.ptr = new_ptr;
+        self.capacity = new_capacity;
+        true
     }
 
-    /// Register a subsystem for tracking
-    pub fn register_subsystem(&self, name: &str, budget: MemoryBudget) -> usize {
-        let mut subsystems = self.subsystems.lock();
-        let id = subsystems.len();
-        subsystems.push((name.to_string(), MemoryStats::new(), budget));
-        id
+    /// Append an element
+    ///
+    /// # Panics
+    /// Panics if the frame allocator is exhausted.
+    #[track_caller]
+    pub fn push(&mut self, value: T) {
+        assert!(self.try_reserve(1), "Frame allocator exhausted");
+        unsafe { std::ptr::write(self.ptr.as_ptr().add(self.len), value) };
+        self.len += 1;
     }
 
-    /// Record an allocation for a subsystem
-    pub fn record_alloc(&self, subsystem_id: usize, size: usize) -> bool {
-        let subsystems = self.subsystems.lock();
-        if let Some((_, stats, budget)) = subsystems.get(subsystem_id) {
-            let new_size = stats.allocated.load(Ordering::Relaxed) + size;
-            if new_size > budget.max_bytes {
-                return false; // Would exceed budget
-            }
-            stats.record_alloc(size);
-            true
-        } else {
-            false
+    /// Remove and return the last element
+    pub fn pop(&mut self) -> Option<T> {
+        if self.len == 0 {
+            return None;
         }
+        self.len -= 1;
+        Some(unsafe { std::ptr::read(self.ptr.as_ptr().add(self.len)) })
     }
 
-    /// Record a deallocation for a subsystem
-    pub fn record_dealloc(&self, subsystem_id: usize, size: usize) {
-        let subsystems = self.subsystems.lock();
-        if let Some((_, stats, _)) = subsystems.get(subsystem_id) {
-            stats.record_dealloc(size);
-        }
+    /// Remove all elements, keeping the capacity
+    pub fn clear(&mut self) {
+        let elements = std::ptr::slice_from_raw_parts_mut(self.ptr.as_ptr(), self.len);
+        self.len = 0;
+        unsafe { std::ptr::drop_in_place(elements) };
     }
+}
 
-    /// Get usage report for all subsystems
-    pub fn get_report(&self) -> Vec<(String, usize, usize, f32)> {
+impl<T: Copy> FrameVec<'_, T> {
+    /// Append all elements of a slice
+    ///
+    /// # Panics
+    /// Panics if the frame allocator is exhausted.
+    #[track_caller]
+    pub fn extend_from_slice(&mut self, values: &[T]) {
+        assert!(self.try_reserve(values.len()), "Frame allocator exhausted");
+        unsafe {
+            std::ptr::copy_nonoverlapping(
+                values.as_ptr(),
+                self.ptr.as_ptr().add(self.len),
+                values.len(),
+            );
+        }
+        self.len += values.len();
+    }
+}
+
+impl<T> std::ops::Deref for FrameVec<'_, T> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        unsafe { std::slice::from_raw_parts(self.ptr.as_ptr(), self.len) }
+    }
+}
+
+impl<T> std::ops::DerefMut for FrameVec<'_, T> {
+    fn deref_mut(&mut self) -> &mut [T] {
+        unsafe { std::slice::from_raw_parts_mut(self.ptr.as_ptr(), self.len) }
+    }
+}
+
+impl<T> Extend<T> for FrameVec<'_, T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        let iter = iter.into_iter();
+        self.try_reserve(iter.size_hint().0);
+        for value in iter {
+            self.push(value);
+        }
+    }
+}
+
+impl<T> Drop for FrameVec<'_, T> {
+    fn drop(&mut self) {
+        self.clear();
+        if self.capacity > 0 && std::mem::size_of::<T>() != 0 {
+            self.allocator.debug.remove(self.ptr.as_ptr() as usize);
+        }
+    }
+}
+
+impl<T: std::fmt::Debug> std::fmt::Debug for FrameVec<'_, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+// Safety: FrameVec owns its elements like Vec does, and FrameAllocator is Sync
+unsafe impl<T: Send> Send for FrameVec<'_, T> {}
+unsafe impl<T: Sync> Sync for FrameVec<'_, T> {}
+
+/// UTF-8 string living in frame memory
+pub struct FrameString<'f> {
+    bytes: FrameVec<'f, u8>,
+}
+
+impl<'f> FrameString<'f> {
+    /// Create an empty string that allocates from the given frame
+    pub fn new_in(frame: Frame<'f>) -> Self {
+        Self {
+            bytes: FrameVec::new_in(frame),
+        }
+    }
+
+    /// Append a string slice
+    ///
+    /// # Panics
+    /// Panics if the frame allocator is exhausted.
+    #[track_caller]
+    pub fn push_str(&mut self, s: &str) {
+        self.bytes.extend_from_slice(s.as_bytes());
+    }
+
+    /// Append a character
+    ///
+    /// # Panics
+    /// Panics if the frame allocator is exhausted.
+    #[track_caller]
+    pub fn push(&mut self, c: char) {
+        self.push_str(c.encode_utf8(&mut [0; 4]));
+    }
+
+    /// Get the string as a slice
+    pub fn as_str(&self) -> &str {
+        // Only whole `str`s are ever appended
+        unsafe { std::str::from_utf8_unchecked(&self.bytes) }
+    }
+
+    /// Get the length in bytes
+    pub fn len(&self) -> usize {
+        self.bytes.len()
+    }
+
+    /// Check if the string is empty
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    /// Remove all contents, keeping the capacity
+    pub fn clear(&mut self) {
+        self.bytes.clear();
+    }
+}
+
+impl std::ops::Deref for FrameString<'_> {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl std::fmt::Write for FrameString<'_> {
+    fn write_str(&mut self, s: &str) -> std::fmt::Result {
+        if self.bytes.try_reserve(s.len()) {
+            self.push_str(s);
+            Ok(())
+        } else {
+            Err(std::fmt::Error)
+        }
+    }
+}
+
+impl std::fmt::Display for FrameString<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::fmt::Debug for FrameString<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+/// Ring of frame allocators for data that must outlive its frame
+///
+/// With `N` buffers, memory handed out during frame `n` is not reset until
+/// frame `n + N` begins, so with the default of three buffers data written
+/// in frame N stays valid through frame N+2 while the GPU consumes it.
+/// Typed collections still borrow the ring, so CPU-side access ends at the
+/// next [`begin_frame`](Self::begin_frame); raw pointers (e.g. from
+/// [`FrameVec::as_ptr`]) remain valid for the full window.
+pub struct BufferedFrameAllocator<const N: usize = 3> {
+    frames: [FrameAllocator; N],
+    frame_number: u64,
+}
+
+impl<const N: usize> BufferedFrameAllocator<N> {
+    /// Create a ring of `N` frame allocators with the given per-frame capacity
+    pub fn new(capacity_per_frame: usize) -> Self {
+        assert!(N > 0, "BufferedFrameAllocator needs at least one buffer");
+        Self {
+            frames: std::array::from_fn(|_| FrameAllocator::new(capacity_per_frame)),
+            frame_number: 0,
+        }
+    }
+
+    /// Advance to the next frame, recycling the oldest buffer
+    pub fn begin_frame(&mut self) -> u64 {
+        self.frame_number += 1;
+        let index = (self.frame_number % N as u64) as usize;
+        self.frames[index].reset();
+        self.frame_number
+    }
+
+    /// Get the current frame number
+    pub fn frame_number(&self) -> u64 {
+        self.frame_number
+    }
+
+    /// Get the allocator serving the current frame
+    pub fn current(&self) -> &FrameAllocator {
+        &self.frames[(self.frame_number % N as u64) as usize]
+    }
+
+    /// Get a token for creating collections in the current frame
+    pub fn frame(&self) -> Frame<'_> {
+        self.current().frame()
+    }
+
+    /// Get the allocator that served an earlier frame, if its data is still valid
+    pub fn get(&self, frame_number: u64) -> Option<&FrameAllocator> {
+        if frame_number > self.frame_number || self.frame_number - frame_number >= N as u64 {
+            return None;
+        }
+        Some(&self.frames[(frame_number % N as u64) as usize])
+    }
+}
+
+/// Marker recording a [`StackAllocator`] position
+///
+/// Popping back to a marker frees everything allocated after it was pushed.
+#[must_use]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StackMarker {
+    offset: usize,
+    id: u64,
+}
+
+/// Stack allocator for nested temporary scopes
+///
+/// Allocations are released in LIFO order by popping back to a marker, so a
+/// recursive loader can push a marker per level and discard that level's
+/// scratch data on the way out. In debug builds, popped memory is poisoned
+/// and allocations still live at reset or drop are reported.
+pub struct StackAllocator {
+    /// Base pointer to the memory block
+    base: NonNull<u8>,
+    /// Size of the memory block
+    capacity: usize,
+    /// Current offset into the block
+    offset: AtomicUsize,
+    /// Ids of the markers currently pushed, innermost last
+    markers: Vec<u64>,
+    /// Id for the next marker; never reused, so popped markers stay invalid
+    next_marker: u64,
+    /// Memory statistics
+    stats: MemoryStats,
+    /// Live allocations
+    debug: AllocationLog,
+}
+
+impl StackAllocator {
+    /// Create a new stack allocator with the given capacity
+    pub fn new(capacity: usize) -> Self {
+        let layout = Layout::from_size_align(capacity, 16).expect("Invalid layout");
+        let ptr = unsafe { alloc(layout) };
+        let base = NonNull::new(ptr).expect("Allocation failed");
+
+        Self {
+            base,
+            capacity,
+            offset: AtomicUsize::new(0),
+            markers: Vec::new(),
+            next_marker: 0,
+            stats: MemoryStats::new(),
+            debug: AllocationLog::default(),
+        }
+    }
+
+    /// Allocate memory from the top of the stack
+    ///
+    /// Returns None if there is not enough space remaining
+    #[track_caller]
+    pub fn alloc(&self, size: usize, align: usize) -> Option<NonNull<u8>> {
+        let align = align.max(1);
+
+        loop {
+            let current_offset = self.offset.load(Ordering::Relaxed);
+            let aligned_offset = aligned_offset(self.base, current_offset, align);
+            let new_offset = aligned_offset + size;
+
+            if new_offset > self.capacity {
+                return None;
+            }
+
+            if self
+                .offset
+                .compare_exchange_weak(current_offset, new_offset, Ordering::Relaxed, Ordering::Relaxed)
+                .is_ok()
+            {
+                self.stats.record_alloc(new_offset - current_offset);
+                let ptr = unsafe { self.base.as_ptr().add(aligned_offset) };
+                self.debug.record(ptr as usize, size, Location::caller());
+                return NonNull::new(ptr);
+            }
+        }
+    }
+
+    /// Allocate and zero-initialize memory
+    #[track_caller]
+    pub fn alloc_zeroed(&self, size: usize, align: usize) -> Option<NonNull<u8>> {
+        let ptr = self.alloc(size, align)?;
+        unsafe {
+            std::ptr::write_bytes(ptr.as_ptr(), 0, size);
+        }
+        Some(ptr)
+    }
+
+    /// Record the current top of the stack
+    pub fn push_marker(&mut self) -> StackMarker {
+        let marker = StackMarker {
+            offset: self.used(),
+            id: self.next_marker,
+        };
+        self.next_marker += 1;
+        self.markers.push(marker.id);
+        marker
+    }
+
+    /// Free everything allocated since `marker` was pushed
+    ///
+    /// Markers pushed after `marker` are popped too. Returns false if the
+    /// marker was already popped or was pushed before the last reset.
+    pub fn pop_to_marker(&mut self, marker: StackMarker) -> bool {
+        let Some(depth) = self.markers.iter().rposition(|&id| id == marker.id) else {
+            return false;
+        };
+        let used = self.used();
+        self.debug.remove_from(self.base.as_ptr() as usize + marker.offset);
+        unsafe { poison(self.base.as_ptr().add(marker.offset), used - marker.offset) };
+        self.stats.record_dealloc(used - marker.offset);
+        self.offset.store(marker.offset, Ordering::Relaxed);
+        self.markers.truncate(depth);
+        true
+    }
+
+    /// Free all allocations and markers
+    pub fn reset(&mut self) {
+        self.debug.report("StackAllocator", "reset");
+        let used = self.used();
+        unsafe { poison(self.base.as_ptr(), used) };
+        self.offset.store(0, Ordering::Relaxed);
+        self.markers.clear();
+        self.stats.reset();
+    }
+
+    /// Get the number of markers currently pushed
+    pub fn depth(&self) -> usize {
+        self.markers.len()
+    }
+
+    /// Get allocations that have not been popped (debug builds only)
+    pub fn outstanding_allocations(&self) -> Vec<OutstandingAllocation> {
+        self.debug.outstanding()
+    }
+
+    /// Get the current usage
+    pub fn used(&self) -> usize {
+        self.offset.load(Ordering::Relaxed)
+    }
+
+    /// Get the capacity
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Get remaining space
+    pub fn remaining(&self) -> usize {
+        self.capacity - self.used()
+    }
+
+    /// Get memory statistics
+    pub fn stats(&self) -> &MemoryStats {
+        &self.stats
+    }
+}
+
+impl Drop for StackAllocator {
+    fn drop(&mut self) {
+        self.debug.report("StackAllocator", "dropped");
+        let layout = Layout::from_size_align(self.capacity, 16).expect("Invalid layout");
+        unsafe {
+            dealloc(self.base.as_ptr(), layout);
+        }
+    }
+}
+
+// Safety: Allocation uses atomic operations; popping requires exclusive access
+unsafe impl Send for StackAllocator {}
+unsafe impl Sync for StackAllocator {}
+
+/// Arena allocator for grouped allocations
+///
+/// All allocations from an arena are freed together when the arena is dropped or reset.
+/// Values placed with [`alloc_init`](Self::alloc_init) are never dropped, so in
+/// debug builds any that need dropping are reported at reset or drop.
+pub struct ArenaAllocator {
+    /// Memory blocks
+    blocks: Mutex<Vec<ArenaBlock>>,
+    /// Current block index
+    current_block: AtomicUsize,
+    /// Block size
+    block_size: usize,
+    /// Memory statistics
+    stats: MemoryStats,
+    /// Values whose destructors will not run
+    debug: AllocationLog,
+}
+
+struct ArenaBlock {
+    base: NonNull<u8>,
+    capacity: usize,
+    offset: usize,
+}
+
+impl ArenaBlock {
+    fn new(size: usize) -> Self {
+        let layout = Layout::from_size_align(size, 16).expect("Invalid layout");
+        let ptr = unsafe { alloc(layout) };
+        let base = NonNull::new(ptr).expect("Allocation failed");
+        
+        Self {
+            base,
+            capacity: size,
+            offset: 0,
+        }
+    }
+
+    fn alloc(&mut self, size: usize, align: usize) -> Option<NonNull<u8>> {
+        let aligned_offset = aligned_offset(self.base, self.offset, align.max(1));
+        let new_offset = aligned_offset + size;
+        
+        if new_offset > self.capacity {
+            return None;
+        }
+        
+        self.offset = new_offset;
+        let ptr = unsafe { self.base.as_ptr().add(aligned_offset) };
+        NonNull::new(ptr)
+    }
+
+    fn reset(&mut self) {
+        unsafe { poison(self.base.as_ptr(), self.offset) };
+        self.offset = 0;
+    }
+}
+
+impl Drop for ArenaBlock {
+    fn drop(&mut self) {
+        let layout = Layout::from_size_align(self.capacity, 16).expect("Invalid layout");
+        unsafe {
+            dealloc(self.base.as_ptr(), layout);
+        }
+    }
+}
+
+impl ArenaAllocator {
+    /// Create a new arena allocator with the given block size
+    pub fn new(block_size: usize) -> Self {
+        let initial_block = ArenaBlock::new(block_size);
+        
+        Self {
+            blocks: Mutex::new(vec![initial_block]),
+            current_block: AtomicUsize::new(0),
+            block_size,
+            stats: MemoryStats::new(),
+            debug: AllocationLog::default(),
+        }
+    }
+
+    /// Allocate memory from the arena
+    pub fn alloc(&self, size: usize, align: usize) -> Option<NonNull<u8>> {
+        let mut blocks = self.blocks.lock();
+        let current = self.current_block.load(Ordering::Relaxed);
+        
+        // Try to allocate from current block
+        if let Some(block) = blocks.get_mut(current) {
+            if let Some(ptr) = block.alloc(size, align) {
+                self.stats.record_alloc(size);
+                return Some(ptr);
+            }
+        }
+        
+        // Need a new block, with room to align within it
+        let new_block_size = self.block_size.max(size + align.saturating_sub(16));
+        let mut new_block = ArenaBlock::new(new_block_size);
+        let ptr = new_block.alloc(size, align);
+        blocks.push(new_block);
+        self.current_block.store(blocks.len() - 1, Ordering::Relaxed);
+        
+        self.stats.record_alloc(size);
+        ptr
+    }
+
+    /// Allocate and initialize with a value
+    ///
+    /// The value is never dropped; its memory is reclaimed on reset.
+    #[track_caller]
+    pub fn alloc_init<T>(&self, value: T) -> Option<&mut T> {
+        let size = std::mem::size_of::<T>();
+        let align = std::mem::align_of::<T>();
+        let ptr = self.alloc(size, align)?;
+        if std::mem::needs_drop::<T>() {
+            self.debug.record(ptr.as_ptr() as usize, size, Location::caller());
+        }
+        
+        unsafe {
+            let typed_ptr = ptr.as_ptr() as *mut T;
+            std::ptr::write(typed_ptr, value);
+            Some(&mut *typed_ptr)
+        }
+    }
+
+    /// Reset the arena, freeing all allocations
+    pub fn reset(&self) {
+        self.debug.report("ArenaAllocator", "reset");
+        let mut blocks = self.blocks.lock();
+        for block in blocks.iter_mut() {
+            block.reset();
+        }
+        // Keep only the first block
+        blocks.truncate(1);
+        self.current_block.store(0, Ordering::Relaxed);
+        self.stats.reset();
+    }
+
+    /// Get values whose destructors will not run (debug builds only)
+    pub fn outstanding_allocations(&self) -> Vec<OutstandingAllocation> {
+        self.debug.outstanding()
+    }
+
+    /// Get memory statistics
+    pub fn stats(&self) -> &MemoryStats {
+        &self.stats
+    }
+}
+
+impl Drop for ArenaAllocator {
+    fn drop(&mut self) {
+        self.debug.report("ArenaAllocator", "dropped");
+    }
+}
+
+/// Generational handle to an object in a [`PoolAllocator`]
+///
+/// Handles stay cheap to copy and never dangle: once the object is freed the
+/// slot's generation changes and lookups through old handles return None.
+pub struct PoolHandle<T> {
+    index: u32,
+    generation: u32,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T> PoolHandle<T> {
+    /// Get the slot index
+    pub fn index(&self) -> u32 {
+        self.index
+    }
+
+    /// Get the slot generation this handle refers to
+    pub fn generation(&self) -> u32 {
+        self.generation
+    }
+}
+
+impl<T> Clone for PoolHandle<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for PoolHandle<T> {}
+
+impl<T> PartialEq for PoolHandle<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.index == other.index && self.generation == other.generation
+    }
+}
+
+impl<T> Eq for PoolHandle<T> {}
+
+impl<T> std::hash::Hash for PoolHandle<T> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.index.hash(state);
+        self.generation.hash(state);
+    }
+}
+
+impl<T> std::fmt::Debug for PoolHandle<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PoolHandle")
+            .field("index", &self.index)
+            .field("generation", &self.generation)
+            .finish()
+    }
+}
+
+/// Slot in a pool chunk
+struct PoolSlot<T> {
+    /// Odd while the slot holds a live value
+    generation: AtomicU32,
+    value: UnsafeCell<MaybeUninit<T>>,
+}
+
+impl<T> PoolSlot<T> {
+    fn is_live(generation: u32) -> bool {
+        generation & 1 == 1
+    }
+}
+
+/// Pool allocator for fixed-size objects
+///
+/// Efficient allocation and deallocation of objects of a single size.
+/// Allocation is thread-safe; freeing and mutable access need exclusive
+/// access to the pool, so no reference can outlive the object it points to.
+/// In debug builds, freed slots are poisoned and objects never freed are
+/// reported when the pool is dropped.
+pub struct PoolAllocator<T> {
+    /// Storage for objects
+    storage: Mutex<PoolStorage<T>>,
+    /// Memory statistics
+    stats: MemoryStats,
+    /// Live objects
+    debug: AllocationLog,
+}
+
+struct PoolStorage<T> {
+    /// Allocated chunks (slot addresses are stable once allocated)
+    chunks: Vec<Box<[PoolSlot<T>]>>,
+    /// Free slot indices
+    free_list: Vec<u32>,
+    /// Chunk size
+    chunk_size: usize,
+    /// Number of live objects
+    live: usize,
+}
+
+impl<T> PoolStorage<T> {
+    fn slot(&self, index: u32) -> Option<&PoolSlot<T>> {
+        let index = index as usize;
+        self.chunks
+            .get(index / self.chunk_size)
+            .map(|chunk| &chunk[index % self.chunk_size])
+    }
+
+    /// Iterate over all slots with their indices
+    fn slots(&self) -> impl Iterator<Item = (u32, &PoolSlot<T>)> {
+        self.chunks
+            .iter()
+            .flat_map(|chunk| chunk.iter())
+            .enumerate()
+            .map(|(index, slot)| (index as u32, slot))
+    }
+}
+
+impl<T> PoolAllocator<T> {
+    /// Create a new pool allocator with the given chunk size
+    pub fn new(chunk_size: usize) -> Self {
+        let chunk_size = chunk_size.max(16);
+        
+        Self {
+            storage: Mutex::new(PoolStorage {
+                chunks: Vec::new(),
+                free_list: Vec::with_capacity(chunk_size),
+                chunk_size,
+                live: 0,
+            }),
+            stats: MemoryStats::new(),
+            debug: AllocationLog::default(),
+        }
+    }
+
+    /// Move an object into the pool
+    #[track_caller]
+    pub fn alloc(&self, value: T) -> PoolHandle<T> {
+        let mut storage = self.storage.lock();
+
+        if storage.free_list.is_empty() {
+            // Allocate new chunk, handing out its lowest slots first
+            let first = (storage.chunks.len() * storage.chunk_size) as u32;
+            let chunk: Box<[PoolSlot<T>]> = (0..storage.chunk_size)
+                .map(|_| PoolSlot {
+                    generation: AtomicU32::new(0),
+                    value: UnsafeCell::new(MaybeUninit::uninit()),
+                })
+                .collect();
+            storage.chunks.push(chunk);
+            let end = first + storage.chunk_size as u32;
+            storage.free_list.extend((first..end).rev());
+        }
+
+        let index = storage.free_list.pop().expect("Pool free list is empty");
+        let slot = storage.slot(index).expect("Pool slot out of range");
+        unsafe { (*slot.value.get()).write(value) };
+        // Publish the value before the generation marks it live
+        let generation = slot.generation.load(Ordering::Relaxed).wrapping_add(1);
+        slot.generation.store(generation, Ordering::Release);
+        self.debug.record(slot.value.get() as usize, std::mem::size_of::<T>(), Location::caller());
+        storage.live += 1;
+
+        self.stats.record_alloc(std::mem::size_of::<T>());
+        PoolHandle {
+            index,
+            generation,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Get an object, or None if the handle is stale
+    pub fn get(&self, handle: PoolHandle<T>) -> Option<&T> {
+        let storage = self.storage.lock();
+        let slot = storage.slot(handle.index)?;
+        if slot.generation.load(Ordering::Acquire) != handle.generation {
+            return None;
+        }
+        // Chunks are never freed while the pool is alive and freeing needs
+        // `&mut self`, so the value outlives this borrow of the pool.
+        let value = slot.value.get();
+        drop(storage);
+        Some(unsafe { (*value).assume_init_ref() })
+    }
+
+    /// Get an object mutably, or None if the handle is stale
+    pub fn get_mut(&mut self, handle: PoolHandle<T>) -> Option<&mut T> {
+        let storage = self.storage.get_mut();
+        let slot = storage.slot(handle.index)?;
+        if slot.generation.load(Ordering::Relaxed) != handle.generation {
+            return None;
+        }
+        Some(unsafe { (*slot.value.get()).assume_init_mut() })
+    }
+
+    /// Check if a handle still refers to a live object
+    pub fn contains(&self, handle: PoolHandle<T>) -> bool {
+        self.storage
+            .lock()
+            .slot(handle.index)
+            .is_some_and(|slot| slot.generation.load(Ordering::Acquire) == handle.generation)
+    }
+
+    /// Remove an object from the pool, returning it
+    ///
+    /// Returns None if the handle is stale.
+    pub fn free(&mut self, handle: PoolHandle<T>) -> Option<T> {
+        let storage = self.storage.get_mut();
+        let slot = storage.slot(handle.index)?;
+        if slot.generation.load(Ordering::Relaxed) != handle.generation {
+            return None;
+        }
+        let value = unsafe { (*slot.value.get()).assume_init_read() };
+        self.debug.remove(slot.value.get() as usize);
+        unsafe { poison(slot.value.get().cast::<u8>(), std::mem::size_of::<T>()) };
+        slot.generation.store(handle.generation.wrapping_add(1), Ordering::Relaxed);
+        storage.free_list.push(handle.index);
+        storage.live -= 1;
+
+        self.stats.record_dealloc(std::mem::size_of::<T>());
+        Some(value)
+    }
+
+    /// Get the number of live objects
+    pub fn len(&self) -> usize {
+        self.storage.lock().live
+    }
+
+    /// Check if the pool holds no live objects
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Get objects that have not been freed (debug builds only)
+    pub fn outstanding_allocations(&self) -> Vec<OutstandingAllocation> {
+        self.debug.outstanding()
+    }
+
+    /// Iterate over live objects
+    pub fn iter(&mut self) -> impl Iterator<Item = (PoolHandle<T>, &T)> {
+        self.storage.get_mut().slots().filter_map(|(index, slot)| {
+            let generation = slot.generation.load(Ordering::Relaxed);
+            PoolSlot::<T>::is_live(generation).then(|| {
+                let handle = PoolHandle {
+                    index,
+                    generation,
+                    _marker: PhantomData,
+                };
+                (handle, unsafe { (*slot.value.get()).assume_init_ref() })
+            })
+        })
+    }
+
+    /// Iterate mutably over live objects
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (PoolHandle<T>, &mut T)> {
+        self.storage.get_mut().slots().filter_map(|(index, slot)| {
+            let generation = slot.generation.load(Ordering::Relaxed);
+            PoolSlot::<T>::is_live(generation).then(|| {
+                let handle = PoolHandle {
+                    index,
+                    generation,
+                    _marker: PhantomData,
+                };
+                // Each live slot is yielded once and the pool is borrowed mutably
+                (handle, unsafe { (*slot.value.get()).assume_init_mut() })
+            })
+        })
+    }
+
+    /// Get memory statistics
+    pub fn stats(&self) -> &MemoryStats {
+        &self.stats
+    }
+}
+
+impl<T> Drop for PoolAllocator<T> {
+    fn drop(&mut self) {
+        self.debug.report("PoolAllocator", "dropped");
+        if !std::mem::needs_drop::<T>() {
+            return;
+        }
+        for (_, slot) in self.storage.get_mut().slots() {
+            if PoolSlot::<T>::is_live(slot.generation.load(Ordering::Relaxed)) {
+                unsafe { (*slot.value.get()).assume_init_drop() };
+            }
+        }
+    }
+}
+
+// Safety: Pool uses mutex for synchronization, and slots are published with
+// release/acquire generation updates
+unsafe impl<T: Send> Send for PoolAllocator<T> {}
+unsafe impl<T: Send + Sync> Sync for PoolAllocator<T> {}
+
+/// Maximum number of subsystems a [`MemoryTracker`] can track
+pub const MAX_TRACKED_SUBSYSTEMS: usize = 64;
+
+/// Maximum number of undrained events a [`MemoryTracker`] keeps; older ones are dropped
+pub const MAX_PENDING_MEMORY_EVENTS: usize = 256;
+
+/// Severity of a platform low-memory signal
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MemoryPressure {
+    /// The OS would like memory back; subsystems drop a quarter of their usage
+    Moderate,
+    /// The process is about to be killed; subsystems drop everything they can
+    Critical,
+}
+
+/// Budget notification emitted by a [`MemoryTracker`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MemoryEvent {
+    /// A subsystem crossed its warning threshold
+    Warning {
+        subsystem: usize,
+        used: usize,
+        budget: usize,
+    },
+    /// A subsystem exceeded its budget or had an allocation refused
+    OverBudget {
+        subsystem: usize,
+        used: usize,
+        /// Size of the refused allocation, if one was refused
+        requested: Option<usize>,
+        budget: usize,
+    },
+    /// The platform reported (or a test simulated) low system memory
+    LowMemory { pressure: MemoryPressure },
+}
+
+/// Handler asked to free up to the given number of bytes, returning the bytes freed
+pub type EvictionHandler = Box<dyn FnMut(usize) -> usize + Send>;
+
+/// Budget pressure levels stored per subsystem
+const PRESSURE_NORMAL: u8 = 0;
+const PRESSURE_WARNING: u8 = 1;
+const PRESSURE_OVER_BUDGET: u8 = 2;
+
+/// Lock-free per-subsystem counters
+struct SubsystemSlot {
+    stats: MemoryStats,
+    max_bytes: AtomicUsize,
+    warning_bytes: AtomicUsize,
+    /// Last reported pressure level, used to emit events on transitions only
+    pressure: AtomicU8,
+}
+
+impl SubsystemSlot {
+    const fn new() -> Self {
+        Self {
+            stats: MemoryStats::new(),
+            max_bytes: AtomicUsize::new(usize::MAX),
+            warning_bytes: AtomicUsize::new(usize::MAX),
+            pressure: AtomicU8::new(PRESSURE_NORMAL),
+        }
+    }
+
+    fn pressure_for(&self, used: usize) -> u8 {
+        if used > self.max_bytes.load(Ordering::Relaxed) {
+            PRESSURE_OVER_BUDGET
+        } else if used >= self.warning_bytes.load(Ordering::Relaxed) {
+            PRESSURE_WARNING
+        } else {
+            PRESSURE_NORMAL
+        }
+    }
+}
+
+/// The tracker fed by [`TrackingAllocator`]
+static GLOBAL_TRACKER: MemoryTracker = MemoryTracker::new();
+
+/// Global memory tracker for all subsystems
+///
+/// Counters live in a fixed table so that allocations can be recorded
+/// without taking a lock, which also makes the tracker usable from inside
+/// a global allocator. Only emitting a budget event takes a lock, which
+/// happens when an allocation is refused or a threshold is crossed.
+pub struct MemoryTracker {
+    /// Per-subsystem counters, indexed by subsystem ID
+    slots: [SubsystemSlot; MAX_TRACKED_SUBSYSTEMS],
+    /// Number of registered subsystems, readable without the lock
+    registered: AtomicUsize,
+    /// Names and budgets of registered subsystems
+    subsystems: Mutex<Vec<(String, MemoryBudget)>>,
+    /// Allocations made outside of any subsystem scope
+    untagged: MemoryStats,
+    /// Pending budget notifications
+    events: Mutex<Vec<MemoryEvent>>,
+    /// Eviction handlers keyed by subsystem ID
+    eviction_handlers: Mutex<Vec<(usize, EvictionHandler)>>,
+}
+
+impl MemoryTracker {
+    /// Create a new memory tracker
+    pub const fn new() -> Self {
+        Self {
+            slots: [const { SubsystemSlot::new() }; MAX_TRACKED_SUBSYSTEMS],
+            registered: AtomicUsize::new(0),
+            subsystems: parking_lot::const_mutex(Vec::new()),
+            untagged: MemoryStats::new(),
+            events: parking_lot::const_mutex(Vec::new()),
+            eviction_handlers: parking_lot::const_mutex(Vec::new()),
+        }
+    }
+
+    /// Get the process-wide tracker fed by [`TrackingAllocator`]
+    pub fn global() -> &'static MemoryTracker {
+        &GLOBAL_TRACKER
+    }
+
+    /// Register a subsystem for tracking
+    ///
+    /// # Panics
+    /// Panics if more than [`MAX_TRACKED_SUBSYSTEMS`] subsystems are registered.
+    pub fn register_subsystem(&self, name: &str, budget: MemoryBudget) -> usize {
+        let mut subsystems = self.subsystems.lock();
+        let id = subsystems.len();
+        assert!(
+            id < MAX_TRACKED_SUBSYSTEMS,
+            "Cannot track more than {MAX_TRACKED_SUBSYSTEMS} subsystems"
+        );
+        let warning_bytes = (budget.max_bytes as f64 * budget.warning_threshold as f64) as usize;
+        self.slots[id].max_bytes.store(budget.max_bytes, Ordering::Relaxed);
+        self.slots[id].warning_bytes.store(warning_bytes, Ordering::Relaxed);
+        subsystems.push((name.to_string(), budget));
+        // Publish the slot only once its budget is in place
+        self.registered.store(id + 1, Ordering::Release);
+        id
+    }
+
+    /// Record an allocation for a subsystem
+    ///
+    /// Refused allocations emit [`MemoryEvent::OverBudget`], and crossing the
+    /// warning threshold emits [`MemoryEvent::Warning`]. Undrained refusals
+    /// for the same subsystem are coalesced into the latest one.
+    pub fn record_alloc(&self, subsystem_id: usize, size: usize) -> bool {
+        if subsystem_id >= self.subsystem_count() {
+            return false;
+        }
+        let slot = &self.slots[subsystem_id];
+        let budget = slot.max_bytes.load(Ordering::Relaxed);
+        if let Err(used) = slot.stats.try_record_alloc(size, budget) {
+            self.emit(MemoryEvent::OverBudget {
+                subsystem: subsystem_id,
+                used,
+                requested: Some(size),
+                budget,
+            });
+            return false;
+        }
+        self.refresh_pressure(subsystem_id);
+        true
+    }
+
+    /// Record a deallocation for a subsystem
+    pub fn record_dealloc(&self, subsystem_id: usize, size: usize) {
+        if subsystem_id < self.subsystem_count() {
+            self.slots[subsystem_id].stats.record_dealloc(size);
+            self.refresh_pressure(subsystem_id);
+        }
+    }
+
+    /// Re-evaluate a subsystem's pressure level, emitting events when it rises
+    fn refresh_pressure(&self, subsystem_id: usize) {
+        let slot = &self.slots[subsystem_id];
+        let used = slot.stats.current();
+        let level = slot.pressure_for(used);
+        let previous = slot.pressure.swap(level, Ordering::Relaxed);
+        if level <= previous {
+            return;
+        }
+
+        let budget = slot.max_bytes.load(Ordering::Relaxed);
+        let event = if level == PRESSURE_OVER_BUDGET {
+            MemoryEvent::OverBudget {
+                subsystem: subsystem_id,
+                used,
+                requested: None,
+                budget,
+            }
+        } else {
+            MemoryEvent::Warning {
+                subsystem: subsystem_id,
+                used,
+                budget,
+            }
+        };
+        self.emit(event);
+    }
+
+    fn emit(&self, event: MemoryEvent) {
+        let mut events = self.events.lock();
+        if let MemoryEvent::OverBudget { subsystem, requested: Some(_), .. } = event {
+            let pending = events.iter_mut().find(|pending| {
+                matches!(pending, MemoryEvent::OverBudget { subsystem: s, requested: Some(_), .. } if *s == subsystem)
+            });
+            if let Some(pending) = pending {
+                *pending = event;
+                return;
+            }
+        }
+        if events.len() >= MAX_PENDING_MEMORY_EVENTS {
+            events.remove(0);
+        }
+        events.push(event);
+    }
+
+    /// Take all pending budget notifications
+    pub fn drain_events(&self) -> Vec<MemoryEvent> {
+        std::mem::take(&mut *self.events.lock())
+    }
+
+    /// Register a handler that frees memory for a subsystem under pressure
+    ///
+    /// The handler receives the number of bytes the tracker would like back and
+    /// returns how many it released. Released memory must still be reported
+    /// through [`record_dealloc`](Self::record_dealloc) (or freed through the
+    /// [`TrackingAllocator`]); the return value is informational.
+    pub fn register_eviction_handler<F>(&self, subsystem_id: usize, handler: F)
+    where
+        F: FnMut(usize) -> usize + Send + 'static,
+    {
+        self.eviction_handlers.lock().push((subsystem_id, Box::new(handler)));
+    }
+
+    /// Ask a subsystem's eviction handlers to free `bytes`, returning the bytes freed
+    ///
+    /// Handlers run without the handler lock held, so they may use the
+    /// tracker freely. While they run they are out of the list: a nested or
+    /// concurrent request for the same subsystem does not reach them.
+    pub fn request_eviction(&self, subsystem_id: usize, bytes: usize) -> usize {
+        let mut running: Vec<_> = {
+            let mut handlers = self.eviction_handlers.lock();
+            let (running, rest) = std::mem::take(&mut *handlers)
+                .into_iter()
+                .partition(|(id, _)| *id == subsystem_id);
+            *handlers = rest;
+            running
+        };
+
+        let mut freed = 0;
+        for (_, handler) in &mut running {
+            if freed >= bytes {
+                break;
+            }
+            freed += handler(bytes - freed);
+        }
+
+        // Back ahead of any handlers registered meanwhile, keeping registration order
+        self.eviction_handlers.lock().splice(0..0, running);
+        freed
+    }
+
+    /// Check all budgets and evict memory from subsystems above their warning threshold
+    ///
+    /// Allocations fed by the [`TrackingAllocator`] cannot raise events as they
+    /// happen, so this should be called once per frame.
+    pub fn update(&self) {
+        for subsystem_id in 0..self.subsystem_count() {
+            self.refresh_pressure(subsystem_id);
+
+            let slot = &self.slots[subsystem_id];
+            let used = slot.stats.current();
+            let warning_bytes = slot.warning_bytes.load(Ordering::Relaxed);
+            if used >= warning_bytes {
+                // Aim to get back below the warning threshold
+                self.request_eviction(subsystem_id, used - warning_bytes + 1);
+            }
+        }
+    }
+
+    /// Handle a low-memory signal, asking every subsystem to free memory
+    ///
+    /// Platform layers call this from OS callbacks such as `onTrimMemory` or
+    /// `didReceiveMemoryWarning`; on desktop it can be called directly to
+    /// simulate memory pressure. Returns the total number of bytes freed.
+    pub fn signal_low_memory(&self, pressure: MemoryPressure) -> usize {
+        self.emit(MemoryEvent::LowMemory { pressure });
+
+        (0..self.subsystem_count())
+            .map(|subsystem_id| {
+                let used = self.slots[subsystem_id].stats.current();
+                let target = match pressure {
+                    MemoryPressure::Moderate => used / 4,
+                    MemoryPressure::Critical => used,
+                };
+                if target > 0 {
+                    self.request_eviction(subsystem_id, target)
+                } else {
+                    0
+                }
+            })
+            .sum()
+    }
+
+    /// Get the statistics of a registered subsystem
+    pub fn stats(&self, subsystem_id: usize) -> Option<&MemoryStats> {
+        (subsystem_id < self.subsystem_count()).then(|| &self.slots[subsystem_id].stats)
+    }
+
+    /// Get the statistics for allocations made outside any subsystem scope
+    pub fn untagged_stats(&self) -> &MemoryStats {
+        &self.untagged
+    }
+
+    /// Get the number of registered subsystems
+    pub fn subsystem_count(&self) -> usize {
+        self.registered.load(Ordering::Acquire)
+    }
+
+    /// Get usage report for all subsystems
+    pub fn get_report(&self) -> Vec<(String, usize, usize, f32)> {
         let subsystems = self.subsystems.lock();
         subsystems
             .iter()
-            .map(|(name, stats, budget)| {
-                let used = stats.current();
+            .zip(self.slots.iter())
+            .map(|((name, budget), slot)| {
+                let used = slot.stats.current();
                 let usage_percent = used as f32 / budget.max_bytes as f32 * 100.0;
                 (name.clone(), used, budget.max_bytes, usage_percent)
             })
             .collect()
     }
+
+    /// Get the counters an allocation tagged with `tag` is attributed to
+    fn tagged_stats(&self, tag: usize) -> &MemoryStats {
+        match self.slots.get(tag) {
+            Some(slot) => &slot.stats,
+            None => &self.untagged,
+        }
+    }
 }
 
 impl Default for MemoryTracker {
@@ -493,13 +1807,170 @@ impl Default for MemoryTracker {
     }
 }
 
+/// Tag used when no subsystem scope is active
+const UNTAGGED: usize = usize::MAX;
+
+thread_local! {
+    /// Subsystem that allocations on this thread are attributed to
+    static CURRENT_SUBSYSTEM: Cell<usize> = const { Cell::new(UNTAGGED) };
+}
+
+fn current_tag() -> usize {
+    CURRENT_SUBSYSTEM.try_with(Cell::get).unwrap_or(UNTAGGED)
+}
+
+/// Get the subsystem that allocations on the current thread are attributed to
+pub fn current_subsystem() -> Option<usize> {
+    let tag = current_tag();
+    (tag != UNTAGGED).then_some(tag)
+}
+
+/// Scoped guard attributing allocations on the current thread to a subsystem
+///
+/// The previous subsystem is restored when the guard is dropped, so scopes nest.
+#[must_use = "allocations are only attributed while the scope is alive"]
+pub struct SubsystemScope {
+    previous: usize,
+    /// Scopes are bound to the thread that entered them
+    _not_send: PhantomData<*const ()>,
+}
+
+impl SubsystemScope {
+    /// Attribute allocations on this thread to `subsystem_id` until dropped
+    pub fn enter(subsystem_id: usize) -> Self {
+        let previous = CURRENT_SUBSYSTEM.with(|current| current.replace(subsystem_id));
+        Self {
+            previous,
+            _not_send: PhantomData,
+        }
+    }
+}
+
+impl Drop for SubsystemScope {
+    fn drop(&mut self) {
+        let _ = CURRENT_SUBSYSTEM.try_with(|current| current.set(self.previous));
+    }
+}
+
+/// Global allocator wrapper that feeds [`MemoryTracker::global`]
+///
+/// Every allocation is attributed to the subsystem of the active
+/// [`SubsystemScope`] (or the running job's subsystem). The tag is stored in
+/// a small header in front of the allocation so frees are credited to the
+/// right subsystem regardless of which thread releases the memory.
+///
+/// Job subsystems are only applied by [`JobSystem::process_jobs`]; jobs run
+/// any other way (e.g. calling [`Job::execute`] directly) are attributed to
+/// whatever scope is active on the calling thread.
+///
+/// [`JobSystem::process_jobs`]: crate::job::JobSystem::process_jobs
+/// [`Job::execute`]: crate::job::Job::execute
+///
+/// ```no_run
+/// use odeza_core::memory::TrackingAllocator;
+///
+/// #[global_allocator]
+/// static ALLOCATOR: TrackingAllocator = TrackingAllocator::new(std::alloc::System);
+/// # fn main() {}
+/// ```
+pub struct TrackingAllocator<A = std::alloc::System> {
+    inner: A,
+}
+
+impl<A> TrackingAllocator<A> {
+    /// Wrap an allocator
+    pub const fn new(inner: A) -> Self {
+        Self { inner }
+    }
+
+    /// Get the layout of an allocation including its tag header
+    ///
+    /// Returns the outer layout and the header size.
+    fn outer_layout(layout: Layout) -> Option<(Layout, usize)> {
+        let align = layout.align().max(std::mem::align_of::<usize>());
+        let header = align.max(std::mem::size_of::<usize>());
+        let size = layout.size().checked_add(header)?;
+        let outer = Layout::from_size_align(size, align).ok()?;
+        Some((outer, header))
+    }
+}
+
+unsafe impl<A: GlobalAlloc> GlobalAlloc for TrackingAllocator<A> {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let Some((outer, header)) = Self::outer_layout(layout) else {
+            return std::ptr::null_mut();
+        };
+        unsafe {
+            let base = self.inner.alloc(outer);
+            if base.is_null() {
+                return base;
+            }
+            let tag = current_tag();
+            let ptr = base.add(header);
+            (ptr as *mut usize).sub(1).write(tag);
+            GLOBAL_TRACKER.tagged_stats(tag).record_alloc(layout.size());
+            ptr
+        }
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let Some((outer, header)) = Self::outer_layout(layout) else {
+            return std::ptr::null_mut();
+        };
+        unsafe {
+            let base = self.inner.alloc_zeroed(outer);
+            if base.is_null() {
+                return base;
+            }
+            let tag = current_tag();
+            let ptr = base.add(header);
+            (ptr as *mut usize).sub(1).write(tag);
+            GLOBAL_TRACKER.tagged_stats(tag).record_alloc(layout.size());
+            ptr
+        }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        // The layout was valid when the block was allocated
+        let (outer, header) = Self::outer_layout(layout).expect("Invalid layout");
+        unsafe {
+            let tag = (ptr as *const usize).sub(1).read();
+            GLOBAL_TRACKER.tagged_stats(tag).record_dealloc(layout.size());
+            self.inner.dealloc(ptr.sub(header), outer);
+        }
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let (outer, header) = Self::outer_layout(layout).expect("Invalid layout");
+        let Some(new_outer_size) = new_size.checked_add(header) else {
+            return std::ptr::null_mut();
+        };
+        unsafe {
+            let base = self.inner.realloc(ptr.sub(header), outer, new_outer_size);
+            if base.is_null() {
+                return base;
+            }
+            // The header moves with the block, so the original subsystem keeps it
+            let ptr = base.add(header);
+            let tag = (ptr as *const usize).sub(1).read();
+            let stats = GLOBAL_TRACKER.tagged_stats(tag);
+            if new_size >= layout.size() {
+                stats.grow(new_size - layout.size());
+            } else {
+                stats.record_dealloc(layout.size() - new_size);
+            }
+            ptr
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn test_frame_allocator() {
-        let allocator = FrameAllocator::new(1024);
+        let mut allocator = FrameAllocator::new(1024);
         
         let ptr1 = allocator.alloc(100, 8);
         assert!(ptr1.is_some());
@@ -553,31 +2024,89 @@ mod tests {
 
     #[test]
     fn test_pool_allocator() {
-        let pool: PoolAllocator<u64> = PoolAllocator::new(16);
-        
-        let ptr1 = pool.alloc();
-        assert!(ptr1.is_some());
+        let mut pool: PoolAllocator<u64> = PoolAllocator::new(16);
         
-        let ptr2 = pool.alloc();
-        assert!(ptr2.is_some());
+        let handle1 = pool.alloc(1);
+        let handle2 = pool.alloc(2);
+        assert_eq!(pool.len(), 2);
         
         // Return one to pool
-        unsafe {
-            pool.dealloc(ptr1.unwrap());
-        }
+        assert_eq!(pool.free(handle1), Some(1));
+        assert_eq!(pool.free(handle1), None);
         
-        // Should reuse the freed slot
-        let ptr3 = pool.alloc();
-        assert!(ptr3.is_some());
+        // Should reuse the freed slot with a new generation
+        let handle3 = pool.alloc(3);
+        assert_eq!(handle3.index(), handle1.index());
+        assert_ne!(handle3.generation(), handle1.generation());
+        assert_eq!(pool.get(handle1), None);
+        assert_eq!(pool.get(handle2), Some(&2));
+        assert_eq!(pool.get(handle3), Some(&3));
     }
 
     #[test]
-    fn test_pool_alloc_init() {
-        let pool: PoolAllocator<String> = PoolAllocator::new(8);
+    fn test_pool_get_mut() {
+        let mut pool: PoolAllocator<String> = PoolAllocator::new(8);
         
-        let value = pool.alloc_init(String::from("hello"));
-        assert!(value.is_some());
-        assert_eq!(value.unwrap(), "hello");
+        let handle = pool.alloc(String::from("hello"));
+        assert_eq!(pool.get(handle).unwrap(), "hello");
+
+        pool.get_mut(handle).unwrap().push_str(" world");
+        assert_eq!(pool.get(handle).unwrap(), "hello world");
+        assert!(pool.contains(handle));
+    }
+
+    #[test]
+    fn test_pool_iteration() {
+        let mut pool: PoolAllocator<u32> = PoolAllocator::new(16);
+        let handles: Vec<_> = (0..40).map(|i| pool.alloc(i)).collect();
+        for handle in handles.iter().step_by(2) {
+            pool.free(*handle);
+        }
+
+        for (_, value) in pool.iter_mut() {
+            *value *= 10;
+        }
+        let live: Vec<_> = pool.iter().map(|(handle, value)| (handle, *value)).collect();
+        assert_eq!(live.len(), 20);
+        assert!(live.iter().all(|(handle, value)| {
+            handles.contains(handle) && value % 20 == 10
+        }));
+    }
+
+    #[test]
+    fn test_pool_concurrent_alloc() {
+        use std::sync::Arc;
+
+        let pool: Arc<PoolAllocator<usize>> = Arc::new(PoolAllocator::new(16));
+        let threads: Vec<_> = (0..4)
+            .map(|t| {
+                let pool = pool.clone();
+                std::thread::spawn(move || {
+                    (0..100).map(|i| (pool.alloc(t * 1000 + i), t * 1000 + i)).collect::<Vec<_>>()
+                })
+            })
+            .collect();
+
+        for thread in threads {
+            for (handle, value) in thread.join().unwrap() {
+                assert_eq!(pool.get(handle), Some(&value));
+            }
+        }
+        assert_eq!(pool.len(), 400);
+    }
+
+    #[test]
+    fn test_pool_drops_live_values() {
+        use std::rc::Rc;
+
+        let counter = Rc::new(());
+        {
+            let pool = PoolAllocator::new(16);
+            pool.alloc(counter.clone());
+            pool.alloc(counter.clone());
+            assert_eq!(Rc::strong_count(&counter), 3);
+        }
+        assert_eq!(Rc::strong_count(&counter), 1);
     }
 
     #[test]
@@ -617,4 +2146,433 @@ mod tests {
         assert_eq!(stats.current(), 200);
         assert_eq!(stats.peak_usage(), 300); // Peak unchanged
     }
+
+    #[test]
+    fn test_subsystem_scope_nesting() {
+        assert_eq!(current_subsystem(), None);
+        {
+            let _outer = SubsystemScope::enter(3);
+            assert_eq!(current_subsystem(), Some(3));
+            {
+                let _inner = SubsystemScope::enter(5);
+                assert_eq!(current_subsystem(), Some(5));
+            }
+            assert_eq!(current_subsystem(), Some(3));
+        }
+        assert_eq!(current_subsystem(), None);
+    }
+
+    #[test]
+    fn test_tracking_allocator_attribution() {
+        let allocator = TrackingAllocator::new(std::alloc::System);
+        let tracker = MemoryTracker::global();
+        let id = tracker.register_subsystem("tracking_test", MemoryBudget::default());
+        let layout = Layout::from_size_align(256, 32).unwrap();
+
+        let ptr = {
+            let _scope = SubsystemScope::enter(id);
+            unsafe { allocator.alloc(layout) }
+        };
+        assert!(!ptr.is_null());
+        assert_eq!(ptr as usize % 32, 0);
+
+        let stats = tracker.stats(id).unwrap();
+        assert_eq!(stats.current(), 256);
+        assert_eq!(stats.count(), 1);
+
+        // Growing keeps the attribution even outside the scope
+        let ptr = unsafe { allocator.realloc(ptr, layout, 1024) };
+        assert!(!ptr.is_null());
+        assert_eq!(stats.current(), 1024);
+        assert_eq!(stats.peak_usage(), 1024);
+        assert_eq!(stats.count(), 1);
+
+        let layout = Layout::from_size_align(1024, 32).unwrap();
+        unsafe { allocator.dealloc(ptr, layout) };
+        assert_eq!(stats.current(), 0);
+        assert_eq!(stats.peak_usage(), 1024);
+    }
+
+    #[test]
+    fn test_tracker_report() {
+        let tracker = MemoryTracker::new();
+        let id = tracker.register_subsystem("audio", MemoryBudget {
+            max_bytes: 1000,
+            warning_threshold: 0.8,
+        });
+
+        assert!(tracker.record_alloc(id, 250));
+        assert!(tracker.stats(id + 1).is_none());
+
+        let report = tracker.get_report();
+        assert_eq!(report.len(), 1);
+        assert_eq!(report[0].0, "audio");
+        assert_eq!(report[0].1, 250);
+        assert!((report[0].3 - 25.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_budget_events() {
+        let tracker = MemoryTracker::new();
+        let id = tracker.register_subsystem("textures", MemoryBudget {
+            max_bytes: 1000,
+            warning_threshold: 0.8,
+        });
+
+        assert!(tracker.record_alloc(id, 500));
+        assert!(tracker.drain_events().is_empty());
+
+        // Crossing the threshold warns once
+        assert!(tracker.record_alloc(id, 350));
+        assert!(tracker.record_alloc(id, 50));
+        assert_eq!(tracker.drain_events(), vec![MemoryEvent::Warning {
+            subsystem: id,
+            used: 850,
+            budget: 1000,
+        }]);
+
+        assert!(!tracker.record_alloc(id, 200));
+        assert_eq!(tracker.drain_events(), vec![MemoryEvent::OverBudget {
+            subsystem: id,
+            used: 900,
+            requested: Some(200),
+            budget: 1000,
+        }]);
+
+        // Dropping below the threshold re-arms the warning
+        tracker.record_dealloc(id, 500);
+        assert!(tracker.record_alloc(id, 450));
+        assert_eq!(tracker.drain_events().len(), 1);
+
+        // Undrained refusals coalesce, and the queue is capped
+        for size in [300, 400, 500] {
+            assert!(!tracker.record_alloc(id, size));
+        }
+        assert_eq!(tracker.drain_events(), vec![MemoryEvent::OverBudget {
+            subsystem: id,
+            used: 850,
+            requested: Some(500),
+            budget: 1000,
+        }]);
+        for _ in 0..MAX_PENDING_MEMORY_EVENTS + 10 {
+            tracker.signal_low_memory(MemoryPressure::Moderate);
+        }
+        assert_eq!(tracker.drain_events().len(), MAX_PENDING_MEMORY_EVENTS);
+    }
+
+    #[test]
+    fn test_budget_holds_under_contention() {
+        let tracker = MemoryTracker::new();
+        let id = tracker.register_subsystem("meshes", MemoryBudget {
+            max_bytes: 5000,
+            warning_threshold: 1.0,
+        });
+        let granted = AtomicUsize::new(0);
+        std::thread::scope(|scope| {
+            for _ in 0..8 {
+                scope.spawn(|| {
+                    for _ in 0..1000 {
+                        if tracker.record_alloc(id, 1) {
+                            granted.fetch_add(1, Ordering::Relaxed);
+                        }
+                    }
+                });
+            }
+        });
+        assert_eq!(granted.load(Ordering::Relaxed), 5000);
+        assert_eq!(tracker.stats(id).unwrap().current(), 5000);
+    }
+
+    #[test]
+    fn test_eviction_under_pressure() {
+        use std::sync::Arc;
+
+        let tracker = Arc::new(MemoryTracker::new());
+        let id = tracker.register_subsystem("audio_cache", MemoryBudget {
+            max_bytes: 1000,
+            warning_threshold: 0.5,
+        });
+        assert!(tracker.record_alloc(id, 700));
+
+        let handler_tracker = tracker.clone();
+        tracker.register_eviction_handler(id, move |bytes| {
+            handler_tracker.record_dealloc(id, bytes);
+            bytes
+        });
+
+        tracker.update();
+        assert!(tracker.stats(id).unwrap().current() < 500);
+        assert!(matches!(tracker.drain_events()[0], MemoryEvent::Warning { .. }));
+
+        // Handlers may use the tracker, including the handler list
+        let other = tracker.register_subsystem("streaming", MemoryBudget::default());
+        let handler_tracker = tracker.clone();
+        tracker.register_eviction_handler(other, move |_| {
+            handler_tracker.register_eviction_handler(other, |_| 0);
+            handler_tracker.request_eviction(other, 1)
+        });
+        assert_eq!(tracker.request_eviction(other, 1000), 0);
+        assert_eq!(tracker.eviction_handlers.lock().len(), 3);
+    }
+
+    #[test]
+    fn test_simulated_low_memory() {
+        let tracker = MemoryTracker::new();
+        let id = tracker.register_subsystem("streaming", MemoryBudget::default());
+        assert!(tracker.record_alloc(id, 4000));
+
+        let requests = std::sync::Arc::new(Mutex::new(Vec::new()));
+        let seen = requests.clone();
+        tracker.register_eviction_handler(id, move |bytes| {
+            seen.lock().push(bytes);
+            bytes
+        });
+
+        assert_eq!(tracker.signal_low_memory(MemoryPressure::Moderate), 1000);
+        assert_eq!(tracker.signal_low_memory(MemoryPressure::Critical), 4000);
+        assert_eq!(*requests.lock(), vec![1000, 4000]);
+
+        let events = tracker.drain_events();
+        assert_eq!(events[0], MemoryEvent::LowMemory { pressure: MemoryPressure::Moderate });
+    }
+
+    #[test]
+    fn test_frame_vec() {
+        let mut allocator = FrameAllocator::new(4096);
+        {
+            let frame = allocator.frame();
+            let mut values = frame.vec::<u32>();
+            for i in 0..100 {
+                values.push(i);
+            }
+            assert_eq!(values.len(), 100);
+            assert_eq!(values.iter().sum::<u32>(), 4950);
+            assert_eq!(values.pop(), Some(99));
+
+            // A lone vector grows in place without wasting memory
+            assert!(allocator.used() <= values.capacity() * 4);
+        }
+        allocator.reset();
+        assert_eq!(allocator.used(), 0);
+    }
+
+    #[test]
+    fn test_frame_vec_drops_elements() {
+        use std::rc::Rc;
+
+        let allocator = FrameAllocator::new(1024);
+        let counter = Rc::new(());
+        {
+            let mut values = allocator.frame().vec();
+            values.push(counter.clone());
+            values.push(counter.clone());
+            assert_eq!(Rc::strong_count(&counter), 3);
+        }
+        assert_eq!(Rc::strong_count(&counter), 1);
+    }
+
+    #[test]
+    fn test_frame_vec_exhausted() {
+        let allocator = FrameAllocator::new(64);
+        let frame = allocator.frame();
+        let mut values = frame.vec::<u64>();
+        assert!(values.try_reserve(8));
+        assert!(!values.try_reserve(9));
+        assert!(FrameVec::<u64>::with_capacity_in(16, frame).is_none());
+    }
+
+    #[test]
+    fn test_frame_box_and_string() {
+        use std::fmt::Write;
+
+        let allocator = FrameAllocator::new(1024);
+        let frame = allocator.frame();
+
+        let mut value = frame.alloc([1.0f32; 4]).unwrap();
+        value[2] = 5.0;
+        assert_eq!(*value, [1.0, 1.0, 5.0, 1.0]);
+
+        let mut name = frame.string();
+        name.push_str("enemy_");
+        write!(name, "{}", 42).unwrap();
+        name.push('!');
+        assert_eq!(name.as_str(), "enemy_42!");
+
+        let copied = frame.alloc_slice_copy(&[1u16, 2, 3]).unwrap();
+        assert_eq!(copied, &[1, 2, 3]);
+    }
+
+    #[test]
+    fn test_buffered_frame_allocator() {
+        let mut ring = BufferedFrameAllocator::<3>::new(1024);
+
+        let first = ring.begin_frame();
+        let ptr = {
+            let mut data = ring.frame().vec::<u32>();
+            data.extend_from_slice(&[7, 8, 9]);
+            data.as_ptr()
+        };
+
+        // Frame N's data survives through frame N+2
+        ring.begin_frame();
+        ring.begin_frame();
+        assert!(ring.get(first).is_some());
+        assert_eq!(unsafe { *ptr.add(1) }, 8);
+
+        ring.begin_frame();
+        assert!(ring.get(first).is_none());
+        assert_eq!(ring.current().used(), 0);
+    }
+
+    #[test]
+    fn test_over_aligned_allocations() {
+        #[repr(align(64))]
+        #[derive(Clone, Copy)]
+        struct CacheLine(#[allow(dead_code)] [u8; 64]);
+        let aligned = |ptr: *const CacheLine| (ptr as usize).is_multiple_of(64);
+
+        // Blocks are only 16-byte aligned, so a one-byte allocation first
+        // leaves the next free address misaligned for 64
+        let allocator = FrameAllocator::new(4096);
+        let frame = allocator.frame();
+        allocator.alloc(1, 1).unwrap();
+        let boxed = FrameBox::new_in(CacheLine([1; 64]), frame).unwrap();
+        assert!(aligned(&*boxed));
+        allocator.alloc(1, 1).unwrap();
+        assert!(aligned(frame.alloc_slice_copy(&[CacheLine([2; 64]); 2]).unwrap().as_ptr()));
+        let mut vec = frame.vec();
+        allocator.alloc(1, 1).unwrap();
+        vec.push(CacheLine([3; 64]));
+        assert!(aligned(vec.as_ptr()));
+
+        let stack = StackAllocator::new(4096);
+        stack.alloc(1, 1).unwrap();
+        assert!(aligned(stack.alloc(64, 64).unwrap().as_ptr().cast()));
+
+        let arena = ArenaAllocator::new(64);
+        arena.alloc(1, 1).unwrap();
+        assert!(aligned(arena.alloc_init(CacheLine([4; 64])).unwrap()));
+        assert!(aligned(arena.alloc_init(CacheLine([5; 64])).unwrap()));
+    }
+
+    #[test]
+    fn test_stack_allocator_markers() {
+        let mut stack = StackAllocator::new(1024);
+        stack.alloc(64, 8).unwrap();
+        let base = stack.used();
+
+        let outer = stack.push_marker();
+        stack.alloc(100, 8).unwrap();
+        let inner = stack.push_marker();
+        stack.alloc(200, 16).unwrap();
+        assert_eq!(stack.depth(), 2);
+
+        assert!(stack.pop_to_marker(inner));
+        assert_eq!(stack.depth(), 1);
+        assert!(stack.used() < base + 300);
+
+        // Popping the outer marker releases everything above it
+        let nested = stack.push_marker();
+        stack.alloc(32, 8).unwrap();
+        assert!(stack.pop_to_marker(outer));
+        assert_eq!(stack.used(), base);
+        assert_eq!(stack.depth(), 0);
+        assert!(!stack.pop_to_marker(nested));
+    }
+
+    #[test]
+    fn test_stack_allocator_rejects_stale_marker() {
+        let mut stack = StackAllocator::new(1024);
+        stack.alloc(100, 1).unwrap();
+        let stale = stack.push_marker();
+        stack.reset();
+
+        let fresh = stack.push_marker();
+        assert!(!stack.pop_to_marker(stale));
+        assert_eq!(stack.used(), 0);
+        assert_eq!(stack.depth(), 1);
+        assert!(stack.pop_to_marker(fresh));
+
+        // A popped marker stays invalid once the depth is pushed again
+        let popped = stack.push_marker();
+        assert!(stack.pop_to_marker(popped));
+        stack.alloc(64, 1).unwrap();
+        let live = stack.push_marker();
+        assert!(!stack.pop_to_marker(popped));
+        assert_eq!(stack.used(), 64);
+        assert_eq!(stack.depth(), 1);
+        assert!(stack.pop_to_marker(live));
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    fn test_stack_allocator_poisons_popped_memory() {
+        let mut stack = StackAllocator::new(256);
+        let marker = stack.push_marker();
+        let ptr = stack.alloc_zeroed(16, 8).unwrap();
+        assert!(stack.pop_to_marker(marker));
+
+        let bytes = unsafe { std::slice::from_raw_parts(ptr.as_ptr(), 16) };
+        assert!(bytes.iter().all(|&b| b == POISON_BYTE));
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    fn test_outstanding_allocation_call_sites() {
+        let mut stack = StackAllocator::new(256);
+        stack.alloc(8, 8).unwrap();
+        let marker = stack.push_marker();
+        let line = line!() + 1;
+        stack.alloc(24, 8).unwrap();
+
+        let outstanding = stack.outstanding_allocations();
+        assert_eq!(outstanding.len(), 2);
+        assert_eq!(outstanding[1].size, 24);
+        assert_eq!(outstanding[1].location.file(), file!());
+        assert_eq!(outstanding[1].location.line(), line);
+
+        assert!(stack.pop_to_marker(marker));
+        assert_eq!(stack.outstanding_allocations().len(), 1);
+        stack.reset();
+        assert!(stack.outstanding_allocations().is_empty());
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    fn test_leaked_frame_objects_reported() {
+        let mut allocator = FrameAllocator::new(1024);
+        {
+            let frame = allocator.frame();
+            let _dropped = frame.alloc(1u64).unwrap();
+            let mut values = frame.vec::<u32>();
+            values.extend_from_slice(&[1, 2, 3, 4, 5]);
+            std::mem::forget(frame.alloc(String::from("leaked")).unwrap());
+        }
+
+        let outstanding = allocator.outstanding_allocations();
+        assert_eq!(outstanding.len(), 1);
+        assert_eq!(outstanding[0].size, std::mem::size_of::<String>());
+
+        let ptr = outstanding[0].address as *const u8;
+        allocator.reset();
+        assert!(allocator.outstanding_allocations().is_empty());
+        assert_eq!(unsafe { *ptr }, POISON_BYTE);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    fn test_arena_and_pool_outstanding() {
+        let arena = ArenaAllocator::new(256);
+        arena.alloc_init(7u32).unwrap();
+        arena.alloc_init(vec![1u8]).unwrap();
+        assert_eq!(arena.outstanding_allocations().len(), 1);
+        arena.reset();
+        assert!(arena.outstanding_allocations().is_empty());
+
+        let mut pool: PoolAllocator<u64> = PoolAllocator::new(16);
+        let freed = pool.alloc(1);
+        pool.alloc(2);
+        pool.free(freed);
+        assert_eq!(pool.outstanding_allocations().len(), 1);
+    }
 }
@@ -2,19 +2,24 @@
 //!
 //! Hierarchical scene representation with:
 //! - Transform parenting
-//! - Prefab support
+//! - Prefab support and instantiation
+//! - Nested prefabs, variants and per-instance overrides
+//! - Path, wildcard and tag lookups
 //! - Editor semantics
 //! - Bridge to ECS for runtime performance
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet, VecDeque};
 
 use glam::{Mat4, Quat, Vec3};
+use serde::{Deserialize, Serialize};
 use smallvec::SmallVec;
+use thiserror::Error;
 
-use crate::ecs::Entity;
+use crate::ecs::{Entity, World};
+use crate::job::JobSystem;
 
 /// Transform component for entities
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub struct Transform {
     /// Local position
     pub position: Vec3,
@@ -109,6 +114,15 @@ impl Transform {
             scale: self.scale.lerp(other.scale, t),
         }
     }
+
+    /// Compose with a child transform (`self` is the parent)
+    pub fn mul_transform(&self, child: &Transform) -> Transform {
+        Transform {
+            position: self.position + self.rotation * (self.scale * child.position),
+            rotation: self.rotation * child.rotation,
+            scale: self.scale * child.scale,
+        }
+    }
 }
 
 impl Default for Transform {
@@ -140,6 +154,12 @@ pub struct Node {
     pub visible: bool,
     /// Whether this node is enabled
     pub enabled: bool,
+    /// Tags for grouping and queries
+    pub tags: SmallVec<[String; 2]>,
+    /// Index of the parent in the scene graph's node array
+    parent_index: usize,
+    /// End (exclusive) of this node's subtree in the scene graph's node array
+    subtree_end: usize,
 }
 
 impl Node {
@@ -156,6 +176,9 @@ impl Node {
             dirty: true,
             visible: true,
             enabled: true,
+            tags: SmallVec::new(),
+            parent_index: NO_PARENT,
+            subtree_end: 0,
         }
     }
 
@@ -170,6 +193,8 @@ impl Node {
     }
 
     /// Mark the transform as dirty
+    ///
+    /// Nodes borrowed through [`SceneGraph::get_node_mut`] are already dirty.
     pub fn mark_dirty(&mut self) {
         self.dirty = true;
     }
@@ -190,84 +215,171 @@ impl Node {
     pub fn remove_child(&mut self, child: Entity) {
         self.children.retain(|c| *c != child);
     }
+
+    /// Add a tag
+    pub fn add_tag(&mut self, tag: impl Into<String>) {
+        let tag = tag.into();
+        if !self.has_tag(&tag) {
+            self.tags.push(tag);
+        }
+    }
+
+    /// Remove a tag
+    pub fn remove_tag(&mut self, tag: &str) -> bool {
+        let before = self.tags.len();
+        self.tags.retain(|t| t != tag);
+        self.tags.len() != before
+    }
+
+    /// Check if the node has a tag
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.iter().any(|t| t == tag)
+    }
 }
 
+/// Parent index of root nodes
+const NO_PARENT: usize = usize::MAX;
+
+/// Dirty node count above which subtrees are updated in parallel
+const PARALLEL_UPDATE_THRESHOLD: usize = 4096;
+
 /// Scene graph managing the hierarchy of entities
+///
+/// Nodes live in a flat array sorted depth-first, so every subtree is a
+/// contiguous range. Hierarchy changes re-sort the array lazily on the next
+/// transform update, and only dirty subtrees are recomputed.
 pub struct SceneGraph {
-    /// All nodes in the scene
-    nodes: HashMap<Entity, Node>,
+    /// All nodes in the scene, depth-first when `order_dirty` is false
+    nodes: Vec<Node>,
+    /// Node array index by entity
+    lookup: HashMap<Entity, usize>,
     /// Root entities (no parent)
     roots: Vec<Entity>,
+    /// Whether the hierarchy changed since the array was sorted
+    order_dirty: bool,
+    /// Nodes marked dirty since the last transform update
+    dirty: Vec<Entity>,
+    /// Nodes whose world transform changed in the last transform update
+    moved: Vec<Entity>,
+    /// Nodes removed before the last transform update
+    removed: Vec<Entity>,
+    /// Nodes removed since the last transform update
+    removed_pending: Vec<Entity>,
 }
 
 impl SceneGraph {
     /// Create a new empty scene graph
     pub fn new() -> Self {
         Self {
-            nodes: HashMap::new(),
+            nodes: Vec::new(),
+            lookup: HashMap::new(),
             roots: Vec::new(),
+            order_dirty: false,
+            dirty: Vec::new(),
+            moved: Vec::new(),
+            removed: Vec::new(),
+            removed_pending: Vec::new(),
         }
     }
 
     /// Add a new node to the scene
     pub fn add_node(&mut self, entity: Entity, name: impl Into<String>) -> &mut Node {
-        let node = Node::new(entity, name);
-        self.nodes.insert(entity, node);
+        self.remove_node(entity);
+
+        let index = self.nodes.len();
+        self.nodes.push(Node::new(entity, name));
+        self.lookup.insert(entity, index);
         self.roots.push(entity);
-        self.nodes.get_mut(&entity).unwrap()
+        self.order_dirty = true;
+        self.dirty.push(entity);
+        &mut self.nodes[index]
     }
 
     /// Remove a node from the scene
     pub fn remove_node(&mut self, entity: Entity) -> Option<Node> {
-        if let Some(node) = self.nodes.remove(&entity) {
-            // Remove from parent's children
-            if let Some(parent_entity) = node.parent {
-                if let Some(parent) = self.nodes.get_mut(&parent_entity) {
-                    parent.remove_child(entity);
-                }
-            }
-            
-            // Remove from roots if it was a root
-            self.roots.retain(|&e| e != entity);
-            
-            // Orphan children (make them roots)
-            for child in &node.children {
-                if let Some(child_node) = self.nodes.get_mut(child) {
-                    child_node.parent = None;
-                    self.roots.push(*child);
-                }
+        let index = self.lookup.remove(&entity)?;
+        let node = self.nodes.swap_remove(index);
+        if let Some(moved) = self.nodes.get(index) {
+            self.lookup.insert(moved.entity, index);
+        }
+        self.order_dirty = true;
+        self.removed_pending.push(entity);
+
+        // Remove from parent's children
+        if let Some(parent) = node.parent.and_then(|parent| self.node_mut_untracked(parent)) {
+            parent.remove_child(entity);
+        }
+        
+        // Remove from roots if it was a root
+        self.roots.retain(|&e| e != entity);
+        
+        // Orphan children (make them roots)
+        for &child in &node.children {
+            if let Some(child_node) = self.node_mut_untracked(child) {
+                child_node.parent = None;
+                self.roots.push(child);
+                self.mark_dirty(child);
             }
-            
-            Some(node)
-        } else {
-            None
         }
+        
+        Some(node)
     }
 
     /// Get a node by entity
     pub fn get_node(&self, entity: Entity) -> Option<&Node> {
-        self.nodes.get(&entity)
+        self.lookup.get(&entity).map(|&index| &self.nodes[index])
     }
 
     /// Get a mutable node by entity
+    ///
+    /// The node is marked dirty, since the caller may change its transform.
     pub fn get_node_mut(&mut self, entity: Entity) -> Option<&mut Node> {
-        self.nodes.get_mut(&entity)
+        let index = *self.lookup.get(&entity)?;
+        if !self.nodes[index].dirty {
+            self.nodes[index].dirty = true;
+            self.dirty.push(entity);
+        }
+        Some(&mut self.nodes[index])
+    }
+
+    /// Get a mutable node without marking it dirty
+    fn node_mut_untracked(&mut self, entity: Entity) -> Option<&mut Node> {
+        let index = *self.lookup.get(&entity)?;
+        Some(&mut self.nodes[index])
+    }
+
+    /// Mark a node's subtree for transform recomputation
+    pub fn mark_dirty(&mut self, entity: Entity) {
+        self.get_node_mut(entity);
     }
 
-    /// Set the parent of a node
-    pub fn set_parent(&mut self, child: Entity, parent: Option<Entity>) {
+    /// Set the parent of a node, keeping its local transform
+    ///
+    /// A reparented node becomes the new parent's last child; passing the
+    /// current parent leaves the sibling order unchanged. Returns false if
+    /// either node is missing or `parent` is the node itself or one of its
+    /// descendants.
+    pub fn set_parent(&mut self, child: Entity, parent: Option<Entity>) -> bool {
+        let Some(old_parent) = self.get_node(child).map(|node| node.parent) else {
+            return false;
+        };
+        if let Some(parent_entity) = parent
+            && (!self.lookup.contains_key(&parent_entity) || self.is_ancestor_of(child, parent_entity))
+        {
+            return false;
+        }
+        if old_parent == parent {
+            return true;
+        }
+
         // Remove from old parent
-        if let Some(child_node) = self.nodes.get(&child) {
-            if let Some(old_parent) = child_node.parent {
-                if let Some(old_parent_node) = self.nodes.get_mut(&old_parent) {
-                    old_parent_node.remove_child(child);
-                }
-            }
+        if let Some(old_parent_node) = old_parent.and_then(|old| self.node_mut_untracked(old)) {
+            old_parent_node.remove_child(child);
         }
         
         // Add to new parent
         if let Some(parent_entity) = parent {
-            if let Some(parent_node) = self.nodes.get_mut(&parent_entity) {
+            if let Some(parent_node) = self.node_mut_untracked(parent_entity) {
                 parent_node.add_child(child);
             }
             
@@ -275,15 +387,131 @@ impl SceneGraph {
             self.roots.retain(|&e| e != child);
         } else {
             // Make it a root if no parent
-            if !self.roots.contains(&child) {
-                self.roots.push(child);
-            }
+            self.roots.push(child);
         }
         
         // Update child's parent reference
-        if let Some(child_node) = self.nodes.get_mut(&child) {
+        if let Some(child_node) = self.get_node_mut(child) {
             child_node.parent = parent;
-            child_node.mark_dirty();
+        }
+        self.order_dirty = true;
+        true
+    }
+
+    /// Set the parent of a node, keeping its world transform
+    ///
+    /// The local transform is recomputed from the current world matrix. Shear
+    /// from non-uniformly scaled parents cannot be represented and is lost.
+    pub fn set_parent_keep_world(&mut self, child: Entity, parent: Option<Entity>) -> bool {
+        let Some(child_world) = self.compute_world_matrix(child) else {
+            return false;
+        };
+        let parent_world = match parent {
+            Some(parent) => match self.compute_world_matrix(parent) {
+                Some(matrix) => matrix,
+                None => return false,
+            },
+            None => Mat4::IDENTITY,
+        };
+        if !self.set_parent(child, parent) {
+            return false;
+        }
+
+        let local = parent_world.inverse() * child_world;
+        let (scale, rotation, translation) = local.to_scale_rotation_translation();
+        if let Some(node) = self.get_node_mut(child) {
+            node.local_transform = Transform::new(translation, rotation.normalize(), scale);
+        }
+        true
+    }
+
+    /// Compute a node's world matrix from the local transforms of its ancestors
+    ///
+    /// Unlike [`Node::world_matrix`] this does not depend on the last update.
+    pub fn compute_world_matrix(&self, entity: Entity) -> Option<Mat4> {
+        let mut node = self.get_node(entity)?;
+        let mut matrix = node.local_transform.local_matrix();
+        while let Some(parent) = node.parent.and_then(|parent| self.get_node(parent)) {
+            matrix = parent.local_transform.local_matrix() * matrix;
+            node = parent;
+        }
+        Some(matrix)
+    }
+
+    /// Check if `ancestor` is `entity` or one of its ancestors
+    pub fn is_ancestor_of(&self, ancestor: Entity, entity: Entity) -> bool {
+        let mut current = Some(entity);
+        while let Some(e) = current {
+            if e == ancestor {
+                return true;
+            }
+            current = self.get_node(e).and_then(|node| node.parent);
+        }
+        false
+    }
+
+    /// Get a node's position among its siblings
+    pub fn sibling_index(&self, entity: Entity) -> Option<usize> {
+        let siblings = match self.get_node(entity)?.parent {
+            Some(parent) => &self.get_node(parent)?.children[..],
+            None => &self.roots[..],
+        };
+        siblings.iter().position(|&e| e == entity)
+    }
+
+    /// Move a node to a position among its siblings (clamped to the last)
+    pub fn set_sibling_index(&mut self, entity: Entity, index: usize) -> bool {
+        let Some(current) = self.sibling_index(entity) else {
+            return false;
+        };
+        let Some(siblings) = self.siblings_mut(entity) else {
+            return false;
+        };
+        let target = index.min(siblings.len() - 1);
+        if target > current {
+            siblings[current..=target].rotate_left(1);
+        } else {
+            siblings[target..=current].rotate_right(1);
+        }
+        if target != current {
+            self.order_dirty = true;
+        }
+        true
+    }
+
+    /// Move a node directly before `sibling`, reparenting it if needed
+    pub fn move_before(&mut self, entity: Entity, sibling: Entity) -> bool {
+        self.move_next_to(entity, sibling, 0)
+    }
+
+    /// Move a node directly after `sibling`, reparenting it if needed
+    pub fn move_after(&mut self, entity: Entity, sibling: Entity) -> bool {
+        self.move_next_to(entity, sibling, 1)
+    }
+
+    fn move_next_to(&mut self, entity: Entity, sibling: Entity, offset: usize) -> bool {
+        if entity == sibling {
+            return false;
+        }
+        let Some(parent) = self.get_node(sibling).map(|node| node.parent) else {
+            return false;
+        };
+        if !self.set_parent(entity, parent) {
+            return false;
+        }
+        let (Some(current), Some(target)) = (self.sibling_index(entity), self.sibling_index(sibling)) else {
+            return false;
+        };
+        // Removing the node first shifts later siblings down by one
+        let target = if current < target { target - 1 } else { target };
+        self.set_sibling_index(entity, target + offset)
+    }
+
+    /// Get the sibling list containing a node
+    fn siblings_mut(&mut self, entity: Entity) -> Option<&mut [Entity]> {
+        match self.get_node(entity)?.parent {
+            Some(parent) => Some(&mut self.node_mut_untracked(parent)?.children[..]),
+            None => Some(&mut self.roots[..]),
         }
     }
 
@@ -293,59 +521,240 @@ impl SceneGraph {
     }
 
     /// Update world transforms for all dirty nodes
+    ///
+    /// Only subtrees under dirty nodes are recomputed, on the calling thread.
     pub fn update_transforms(&mut self) {
-        // Start from roots and propagate down
-        let roots: Vec<_> = self.roots.clone();
-        for root in roots {
-            self.update_transform_recursive(root, Mat4::IDENTITY);
+        self.update_transforms_with(None);
+    }
+
+    /// Update world transforms, spreading independent dirty subtrees across
+    /// threads when there is enough work
+    ///
+    /// With at least 4096 dirty nodes in more than one subtree, the subtrees
+    /// run in parallel on the calling thread and up to `num_workers - 1`
+    /// helper threads (see [`JobSystem::scope`]). Each subtree is still
+    /// updated by a single thread.
+    pub fn update_transforms_on(&mut self, job_system: &JobSystem) {
+        self.update_transforms_with(Some(job_system));
+    }
+
+    fn update_transforms_with(&mut self, job_system: Option<&JobSystem>) {
+        self.moved.clear();
+        self.removed.clear();
+        std::mem::swap(&mut self.removed, &mut self.removed_pending);
+        if self.order_dirty {
+            self.rebuild_order();
+        }
+        if self.dirty.is_empty() {
+            return;
         }
+
+        let mut starts: Vec<usize> = self
+            .dirty
+            .drain(..)
+            .filter_map(|entity| self.lookup.get(&entity).copied())
+            .collect();
+        starts.sort_unstable();
+
+        // Keep the outermost dirty nodes; nested ones are covered by their ancestor
+        let mut subtrees = Vec::new();
+        let mut covered = 0;
+        let mut dirty_nodes = 0;
+        for start in starts {
+            if start < covered {
+                continue;
+            }
+            let node = &self.nodes[start];
+            let parent_world = match node.parent_index {
+                NO_PARENT => Mat4::IDENTITY,
+                parent => self.nodes[parent].world_matrix,
+            };
+            covered = node.subtree_end;
+            dirty_nodes += covered - start;
+            subtrees.push((start, covered, parent_world));
+        }
+
+        // Split the node array into the disjoint dirty ranges
+        let mut ranges = Vec::with_capacity(subtrees.len());
+        let mut rest = self.nodes.as_mut_slice();
+        let mut offset = 0;
+        for &(start, end, parent_world) in &subtrees {
+            let (_, tail) = rest.split_at_mut(start - offset);
+            let (range, tail) = tail.split_at_mut(end - start);
+            ranges.push((range, start, parent_world));
+            rest = tail;
+            offset = end;
+        }
+
+        match job_system {
+            Some(job_system) if dirty_nodes >= PARALLEL_UPDATE_THRESHOLD && ranges.len() > 1 => {
+                job_system.scope(
+                    "update_transforms",
+                    ranges
+                        .into_iter()
+                        .map(|(range, start, parent_world)| move || update_subtree(range, start, parent_world)),
+                );
+            }
+            _ => {
+                for (range, start, parent_world) in ranges {
+                    update_subtree(range, start, parent_world);
+                }
+            }
+        }
+
+        for (start, end, _) in subtrees {
+            self.moved.extend(self.nodes[start..end].iter().map(|node| node.entity));
+        }
+    }
+
+    /// Get the nodes whose world transforms the last update recomputed
+    pub fn moved_nodes(&self) -> &[Entity] {
+        &self.moved
+    }
+
+    /// Get the nodes removed before the last update
+    pub fn removed_nodes(&self) -> &[Entity] {
+        &self.removed
     }
 
-    fn update_transform_recursive(&mut self, entity: Entity, parent_world: Mat4) {
-        let (world_matrix, children) = {
-            let node = match self.nodes.get_mut(&entity) {
-                Some(n) => n,
-                None => return,
+    /// Re-sort the node array depth-first after hierarchy changes
+    fn rebuild_order(&mut self) {
+        let mut old: Vec<Option<Node>> = std::mem::take(&mut self.nodes).into_iter().map(Some).collect();
+        let mut stack: Vec<(Entity, usize)> = self.roots.iter().rev().map(|&root| (root, NO_PARENT)).collect();
+
+        while let Some((entity, parent_index)) = stack.pop() {
+            let Some(mut node) = self.lookup.get(&entity).and_then(|&index| old[index].take()) else {
+                continue;
             };
-            
-            let world_matrix = parent_world * node.local_transform.local_matrix();
-            node.world_matrix = world_matrix;
-            
-            // Decompose world matrix to get world transform
-            let (scale, rotation, translation) = world_matrix.to_scale_rotation_translation();
-            node.world_transform = Transform::new(translation, rotation, scale);
-            node.dirty = false;
-            
-            (world_matrix, node.children.clone())
-        };
-        
-        // Recursively update children
-        for child in children {
-            self.update_transform_recursive(child, world_matrix);
+            let index = self.nodes.len();
+            node.parent_index = parent_index;
+            node.subtree_end = index + 1;
+            stack.extend(node.children.iter().rev().map(|&child| (child, index)));
+            self.nodes.push(node);
+        }
+
+        // Children come after their parent, so a reverse pass sees them first
+        for index in (0..self.nodes.len()).rev() {
+            let parent = self.nodes[index].parent_index;
+            if parent != NO_PARENT {
+                let end = self.nodes[index].subtree_end;
+                self.nodes[parent].subtree_end = self.nodes[parent].subtree_end.max(end);
+            }
+        }
+
+        for (index, node) in self.nodes.iter().enumerate() {
+            self.lookup.insert(node.entity, index);
         }
+        self.order_dirty = false;
     }
 
     /// Find a node by name
     pub fn find_by_name(&self, name: &str) -> Option<Entity> {
-        self.nodes.values()
+        self.nodes.iter()
             .find(|node| node.name == name)
             .map(|node| node.entity)
     }
 
-    /// Get all descendants of an entity
-    pub fn get_descendants(&self, entity: Entity) -> Vec<Entity> {
-        let mut descendants = Vec::new();
-        self.collect_descendants(entity, &mut descendants);
-        descendants
+    /// Find a node by path relative to `root` (e.g. `"Body/Arm_L/Hand"`)
+    ///
+    /// Path segments may use wildcards: `*` matches any run of characters,
+    /// `?` a single character, and a `**` segment any number of levels.
+    /// Returns the first match in depth-first order.
+    pub fn find_path(&self, root: Entity, path: &str) -> Option<Entity> {
+        let mut found = None;
+        self.match_path(root, path, &mut |entity| {
+            found = Some(entity);
+            true
+        });
+        found
+    }
+
+    /// Find every node matching a path pattern relative to `root`
+    pub fn find_path_all(&self, root: Entity, path: &str) -> Vec<Entity> {
+        let mut found = Vec::new();
+        self.match_path(root, path, &mut |entity| {
+            if !found.contains(&entity) {
+                found.push(entity);
+            }
+            false
+        });
+        found
     }
 
-    fn collect_descendants(&self, entity: Entity, result: &mut Vec<Entity>) {
-        if let Some(node) = self.nodes.get(&entity) {
-            for &child in &node.children {
-                result.push(child);
-                self.collect_descendants(child, result);
+    fn match_path(&self, root: Entity, path: &str, visit: &mut dyn FnMut(Entity) -> bool) {
+        let path = path.trim_matches('/');
+        if path.is_empty() {
+            if self.lookup.contains_key(&root) {
+                visit(root);
             }
+            return;
+        }
+        self.match_segments(root, path, visit);
+    }
+
+    /// Match the remaining `path` below `entity`; returns true to stop
+    fn match_segments(&self, entity: Entity, path: &str, visit: &mut dyn FnMut(Entity) -> bool) -> bool {
+        if path.is_empty() {
+            return visit(entity);
         }
+        let Some(node) = self.get_node(entity) else {
+            return false;
+        };
+        let (segment, rest) = path.split_once('/').unwrap_or((path, ""));
+
+        if segment == "**" {
+            if self.match_segments(entity, rest, visit) {
+                return true;
+            }
+            return node.children.iter().any(|&child| self.match_segments(child, path, visit));
+        }
+
+        node.children.iter().any(|&child| {
+            self.get_node(child).is_some_and(|c| glob_match(segment, &c.name))
+                && self.match_segments(child, rest, visit)
+        })
+    }
+
+    /// Iterate nodes with a tag
+    pub fn find_by_tag<'a>(&'a self, tag: &'a str) -> impl Iterator<Item = Entity> + 'a {
+        self.nodes.iter().filter(move |node| node.has_tag(tag)).map(|node| node.entity)
+    }
+
+    /// Iterate the ancestors of a node, nearest first
+    pub fn ancestors(&self, entity: Entity) -> Ancestors<'_> {
+        Ancestors {
+            graph: self,
+            next: self.get_node(entity).and_then(|node| node.parent),
+        }
+    }
+
+    /// Iterate the descendants of a node depth-first, without allocating
+    pub fn descendants(&self, entity: Entity) -> Descendants<'_> {
+        Descendants {
+            graph: self,
+            root: entity,
+            next: self.get_node(entity).and_then(|node| node.children.first().copied()),
+        }
+    }
+
+    /// Iterate the descendants of a node breadth-first
+    ///
+    /// `queue` is scratch space; reuse it across calls to avoid allocating.
+    pub fn descendants_breadth_first<'a>(
+        &'a self,
+        entity: Entity,
+        queue: &'a mut VecDeque<Entity>,
+    ) -> BreadthFirst<'a> {
+        queue.clear();
+        if let Some(node) = self.get_node(entity) {
+            queue.extend(node.children.iter().copied());
+        }
+        BreadthFirst { graph: self, queue }
+    }
+
+    /// Get all descendants of an entity
+    pub fn get_descendants(&self, entity: Entity) -> Vec<Entity> {
+        self.descendants(entity).collect()
     }
 
     /// Get the number of nodes in the scene
@@ -361,7 +770,136 @@ impl SceneGraph {
     /// Clear all nodes from the scene
     pub fn clear(&mut self) {
         self.nodes.clear();
+        self.lookup.clear();
         self.roots.clear();
+        self.dirty.clear();
+        self.moved.clear();
+        self.removed.clear();
+        self.removed_pending.clear();
+        self.order_dirty = false;
+    }
+}
+
+/// Iterator over a node's ancestors
+pub struct Ancestors<'a> {
+    graph: &'a SceneGraph,
+    next: Option<Entity>,
+}
+
+impl Iterator for Ancestors<'_> {
+    type Item = Entity;
+
+    fn next(&mut self) -> Option<Entity> {
+        let entity = self.next?;
+        self.next = self.graph.get_node(entity).and_then(|node| node.parent);
+        Some(entity)
+    }
+}
+
+/// Depth-first iterator over a node's descendants
+pub struct Descendants<'a> {
+    graph: &'a SceneGraph,
+    root: Entity,
+    next: Option<Entity>,
+}
+
+impl Iterator for Descendants<'_> {
+    type Item = Entity;
+
+    fn next(&mut self) -> Option<Entity> {
+        let entity = self.next?;
+        let node = self.graph.get_node(entity)?;
+
+        // First child, else the next sibling of the nearest ancestor below the root
+        self.next = node.children.first().copied().or_else(|| {
+            let mut current = node;
+            while current.entity != self.root {
+                let parent = self.graph.get_node(current.parent?)?;
+                let index = parent.children.iter().position(|&c| c == current.entity)?;
+                if let Some(&sibling) = parent.children.get(index + 1) {
+                    return Some(sibling);
+                }
+                if parent.entity == self.root {
+                    return None;
+                }
+                current = parent;
+            }
+            None
+        });
+        Some(entity)
+    }
+}
+
+/// Breadth-first iterator over a node's descendants
+pub struct BreadthFirst<'a> {
+    graph: &'a SceneGraph,
+    queue: &'a mut VecDeque<Entity>,
+}
+
+impl Iterator for BreadthFirst<'_> {
+    type Item = Entity;
+
+    fn next(&mut self) -> Option<Entity> {
+        let entity = self.queue.pop_front()?;
+        if let Some(node) = self.graph.get_node(entity) {
+            self.queue.extend(node.children.iter().copied());
+        }
+        Some(entity)
+    }
+}
+
+/// Match a name against a pattern with `*` and `?` wildcards
+///
+/// Wildcards match whole characters, not UTF-8 bytes.
+fn glob_match(pattern: &str, name: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+    let (mut p, mut n) = (0, 0);
+    let mut backtrack = None;
+
+    while n < name.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                backtrack = Some((p, n));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == name[n] => {
+                p += 1;
+                n += 1;
+            }
+            _ => match backtrack {
+                // Let the last `*` swallow one more character
+                Some((star, matched)) => {
+                    p = star + 1;
+                    n = matched + 1;
+                    backtrack = Some((star, matched + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+/// Recompute world transforms for a contiguous depth-first subtree
+///
+/// `start` is the subtree root's index in the full node array.
+fn update_subtree(nodes: &mut [Node], start: usize, parent_world: Mat4) {
+    for index in 0..nodes.len() {
+        let parent_world = if index == 0 {
+            parent_world
+        } else {
+            nodes[nodes[index].parent_index - start].world_matrix
+        };
+
+        let node = &mut nodes[index];
+        let world_matrix = parent_world * node.local_transform.local_matrix();
+        node.world_matrix = world_matrix;
+        
+        // Decompose world matrix to get world transform
+        let (scale, rotation, translation) = world_matrix.to_scale_rotation_translation();
+        node.world_transform = Transform::new(translation, rotation, scale);
+        node.dirty = false;
     }
 }
 
@@ -371,13 +909,68 @@ impl Default for SceneGraph {
     }
 }
 
+/// Prefab instantiation errors
+#[derive(Error, Debug)]
+pub enum PrefabError {
+    #[error("Unknown component '{component}' on prefab node '{path}'")]
+    UnknownComponent { path: String, component: String },
+    
+    #[error("Invalid data for component '{component}' on prefab node '{path}': {reason}")]
+    InvalidComponent {
+        path: String,
+        component: String,
+        reason: String,
+    },
+    
+    #[error("Duplicate prefab node path '{0}'")]
+    DuplicatePath(String),
+    
+    #[error("Prefab '{0}' not found")]
+    MissingPrefab(String),
+    
+    #[error("Prefab '{0}' references itself")]
+    Cycle(String),
+    
+    #[error("Override target '{0}' does not exist")]
+    InvalidOverride(String),
+    
+    #[error("Prefab '{0}' has a base or nested prefabs and must be instantiated through a PrefabLibrary")]
+    RequiresLibrary(String),
+}
+
+/// Serialized component attached to a prefab node
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ComponentData {
+    /// Name the component type is registered under
+    pub type_name: String,
+    /// Serialized component value
+    pub data: serde_json::Value,
+}
+
+impl ComponentData {
+    /// Create component data
+    pub fn new(type_name: impl Into<String>, data: serde_json::Value) -> Self {
+        Self {
+            type_name: type_name.into(),
+            data,
+        }
+    }
+}
+
 /// Prefab definition for instantiating hierarchies
 #[derive(Debug, Clone)]
 pub struct Prefab {
     /// Prefab name
     pub name: String,
     /// Root node template
+    ///
+    /// For variants, the root's components and children are added to the
+    /// base prefab's root.
     pub root: PrefabNode,
+    /// Base prefab this variant inherits from
+    pub base: Option<String>,
+    /// Overrides applied on top of the base prefab
+    pub overrides: Vec<PropertyOverride>,
 }
 
 /// Node within a prefab
@@ -389,7 +982,176 @@ pub struct PrefabNode {
     pub transform: Transform,
     /// Child nodes
     pub children: Vec<PrefabNode>,
-    // Components would be added here
+    /// Components added to the spawned entity
+    pub components: Vec<ComponentData>,
+    /// Name of a prefab in a [`PrefabLibrary`] this node instantiates
+    ///
+    /// The referenced prefab's root takes this node's place; this node's
+    /// transform is applied on top and its components and children are added.
+    pub prefab: Option<String>,
+}
+
+impl PrefabNode {
+    /// Create an empty node
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            transform: Transform::IDENTITY,
+            children: Vec::new(),
+            components: Vec::new(),
+            prefab: None,
+        }
+    }
+
+    /// Create a node that instantiates another prefab
+    pub fn from_prefab(name: impl Into<String>, prefab: impl Into<String>) -> Self {
+        let mut node = Self::new(name);
+        node.prefab = Some(prefab.into());
+        node
+    }
+
+    /// Set the local transform
+    pub fn with_transform(mut self, transform: Transform) -> Self {
+        self.transform = transform;
+        self
+    }
+
+    /// Add a serialized component
+    pub fn with_component(mut self, type_name: impl Into<String>, data: serde_json::Value) -> Self {
+        self.components.push(ComponentData::new(type_name, data));
+        self
+    }
+
+    /// Add a child node
+    pub fn with_child(mut self, child: PrefabNode) -> Self {
+        self.children.push(child);
+        self
+    }
+
+    /// Find a descendant by path relative to this node (e.g. `"Root/Door"`)
+    pub fn find_path_mut(&mut self, path: &str) -> Option<&mut PrefabNode> {
+        let mut parts = path.split('/');
+        if parts.next() != Some(self.name.as_str()) {
+            return None;
+        }
+        let mut node = self;
+        for part in parts {
+            node = node.children.iter_mut().find(|child| child.name == part)?;
+        }
+        Some(node)
+    }
+
+    /// Check if this node or a descendant instantiates another prefab
+    fn references_prefabs(&self) -> bool {
+        self.prefab.is_some() || self.children.iter().any(Self::references_prefabs)
+    }
+
+    /// Add a component, replacing one of the same type
+    fn set_component(&mut self, component: ComponentData) {
+        match self.components.iter_mut().find(|c| c.type_name == component.type_name) {
+            Some(existing) => *existing = component,
+            None => self.components.push(component),
+        }
+    }
+}
+
+/// Property changed by a prefab override
+#[derive(Debug, Clone, PartialEq)]
+pub enum OverrideProperty {
+    /// Replace the node's local transform
+    Transform(Transform),
+    /// Set a component value
+    ///
+    /// `field` is a `/`-separated path into the component data; an empty
+    /// field replaces (or adds) the whole component.
+    Component {
+        type_name: String,
+        field: String,
+        value: serde_json::Value,
+    },
+}
+
+/// Override of one property on a prefab node
+#[derive(Debug, Clone, PartialEq)]
+pub struct PropertyOverride {
+    /// Node path including the root name (e.g. `"Root/Door"`)
+    pub path: String,
+    /// Overridden property
+    pub property: OverrideProperty,
+}
+
+impl PropertyOverride {
+    /// Override a node's transform
+    pub fn transform(path: impl Into<String>, transform: Transform) -> Self {
+        Self {
+            path: path.into(),
+            property: OverrideProperty::Transform(transform),
+        }
+    }
+
+    /// Override a component field (empty `field` for the whole component)
+    pub fn component(
+        path: impl Into<String>,
+        type_name: impl Into<String>,
+        field: impl Into<String>,
+        value: serde_json::Value,
+    ) -> Self {
+        Self {
+            path: path.into(),
+            property: OverrideProperty::Component {
+                type_name: type_name.into(),
+                field: field.into(),
+                value,
+            },
+        }
+    }
+
+    /// Check if both overrides target the same property
+    fn same_target(&self, other: &PropertyOverride) -> bool {
+        if self.path != other.path {
+            return false;
+        }
+        match (&self.property, &other.property) {
+            (OverrideProperty::Transform(_), OverrideProperty::Transform(_)) => true,
+            (
+                OverrideProperty::Component { type_name: a, field: fa, .. },
+                OverrideProperty::Component { type_name: b, field: fb, .. },
+            ) => a == b && fa == fb,
+            _ => false,
+        }
+    }
+
+    /// Apply to a resolved prefab tree
+    fn apply(&self, root: &mut PrefabNode) -> Result<(), PrefabError> {
+        let invalid = || PrefabError::InvalidOverride(self.path.clone());
+        let node = root.find_path_mut(&self.path).ok_or_else(invalid)?;
+        match &self.property {
+            OverrideProperty::Transform(transform) => node.transform = *transform,
+            OverrideProperty::Component { type_name, field, value } if field.is_empty() => {
+                node.set_component(ComponentData::new(type_name.clone(), value.clone()));
+            }
+            OverrideProperty::Component { type_name, field, value } => {
+                let component = node
+                    .components
+                    .iter_mut()
+                    .find(|c| &c.type_name == type_name)
+                    .ok_or_else(invalid)?;
+                let mut target = &mut component.data;
+                for key in field.split('/') {
+                    if !target.is_object() {
+                        *target = serde_json::Value::Object(Default::default());
+                    }
+                    target = target
+                        .as_object_mut()
+                        .unwrap()
+                        .entry(key)
+                        .or_insert(serde_json::Value::Null);
+                }
+                *target = value.clone();
+            }
+        }
+        Ok(())
+    }
 }
 
 impl Prefab {
@@ -397,13 +1159,427 @@ impl Prefab {
     pub fn new(name: impl Into<String>) -> Self {
         Self {
             name: name.into(),
-            root: PrefabNode {
-                name: String::from("Root"),
-                transform: Transform::IDENTITY,
+            root: PrefabNode::new("Root"),
+            base: None,
+            overrides: Vec::new(),
+        }
+    }
+
+    /// Create a variant inheriting from a base prefab
+    pub fn variant(name: impl Into<String>, base: impl Into<String>) -> Self {
+        let mut prefab = Self::new(name);
+        prefab.base = Some(base.into());
+        prefab
+    }
+
+    /// Add an override (variants only)
+    pub fn with_override(mut self, property_override: PropertyOverride) -> Self {
+        self.overrides.push(property_override);
+        self
+    }
+
+    /// Instantiate the prefab into a world and scene graph
+    ///
+    /// Spawns one entity and scene node per prefab node, adds their
+    /// components, and parents the instance root under `parent`. The root's
+    /// local transform is `transform` applied on top of the prefab root's.
+    /// Returns spawned entities keyed by node path (e.g. `"Root/Arm/Hand"`).
+    /// On error, everything spawned so far is removed again.
+    ///
+    /// Variants and prefabs with nested prefab references need the rest of
+    /// their library; they fail with [`PrefabError::RequiresLibrary`] and
+    /// must go through [`PrefabLibrary::instantiate`] instead.
+    pub fn instantiate(
+        &self,
+        world: &mut World,
+        scene_graph: &mut SceneGraph,
+        parent: Option<Entity>,
+        transform: Transform,
+    ) -> Result<HashMap<String, Entity>, PrefabError> {
+        if self.base.is_some() || self.root.references_prefabs() {
+            return Err(PrefabError::RequiresLibrary(self.name.clone()));
+        }
+
+        let mut spawned = HashMap::new();
+        let root_transform = transform.mul_transform(&self.root.transform);
+        let result = Self::instantiate_node(
+            &self.root,
+            self.root.name.clone(),
+            root_transform,
+            parent,
+            world,
+            scene_graph,
+            &mut spawned,
+        );
+
+        if let Err(err) = result {
+            for &entity in spawned.values() {
+                scene_graph.remove_node(entity);
+                world.despawn(entity);
+            }
+            return Err(err);
+        }
+
+        Ok(spawned)
+    }
+
+    fn instantiate_node(
+        node: &PrefabNode,
+        path: String,
+        transform: Transform,
+        parent: Option<Entity>,
+        world: &mut World,
+        scene_graph: &mut SceneGraph,
+        spawned: &mut HashMap<String, Entity>,
+    ) -> Result<(), PrefabError> {
+        if spawned.contains_key(&path) {
+            return Err(PrefabError::DuplicatePath(path));
+        }
+
+        let entity = world.spawn();
+        scene_graph.add_node(entity, node.name.clone()).local_transform = transform;
+        if parent.is_some() {
+            scene_graph.set_parent(entity, parent);
+        }
+        spawned.insert(path.clone(), entity);
+        add_node_components(node, &path, entity, world)?;
+
+        for child in &node.children {
+            let child_path = format!("{}/{}", path, child.name);
+            Self::instantiate_node(child, child_path, child.transform, Some(entity), world, scene_graph, spawned)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Add a prefab node's components to an entity
+fn add_node_components(node: &PrefabNode, path: &str, entity: Entity, world: &mut World) -> Result<(), PrefabError> {
+    for component in &node.components {
+        if !world.component_registry().contains(&component.type_name) {
+            return Err(PrefabError::UnknownComponent {
+                path: path.to_string(),
+                component: component.type_name.clone(),
+            });
+        }
+        if let Err(reason) = world.add_serialized_component(entity, &component.type_name, &component.data) {
+            return Err(PrefabError::InvalidComponent {
+                path: path.to_string(),
+                component: component.type_name.clone(),
+                reason,
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Collection of prefabs that can reference each other by name
+#[derive(Debug, Clone, Default)]
+pub struct PrefabLibrary {
+    prefabs: HashMap<String, Prefab>,
+}
+
+impl PrefabLibrary {
+    /// Create an empty library
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add or replace a prefab
+    pub fn insert(&mut self, prefab: Prefab) {
+        self.prefabs.insert(prefab.name.clone(), prefab);
+    }
+
+    /// Get a prefab by name
+    pub fn get(&self, name: &str) -> Option<&Prefab> {
+        self.prefabs.get(name)
+    }
+
+    /// Get a mutable prefab by name
+    ///
+    /// Call [`PrefabLibrary::sync_instance`] afterwards to update instances.
+    pub fn get_mut(&mut self, name: &str) -> Option<&mut Prefab> {
+        self.prefabs.get_mut(name)
+    }
+
+    /// Remove a prefab
+    pub fn remove(&mut self, name: &str) -> Option<Prefab> {
+        self.prefabs.remove(name)
+    }
+
+    /// Get the number of prefabs
+    pub fn len(&self) -> usize {
+        self.prefabs.len()
+    }
+
+    /// Check if the library is empty
+    pub fn is_empty(&self) -> bool {
+        self.prefabs.is_empty()
+    }
+
+    /// Resolve a prefab into a flat tree
+    ///
+    /// Expands variants and nested prefab references so the result can be
+    /// instantiated directly.
+    pub fn resolve(&self, name: &str) -> Result<Prefab, PrefabError> {
+        let mut stack = Vec::new();
+        let root = self.resolve_prefab(name, &mut stack)?;
+        Ok(Prefab {
+            name: name.to_string(),
+            root,
+            base: None,
+            overrides: Vec::new(),
+        })
+    }
+
+    fn resolve_prefab(&self, name: &str, stack: &mut Vec<String>) -> Result<PrefabNode, PrefabError> {
+        if stack.iter().any(|n| n == name) {
+            return Err(PrefabError::Cycle(name.to_string()));
+        }
+        let prefab = self
+            .prefabs
+            .get(name)
+            .ok_or_else(|| PrefabError::MissingPrefab(name.to_string()))?;
+        stack.push(name.to_string());
+
+        let root = match &prefab.base {
+            Some(base) => {
+                let mut root = self.resolve_prefab(base, stack)?;
+                for component in &prefab.root.components {
+                    root.set_component(component.clone());
+                }
+                for child in &prefab.root.children {
+                    root.children.push(self.resolve_node(child, stack)?);
+                }
+                for property_override in &prefab.overrides {
+                    property_override.apply(&mut root)?;
+                }
+                root
+            }
+            None => self.resolve_node(&prefab.root, stack)?,
+        };
+
+        stack.pop();
+        Ok(root)
+    }
+
+    fn resolve_node(&self, node: &PrefabNode, stack: &mut Vec<String>) -> Result<PrefabNode, PrefabError> {
+        let mut resolved = match &node.prefab {
+            Some(reference) => {
+                let mut root = self.resolve_prefab(reference, stack)?;
+                root.name = node.name.clone();
+                root.transform = node.transform.mul_transform(&root.transform);
+                for component in &node.components {
+                    root.set_component(component.clone());
+                }
+                root
+            }
+            None => PrefabNode {
                 children: Vec::new(),
+                ..node.clone()
             },
+        };
+        for child in &node.children {
+            resolved.children.push(self.resolve_node(child, stack)?);
+        }
+        Ok(resolved)
+    }
+
+    /// Instantiate a prefab, tracking the instance for later syncing
+    pub fn instantiate(
+        &self,
+        name: &str,
+        world: &mut World,
+        scene_graph: &mut SceneGraph,
+        parent: Option<Entity>,
+        transform: Transform,
+    ) -> Result<PrefabInstance, PrefabError> {
+        let entities = self.resolve(name)?.instantiate(world, scene_graph, parent, transform)?;
+        Ok(PrefabInstance {
+            prefab: name.to_string(),
+            transform,
+            parent,
+            entities,
+            overrides: Vec::new(),
+        })
+    }
+
+    /// Bring an instance up to date with its source prefab and overrides
+    ///
+    /// Transforms and component values are rewritten from the resolved
+    /// prefab with the instance's overrides applied on top. Nodes added to
+    /// the source are spawned and nodes removed from it are despawned.
+    /// Components removed from a source node are not removed from its
+    /// existing entity; re-instantiate to drop them.
+    ///
+    /// Unknown components and duplicate paths are caught before anything is
+    /// touched. If a component fails to deserialize midway, nodes spawned by
+    /// this sync are removed again and the instance keeps its entities, but
+    /// components already rewritten keep their new values.
+    pub fn sync_instance(
+        &self,
+        instance: &mut PrefabInstance,
+        world: &mut World,
+        scene_graph: &mut SceneGraph,
+    ) -> Result<(), PrefabError> {
+        let mut prefab = self.resolve(&instance.prefab)?;
+        for property_override in &instance.overrides {
+            property_override.apply(&mut prefab.root)?;
+        }
+        Self::validate_node(&prefab.root, prefab.root.name.clone(), world, &mut HashSet::new())?;
+
+        let mut entities = HashMap::with_capacity(instance.entities.len());
+        let root_transform = instance.transform.mul_transform(&prefab.root.transform);
+        let result = Self::sync_node(
+            &prefab.root,
+            prefab.root.name.clone(),
+            root_transform,
+            instance.parent,
+            instance,
+            world,
+            scene_graph,
+            &mut entities,
+        );
+
+        if let Err(err) = result {
+            // Roll back nodes spawned by this sync
+            for (path, entity) in entities {
+                if instance.entities.get(&path) != Some(&entity) {
+                    scene_graph.remove_node(entity);
+                    world.despawn(entity);
+                }
+            }
+            return Err(err);
+        }
+
+        // Despawn nodes no longer in the prefab
+        for (path, entity) in instance.entities.drain() {
+            if !entities.contains_key(&path) {
+                scene_graph.remove_node(entity);
+                world.despawn(entity);
+            }
+        }
+        instance.entities = entities;
+        Ok(())
+    }
+
+    /// Check component types and path uniqueness of a resolved tree
+    fn validate_node(
+        node: &PrefabNode,
+        path: String,
+        world: &World,
+        paths: &mut HashSet<String>,
+    ) -> Result<(), PrefabError> {
+        if !paths.insert(path.clone()) {
+            return Err(PrefabError::DuplicatePath(path));
+        }
+        if let Some(component) = node
+            .components
+            .iter()
+            .find(|component| !world.component_registry().contains(&component.type_name))
+        {
+            return Err(PrefabError::UnknownComponent {
+                path,
+                component: component.type_name.clone(),
+            });
+        }
+        for child in &node.children {
+            Self::validate_node(child, format!("{}/{}", path, child.name), world, paths)?;
+        }
+        Ok(())
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn sync_node(
+        node: &PrefabNode,
+        path: String,
+        transform: Transform,
+        parent: Option<Entity>,
+        instance: &PrefabInstance,
+        world: &mut World,
+        scene_graph: &mut SceneGraph,
+        entities: &mut HashMap<String, Entity>,
+    ) -> Result<(), PrefabError> {
+        if entities.contains_key(&path) {
+            return Err(PrefabError::DuplicatePath(path));
+        }
+
+        let entity = match instance.entities.get(&path) {
+            Some(&entity) if world.is_alive(entity) => entity,
+            _ => {
+                let entity = world.spawn();
+                scene_graph.add_node(entity, node.name.clone());
+                if parent.is_some() {
+                    scene_graph.set_parent(entity, parent);
+                }
+                entity
+            }
+        };
+        if let Some(scene_node) = scene_graph.get_node_mut(entity) {
+            scene_node.local_transform = transform;
+        }
+        entities.insert(path.clone(), entity);
+        add_node_components(node, &path, entity, world)?;
+
+        for child in &node.children {
+            let child_path = format!("{}/{}", path, child.name);
+            Self::sync_node(child, child_path, child.transform, Some(entity), instance, world, scene_graph, entities)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Spawned prefab instance with per-instance overrides
+///
+/// Overrides are stored as diffs against the source prefab so that edits to
+/// the source reach every instance without losing overridden values.
+#[derive(Debug, Clone)]
+pub struct PrefabInstance {
+    prefab: String,
+    transform: Transform,
+    parent: Option<Entity>,
+    entities: HashMap<String, Entity>,
+    overrides: Vec<PropertyOverride>,
+}
+
+impl PrefabInstance {
+    /// Get the source prefab name
+    pub fn prefab(&self) -> &str {
+        &self.prefab
+    }
+
+    /// Get spawned entities keyed by node path
+    pub fn entities(&self) -> &HashMap<String, Entity> {
+        &self.entities
+    }
+
+    /// Get the entity spawned for a node path
+    pub fn entity(&self, path: &str) -> Option<Entity> {
+        self.entities.get(path).copied()
+    }
+
+    /// Get the instance overrides
+    pub fn overrides(&self) -> &[PropertyOverride] {
+        &self.overrides
+    }
+
+    /// Set an override, replacing any on the same property
+    ///
+    /// Takes effect on the next [`PrefabLibrary::sync_instance`].
+    pub fn set_override(&mut self, property_override: PropertyOverride) {
+        match self.overrides.iter_mut().find(|o| o.same_target(&property_override)) {
+            Some(existing) => *existing = property_override,
+            None => self.overrides.push(property_override),
         }
     }
+
+    /// Revert all overrides on a node path, returning how many were removed
+    pub fn revert(&mut self, path: &str) -> usize {
+        let before = self.overrides.len();
+        self.overrides.retain(|o| o.path != path);
+        before - self.overrides.len()
+    }
 }
 
 #[cfg(test)]
@@ -514,6 +1690,184 @@ mod tests {
         assert!((child_node.world_transform().position.x - 15.0).abs() < 0.001);
     }
 
+    #[test]
+    fn test_incremental_transform_update() {
+        let mut sg = SceneGraph::new();
+        let a = Entity::new(0, 0);
+        let b = Entity::new(1, 0);
+        let c = Entity::new(2, 0);
+        
+        sg.add_node(a, "A");
+        sg.add_node(b, "B").local_transform.position = Vec3::X;
+        sg.add_node(c, "C").local_transform.position = Vec3::Y;
+        sg.set_parent(b, Some(a));
+        sg.set_parent(c, Some(b));
+        sg.update_transforms();
+        assert!(!sg.get_node(c).unwrap().is_dirty());
+        
+        // Moving the middle node updates its subtree only
+        sg.get_node_mut(b).unwrap().local_transform.position = Vec3::Z;
+        assert!(sg.get_node(b).unwrap().is_dirty());
+        assert!(!sg.get_node(a).unwrap().is_dirty());
+        sg.update_transforms();
+        assert_eq!(sg.get_node(c).unwrap().world_transform().position, Vec3::new(0.0, 1.0, 1.0));
+        
+        // Removing a parent turns its children into roots
+        sg.remove_node(b);
+        sg.update_transforms();
+        assert!(sg.roots().contains(&c));
+        assert_eq!(sg.get_node(c).unwrap().world_transform().position, Vec3::Y);
+    }
+
+    #[test]
+    fn test_parallel_transform_update() {
+        let mut sg = SceneGraph::new();
+        let count = PARALLEL_UPDATE_THRESHOLD as u32;
+        for i in 0..count {
+            let root = Entity::new(i * 2, 0);
+            let child = Entity::new(i * 2 + 1, 0);
+            sg.add_node(root, "Root").local_transform.position = Vec3::new(i as f32, 0.0, 0.0);
+            sg.add_node(child, "Child").local_transform.position = Vec3::Y;
+            sg.set_parent(child, Some(root));
+        }
+        sg.update_transforms_on(&JobSystem::new(4));
+        
+        for i in 0..count {
+            let child = sg.get_node(Entity::new(i * 2 + 1, 0)).unwrap();
+            assert_eq!(child.world_transform().position, Vec3::new(i as f32, 1.0, 0.0));
+        }
+    }
+
+    #[test]
+    fn test_set_parent_keep_world() {
+        let mut sg = SceneGraph::new();
+        let parent = Entity::new(0, 0);
+        let child = Entity::new(1, 0);
+        
+        sg.add_node(parent, "Parent").local_transform = Transform::new(
+            Vec3::new(10.0, 0.0, 0.0),
+            Quat::from_rotation_y(std::f32::consts::FRAC_PI_2),
+            Vec3::splat(2.0),
+        );
+        sg.add_node(child, "Child").local_transform.position = Vec3::new(1.0, 2.0, 3.0);
+        
+        assert!(sg.set_parent_keep_world(child, Some(parent)));
+        sg.update_transforms();
+        let world = sg.get_node(child).unwrap().world_transform().position;
+        assert!((world - Vec3::new(1.0, 2.0, 3.0)).length() < 0.001);
+        
+        assert!(sg.set_parent_keep_world(child, None));
+        let local = sg.get_node(child).unwrap().local_transform.position;
+        assert!((local - Vec3::new(1.0, 2.0, 3.0)).length() < 0.001);
+    }
+
+    #[test]
+    fn test_set_parent_rejects_cycles() {
+        let mut sg = SceneGraph::new();
+        let a = Entity::new(0, 0);
+        let b = Entity::new(1, 0);
+        let c = Entity::new(2, 0);
+        sg.add_node(a, "A");
+        sg.add_node(b, "B");
+        sg.add_node(c, "C");
+        
+        assert!(sg.set_parent(b, Some(a)));
+        assert!(sg.set_parent(c, Some(b)));
+        assert!(!sg.set_parent(a, Some(c)));
+        assert!(!sg.set_parent(a, Some(a)));
+        assert!(!sg.set_parent(a, Some(Entity::new(9, 0))));
+        assert_eq!(sg.get_node(a).unwrap().parent, None);
+        assert!(sg.is_ancestor_of(a, c));
+    }
+
+    #[test]
+    fn test_sibling_order() {
+        let mut sg = SceneGraph::new();
+        let parent = Entity::new(0, 0);
+        let children: Vec<_> = (1..=4).map(|i| Entity::new(i, 0)).collect();
+        sg.add_node(parent, "Parent");
+        for &child in &children {
+            sg.add_node(child, "Child");
+            sg.set_parent(child, Some(parent));
+        }
+        let order = |sg: &SceneGraph| sg.get_node(parent).unwrap().children.to_vec();
+        
+        assert!(sg.set_sibling_index(children[0], 2));
+        assert_eq!(order(&sg), [children[1], children[2], children[0], children[3]]);
+        
+        assert!(sg.move_before(children[3], children[1]));
+        assert_eq!(order(&sg), [children[3], children[1], children[2], children[0]]);
+        
+        assert!(sg.move_after(children[3], children[0]));
+        assert_eq!(order(&sg), [children[1], children[2], children[0], children[3]]);
+        assert_eq!(sg.sibling_index(children[3]), Some(3));
+        
+        // Moving next to a root reparents to the root list
+        assert!(sg.move_before(children[2], parent));
+        assert_eq!(sg.roots(), &[children[2], parent]);
+        assert!(!sg.move_after(parent, children[0]));
+    }
+
+    fn build_character(sg: &mut SceneGraph, first: u32) -> Entity {
+        let names = ["Character", "Body", "Arm_L", "Hand", "Arm_R", "Hand"];
+        let parents = [None, Some(0), Some(1), Some(2), Some(1), Some(4)];
+        let entities: Vec<_> = (0..6).map(|i| Entity::new(first + i, 0)).collect();
+        for (i, name) in names.iter().enumerate() {
+            sg.add_node(entities[i], *name);
+            if let Some(parent) = parents[i] {
+                sg.set_parent(entities[i], Some(entities[parent]));
+            }
+        }
+        entities[0]
+    }
+
+    #[test]
+    fn test_find_path() {
+        let mut sg = SceneGraph::new();
+        let hero = build_character(&mut sg, 0);
+        let villain = build_character(&mut sg, 10);
+        
+        assert_eq!(sg.find_path(hero, "Body/Arm_L/Hand"), Some(Entity::new(3, 0)));
+        assert_eq!(sg.find_path(villain, "Body/Arm_L/Hand"), Some(Entity::new(13, 0)));
+        assert_eq!(sg.find_path(hero, "Body/Arm_R/Hand"), Some(Entity::new(5, 0)));
+        assert_eq!(sg.find_path(hero, "Body/Leg/Foot"), None);
+        assert_eq!(sg.find_path(hero, ""), Some(hero));
+        
+        assert_eq!(sg.find_path_all(hero, "Body/Arm_?/Hand").len(), 2);
+        assert_eq!(sg.find_path_all(hero, "**/Hand").len(), 2);
+        assert_eq!(sg.find_path_all(hero, "**/A*").len(), 2);
+        assert_eq!(sg.find_path_all(hero, "**").len(), 6);
+        assert!(glob_match("*_L", "Arm_L"));
+        assert!(!glob_match("*_L", "Arm_R"));
+        assert!(glob_match("Bäume?", "Bäume1"));
+        assert!(glob_match("?", "ä"));
+        assert!(glob_match("*ß*", "Straße"));
+        assert!(!glob_match("??", "ä"));
+    }
+
+    #[test]
+    fn test_tags_and_iterators() {
+        let mut sg = SceneGraph::new();
+        let hero = build_character(&mut sg, 0);
+        sg.get_node_mut(Entity::new(3, 0)).unwrap().add_tag("Grip");
+        sg.get_node_mut(Entity::new(5, 0)).unwrap().add_tag("Grip");
+        assert_eq!(sg.find_by_tag("Grip").count(), 2);
+        assert!(sg.get_node_mut(Entity::new(5, 0)).unwrap().remove_tag("Grip"));
+        assert_eq!(sg.find_by_tag("Grip").collect::<Vec<_>>(), [Entity::new(3, 0)]);
+        
+        let ancestors: Vec<_> = sg.ancestors(Entity::new(3, 0)).collect();
+        assert_eq!(ancestors, [Entity::new(2, 0), Entity::new(1, 0), hero]);
+        
+        let depth_first: Vec<_> = sg.descendants(hero).map(|e| e.index()).collect();
+        assert_eq!(depth_first, [1, 2, 3, 4, 5]);
+        let body: Vec<_> = sg.descendants(Entity::new(2, 0)).map(|e| e.index()).collect();
+        assert_eq!(body, [3]);
+        
+        let mut queue = VecDeque::new();
+        let breadth_first: Vec<_> = sg.descendants_breadth_first(hero, &mut queue).map(|e| e.index()).collect();
+        assert_eq!(breadth_first, [1, 2, 4, 3, 5]);
+    }
+
     #[test]
     fn test_find_by_name() {
         let mut sg = SceneGraph::new();
@@ -551,4 +1905,181 @@ mod tests {
         assert!(descendants.contains(&child2));
         assert!(descendants.contains(&grandchild));
     }
+
+    #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+    struct Health(f32);
+
+    fn test_prefab() -> Prefab {
+        let mut prefab = Prefab::new("Turret");
+        prefab.root = PrefabNode::new("Base")
+            .with_component("Health", serde_json::json!(100.0))
+            .with_child(
+                PrefabNode::new("Barrel")
+                    .with_transform(Transform::from_position(Vec3::new(0.0, 1.0, 0.0)))
+                    .with_component("Health", serde_json::json!(25.0)),
+            );
+        prefab
+    }
+
+    #[test]
+    fn test_prefab_instantiate() {
+        let mut world = World::new();
+        world.component_registry_mut().register::<Health>("Health");
+        let mut sg = SceneGraph::new();
+        let level = world.spawn();
+        sg.add_node(level, "Level");
+        
+        let transform = Transform::from_position(Vec3::new(5.0, 0.0, 0.0));
+        let spawned = test_prefab().instantiate(&mut world, &mut sg, Some(level), transform).unwrap();
+        assert_eq!(spawned.len(), 2);
+        
+        let base = spawned["Base"];
+        let barrel = spawned["Base/Barrel"];
+        assert_eq!(world.get_component::<Health>(base), Some(&Health(100.0)));
+        assert_eq!(world.get_component::<Health>(barrel), Some(&Health(25.0)));
+        assert_eq!(sg.get_node(base).unwrap().parent, Some(level));
+        assert_eq!(sg.get_node(barrel).unwrap().parent, Some(base));
+        
+        sg.update_transforms();
+        let barrel_position = sg.get_node(barrel).unwrap().world_transform().position;
+        assert!((barrel_position - Vec3::new(5.0, 1.0, 0.0)).length() < 0.001);
+    }
+
+    #[test]
+    fn test_prefab_instantiate_rolls_back() {
+        let mut world = World::new();
+        let mut sg = SceneGraph::new();
+        
+        let result = test_prefab().instantiate(&mut world, &mut sg, None, Transform::IDENTITY);
+        assert!(matches!(result, Err(PrefabError::UnknownComponent { .. })));
+        assert_eq!(world.entity_count(), 0);
+        assert!(sg.is_empty());
+        
+        let mut prefab = Prefab::new("Twins");
+        prefab.root = PrefabNode::new("Root")
+            .with_child(PrefabNode::new("Twin"))
+            .with_child(PrefabNode::new("Twin"));
+        let result = prefab.instantiate(&mut world, &mut sg, None, Transform::IDENTITY);
+        assert!(matches!(result, Err(PrefabError::DuplicatePath(path)) if path == "Root/Twin"));
+        assert!(sg.is_empty());
+    }
+
+    #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+    struct Door {
+        locked: bool,
+        key: u32,
+    }
+
+    fn test_library() -> PrefabLibrary {
+        let mut library = PrefabLibrary::new();
+        let mut door = Prefab::new("Door");
+        door.root = PrefabNode::new("Door").with_component("Door", serde_json::json!({ "locked": false, "key": 0 }));
+        library.insert(door);
+        
+        let mut house = Prefab::new("House");
+        house.root = PrefabNode::new("House")
+            .with_child(
+                PrefabNode::from_prefab("Front", "Door")
+                    .with_transform(Transform::from_position(Vec3::new(0.0, 0.0, 2.0))),
+            )
+            .with_child(PrefabNode::from_prefab("Back", "Door"));
+        library.insert(house);
+        library
+    }
+
+    #[test]
+    fn test_nested_prefab_and_variant() {
+        let mut library = test_library();
+        library.insert(
+            Prefab::variant("Vault", "House")
+                .with_override(PropertyOverride::component("House/Front", "Door", "locked", serde_json::json!(true))),
+        );
+        
+        let mut world = World::new();
+        world.component_registry_mut().register::<Door>("Door");
+        let mut sg = SceneGraph::new();
+        
+        let instance = library.instantiate("Vault", &mut world, &mut sg, None, Transform::IDENTITY).unwrap();
+        assert_eq!(instance.entities().len(), 3);
+        let front = instance.entity("House/Front").unwrap();
+        let back = instance.entity("House/Back").unwrap();
+        assert_eq!(world.get_component::<Door>(front), Some(&Door { locked: true, key: 0 }));
+        assert_eq!(world.get_component::<Door>(back), Some(&Door { locked: false, key: 0 }));
+        assert_eq!(sg.get_node(front).unwrap().local_transform.position, Vec3::new(0.0, 0.0, 2.0));
+        
+        library.insert(Prefab::variant("Loop", "Loop"));
+        assert!(matches!(library.resolve("Loop"), Err(PrefabError::Cycle(_))));
+        assert!(matches!(library.resolve("Shed"), Err(PrefabError::MissingPrefab(_))));
+        
+        // Only the library can expand variants and nested references
+        for name in ["Vault", "House"] {
+            let result = library.get(name).unwrap().instantiate(&mut world, &mut sg, None, Transform::IDENTITY);
+            assert!(matches!(result, Err(PrefabError::RequiresLibrary(n)) if n == name));
+        }
+        assert_eq!(world.entity_count(), 3);
+    }
+
+    #[test]
+    fn test_failed_sync_keeps_instance() {
+        let mut library = test_library();
+        let mut world = World::new();
+        world.component_registry_mut().register::<Door>("Door");
+        let mut sg = SceneGraph::new();
+        let mut instance = library.instantiate("House", &mut world, &mut sg, None, Transform::IDENTITY).unwrap();
+        let before = instance.entities().clone();
+        
+        // A node spawned ahead of the failing one is rolled back
+        library.get_mut("House").unwrap().root.children.insert(0, PrefabNode::new("Porch"));
+        instance.set_override(PropertyOverride::component("House/Front", "Door", "", serde_json::json!("broken")));
+        let result = library.sync_instance(&mut instance, &mut world, &mut sg);
+        assert!(matches!(result, Err(PrefabError::InvalidComponent { path, .. }) if path == "House/Front"));
+        assert_eq!(instance.entities(), &before);
+        assert!(before.values().all(|&entity| world.is_alive(entity)));
+        assert_eq!(world.entity_count(), 3);
+        assert_eq!(sg.node_count(), 3);
+        
+        // Unknown components are caught before anything changes
+        instance.revert("House/Front");
+        library.get_mut("Door").unwrap().root.components.push(ComponentData::new("Hinge", serde_json::json!(null)));
+        let result = library.sync_instance(&mut instance, &mut world, &mut sg);
+        assert!(matches!(result, Err(PrefabError::UnknownComponent { .. })));
+        assert_eq!(instance.entities(), &before);
+        assert_eq!(world.entity_count(), 3);
+    }
+
+    #[test]
+    fn test_instance_overrides_survive_source_edits() {
+        let mut library = test_library();
+        let mut world = World::new();
+        world.component_registry_mut().register::<Door>("Door");
+        let mut sg = SceneGraph::new();
+        
+        let mut instance = library.instantiate("House", &mut world, &mut sg, None, Transform::IDENTITY).unwrap();
+        instance.set_override(PropertyOverride::component("House/Front", "Door", "key", serde_json::json!(7)));
+        instance.set_override(PropertyOverride::component("House/Front", "Door", "key", serde_json::json!(9)));
+        assert_eq!(instance.overrides().len(), 1);
+        library.sync_instance(&mut instance, &mut world, &mut sg).unwrap();
+        
+        // Edit the source: lock every door and add a window
+        let door = library.get_mut("Door").unwrap();
+        door.root.components[0].data = serde_json::json!({ "locked": true, "key": 1 });
+        library.get_mut("House").unwrap().root.children.push(PrefabNode::new("Window"));
+        library.sync_instance(&mut instance, &mut world, &mut sg).unwrap();
+        
+        let front = instance.entity("House/Front").unwrap();
+        let back = instance.entity("House/Back").unwrap();
+        assert_eq!(world.get_component::<Door>(front), Some(&Door { locked: true, key: 9 }));
+        assert_eq!(world.get_component::<Door>(back), Some(&Door { locked: true, key: 1 }));
+        let window = instance.entity("House/Window").unwrap();
+        assert_eq!(sg.get_node(window).unwrap().parent, instance.entity("House"));
+        
+        // Removing a node from the source despawns it
+        library.get_mut("House").unwrap().root.children.pop();
+        library.sync_instance(&mut instance, &mut world, &mut sg).unwrap();
+        assert!(!world.is_alive(window));
+        
+        assert_eq!(instance.revert("House/Front"), 1);
+        library.sync_instance(&mut instance, &mut world, &mut sg).unwrap();
+        assert_eq!(world.get_component::<Door>(front), Some(&Door { locked: true, key: 1 }));
+    }
 }
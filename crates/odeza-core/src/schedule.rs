@@ -0,0 +1,156 @@
+//! Frame Phases
+//!
+//! Systems registered per phase and run by the engine each frame:
+//! - FixedUpdate: zero or more times per frame at the fixed timestep
+//! - Update: once per frame with the variable delta time
+//! - LateUpdate: once per frame after Update (cameras, follow logic)
+//! - Render: once per frame with the fixed-step interpolation alpha
+
+use crate::ecs::World;
+use crate::scene::SceneGraph;
+use crate::time::TimeManager;
+
+/// Frame phase a system runs in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Phase {
+    /// Fixed-step simulation (physics, deterministic gameplay)
+    FixedUpdate,
+    /// Variable-step gameplay
+    Update,
+    /// Runs after Update
+    LateUpdate,
+    /// Rendering preparation
+    Render,
+}
+
+impl Phase {
+    /// All phases in execution order
+    pub const ALL: [Phase; 4] = [Phase::FixedUpdate, Phase::Update, Phase::LateUpdate, Phase::Render];
+
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+/// State passed to systems while a phase runs
+pub struct PhaseContext<'a> {
+    /// Phase being run
+    pub phase: Phase,
+    /// ECS world
+    pub world: &'a mut World,
+    /// Scene graph
+    pub scene_graph: &'a mut SceneGraph,
+    /// Engine time
+    pub time: &'a TimeManager,
+    /// Step for this phase: the fixed timestep in FixedUpdate, the frame delta otherwise
+    pub delta_time: f64,
+    /// Fraction of a fixed step accumulated since the last FixedUpdate
+    ///
+    /// Use it in Render to interpolate between the previous and current
+    /// simulation state. Always 0 outside Render.
+    pub alpha: f64,
+}
+
+/// System run by a [`Schedule`]
+pub trait System: Send {
+    /// Run the system
+    fn run(&mut self, ctx: &mut PhaseContext<'_>);
+}
+
+impl<F> System for F
+where
+    F: FnMut(&mut PhaseContext<'_>) + Send,
+{
+    fn run(&mut self, ctx: &mut PhaseContext<'_>) {
+        self(ctx)
+    }
+}
+
+/// Identifier of a registered system
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SystemId(u64);
+
+/// Systems grouped by phase, run in registration order
+#[derive(Default)]
+pub struct Schedule {
+    phases: [Vec<(SystemId, Box<dyn System>)>; 4],
+    next_id: u64,
+}
+
+impl Schedule {
+    /// Create an empty schedule
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a system to run in a phase
+    pub fn add_system<S: System + 'static>(&mut self, phase: Phase, system: S) -> SystemId {
+        let id = SystemId(self.next_id);
+        self.next_id += 1;
+        self.phases[phase.index()].push((id, Box::new(system)));
+        id
+    }
+
+    /// Remove a system
+    pub fn remove_system(&mut self, id: SystemId) -> bool {
+        for systems in &mut self.phases {
+            if let Some(index) = systems.iter().position(|(system_id, _)| *system_id == id) {
+                systems.remove(index);
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Get the number of systems in a phase
+    pub fn system_count(&self, phase: Phase) -> usize {
+        self.phases[phase.index()].len()
+    }
+
+    /// Run every system registered for the context's phase
+    pub fn run(&mut self, ctx: &mut PhaseContext<'_>) {
+        for (_, system) in &mut self.phases[ctx.phase.index()] {
+            system.run(ctx);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_schedule_runs_phase_in_order() {
+        let mut schedule = Schedule::new();
+        let mut world = World::new();
+        let mut scene_graph = SceneGraph::new();
+        let time = TimeManager::new();
+
+        schedule.add_system(Phase::Update, |ctx: &mut PhaseContext<'_>| {
+            ctx.world.spawn();
+        });
+        let second = schedule.add_system(Phase::Update, |ctx: &mut PhaseContext<'_>| {
+            assert_eq!(ctx.world.entity_count(), 1);
+            ctx.world.spawn();
+        });
+        schedule.add_system(Phase::Render, |ctx: &mut PhaseContext<'_>| {
+            ctx.world.spawn();
+        });
+        assert_eq!(schedule.system_count(Phase::Update), 2);
+
+        let mut ctx = PhaseContext {
+            phase: Phase::Update,
+            world: &mut world,
+            scene_graph: &mut scene_graph,
+            time: &time,
+            delta_time: 1.0 / 60.0,
+            alpha: 0.0,
+        };
+        schedule.run(&mut ctx);
+        assert_eq!(ctx.world.entity_count(), 2);
+
+        assert!(schedule.remove_system(second));
+        assert!(!schedule.remove_system(second));
+        assert_eq!(schedule.system_count(Phase::Update), 1);
+    }
+}
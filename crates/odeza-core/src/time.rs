@@ -4,6 +4,7 @@
 //! - Variable render step
 //! - Fixed-step simulation
 //! - Deterministic-friendly hooks for networking/replays
+//! - Independent clocks (real, game, UI, custom) with their own scale and pause
 
 use std::time::{Duration, Instant};
 
@@ -92,16 +93,143 @@ impl FixedTimeStep {
     }
 }
 
+/// Identifier of a clock owned by a [`TimeManager`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ClockId(u32);
+
+impl ClockId {
+    /// Unscaled frame time; never paused
+    pub const REAL: ClockId = ClockId(0);
+    /// Gameplay time; drives fixed updates
+    pub const GAME: ClockId = ClockId(1);
+    /// UI time; keeps running while gameplay is paused
+    pub const UI: ClockId = ClockId(2);
+}
+
+/// Clock with its own time scale and pause state
+///
+/// A clock advances by its parent's delta times its scale, so pausing or
+/// slowing a clock also affects every clock derived from it.
+#[derive(Debug, Clone)]
+pub struct Clock {
+    name: String,
+    parent: Option<ClockId>,
+    scale: f64,
+    paused: bool,
+    delta: f64,
+    elapsed: f64,
+}
+
+impl Clock {
+    fn new(name: &str, parent: Option<ClockId>) -> Self {
+        Self {
+            name: name.to_string(),
+            parent,
+            scale: 1.0,
+            paused: false,
+            delta: 1.0 / 60.0,
+            elapsed: 0.0,
+        }
+    }
+
+    /// Get the clock name
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Get the clock this one is derived from
+    pub fn parent(&self) -> Option<ClockId> {
+        self.parent
+    }
+
+    /// Get the time scale
+    pub fn scale(&self) -> f64 {
+        self.scale
+    }
+
+    /// Set the time scale
+    pub fn set_scale(&mut self, scale: f64) {
+        self.scale = scale.max(0.0);
+    }
+
+    /// Check if the clock is paused
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Pause the clock
+    pub fn pause(&mut self) {
+        self.paused = true;
+    }
+
+    /// Resume the clock
+    pub fn resume(&mut self) {
+        self.paused = false;
+    }
+
+    /// Get the time this clock advanced in the last frame
+    pub fn delta(&self) -> f64 {
+        self.delta
+    }
+
+    /// Get the total time this clock has advanced
+    pub fn elapsed(&self) -> f64 {
+        self.elapsed
+    }
+}
+
+/// Per-entity time dilation
+///
+/// Entities scale the delta of their clock by this factor, e.g. to keep the
+/// player at normal speed while the game clock runs in slow motion.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TimeDilation {
+    /// Clock the entity follows
+    pub clock: ClockId,
+    /// Additional scale on top of the clock
+    pub scale: f64,
+}
+
+impl TimeDilation {
+    /// Dilate game time by the given factor
+    pub fn new(scale: f64) -> Self {
+        Self {
+            clock: ClockId::GAME,
+            scale,
+        }
+    }
+
+    /// Follow another clock
+    pub fn with_clock(mut self, clock: ClockId) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Get this frame's delta time for the entity
+    pub fn delta(&self, time: &TimeManager) -> f64 {
+        time.clock_delta(self.clock) * self.scale
+    }
+}
+
+impl Default for TimeDilation {
+    fn default() -> Self {
+        Self::new(1.0)
+    }
+}
+
 /// Time manager for tracking frame timing and fixed updates
+///
+/// Owns the set of clocks. The single-clock API (`delta_time`, `pause`,
+/// `set_time_scale`, ...) operates on [`ClockId::GAME`].
 pub struct TimeManager {
     /// Time when the engine started
     start_time: Instant,
     /// Time of the last frame
     last_frame_time: Instant,
-    /// Total elapsed time since start
-    total_time: f64,
-    /// Delta time of the last frame
-    delta_time: f64,
+    /// Delta time passed to the last update, before clamping
+    frame_delta: f64,
+    /// Clocks indexed by id; parents always precede their children
+    clocks: Vec<Clock>,
     /// Accumulated time for fixed updates
     fixed_accumulator: f64,
     /// Frame count
@@ -114,10 +242,6 @@ pub struct TimeManager {
     frame_times: [f64; 60],
     /// Current index in frame time history
     frame_time_index: usize,
-    /// Time scale (for slow motion or fast forward)
-    time_scale: f64,
-    /// Whether the game is paused
-    paused: bool,
 }
 
 impl TimeManager {
@@ -127,32 +251,41 @@ impl TimeManager {
         Self {
             start_time: now,
             last_frame_time: now,
-            total_time: 0.0,
-            delta_time: 1.0 / 60.0,
+            frame_delta: 0.0,
+            clocks: vec![
+                Clock::new("real", None),
+                Clock::new("game", Some(ClockId::REAL)),
+                Clock::new("ui", Some(ClockId::REAL)),
+            ],
             fixed_accumulator: 0.0,
             frame_count: 0,
             fixed_update_count: 0,
             fps: 60.0,
             frame_times: [1.0 / 60.0; 60],
             frame_time_index: 0,
-            time_scale: 1.0,
-            paused: false,
         }
     }
 
     /// Update the time manager for a new frame
     pub fn update(&mut self, delta_time: f64) {
         let now = Instant::now();
+        self.frame_delta = delta_time;
         
         // Clamp delta time to prevent extreme values
         let clamped_dt = delta_time.min(0.25).max(0.0001);
         
-        // Apply time scale
-        let scaled_dt = if self.paused { 0.0 } else { clamped_dt * self.time_scale };
+        // Advance clocks, applying scale and pause down each chain
+        for index in 0..self.clocks.len() {
+            let parent_delta = match self.clocks[index].parent {
+                Some(parent) => self.clocks[parent.0 as usize].delta,
+                None => clamped_dt,
+            };
+            let clock = &mut self.clocks[index];
+            clock.delta = if clock.paused { 0.0 } else { parent_delta * clock.scale };
+            clock.elapsed += clock.delta;
+        }
         
-        self.delta_time = scaled_dt;
-        self.total_time += scaled_dt;
-        self.fixed_accumulator += scaled_dt;
+        self.fixed_accumulator += self.game().delta;
         self.frame_count += 1;
         
         // Update FPS calculation
@@ -165,6 +298,46 @@ impl TimeManager {
         self.last_frame_time = now;
     }
 
+    /// Add a custom clock derived from `parent`
+    ///
+    /// Returns None if the parent does not exist.
+    pub fn add_clock(&mut self, name: &str, parent: ClockId) -> Option<ClockId> {
+        self.clocks.get(parent.0 as usize)?;
+        self.clocks.push(Clock::new(name, Some(parent)));
+        Some(ClockId(self.clocks.len() as u32 - 1))
+    }
+
+    /// Find a clock by name
+    pub fn find_clock(&self, name: &str) -> Option<ClockId> {
+        self.clocks
+            .iter()
+            .position(|clock| clock.name == name)
+            .map(|index| ClockId(index as u32))
+    }
+
+    /// Get a clock
+    pub fn clock(&self, id: ClockId) -> Option<&Clock> {
+        self.clocks.get(id.0 as usize)
+    }
+
+    /// Get mutable access to a clock
+    pub fn clock_mut(&mut self, id: ClockId) -> Option<&mut Clock> {
+        self.clocks.get_mut(id.0 as usize)
+    }
+
+    /// Get the delta time of a clock (0 if it does not exist)
+    pub fn clock_delta(&self, id: ClockId) -> f64 {
+        self.clock(id).map_or(0.0, Clock::delta)
+    }
+
+    fn game(&self) -> &Clock {
+        &self.clocks[ClockId::GAME.0 as usize]
+    }
+
+    fn game_mut(&mut self) -> &mut Clock {
+        &mut self.clocks[ClockId::GAME.0 as usize]
+    }
+
     /// Check if a fixed update should run
     pub fn should_run_fixed_update(&self, fixed_step: f64) -> bool {
         self.fixed_accumulator >= fixed_step
@@ -182,24 +355,43 @@ impl TimeManager {
         updates.min(config.max_updates)
     }
 
+    /// Drop whole fixed steps left in the accumulator
+    ///
+    /// Used when the per-frame update cap is hit so the simulation slows
+    /// down instead of falling further behind. Returns the discarded time.
+    pub fn discard_fixed_backlog(&mut self, fixed_step: f64) -> f64 {
+        let remainder = self.fixed_accumulator % fixed_step;
+        let discarded = self.fixed_accumulator - remainder;
+        self.fixed_accumulator = remainder;
+        discarded
+    }
+
     /// Get the interpolation factor for rendering between fixed updates
     pub fn fixed_interpolation(&self, fixed_step: f64) -> f64 {
         (self.fixed_accumulator / fixed_step).clamp(0.0, 1.0)
     }
 
-    /// Get the delta time for the current frame
+    /// Get the game delta time for the current frame
     pub fn delta_time(&self) -> DeltaTime {
-        DeltaTime(self.delta_time)
+        DeltaTime(self.game().delta)
     }
 
     /// Get the raw delta time (unscaled)
     pub fn raw_delta_time(&self) -> f64 {
-        self.delta_time / self.time_scale.max(0.0001)
+        self.clocks[ClockId::REAL.0 as usize].delta
+    }
+
+    /// Get the delta time passed to the last update, before clamping
+    ///
+    /// Feeding this back to [`TimeManager::update`] reproduces the frame
+    /// exactly, which is what replay recording stores.
+    pub fn frame_delta(&self) -> f64 {
+        self.frame_delta
     }
 
-    /// Get the total elapsed time
+    /// Get the total elapsed game time
     pub fn total_time(&self) -> f64 {
-        self.total_time
+        self.game().elapsed
     }
 
     /// Get the frame count
@@ -217,34 +409,35 @@ impl TimeManager {
         self.fps
     }
 
-    /// Get the time scale
+    /// Get the game time scale
     pub fn time_scale(&self) -> f64 {
-        self.time_scale
+        self.game().scale
     }
 
-    /// Set the time scale
+    /// Set the game time scale
     pub fn set_time_scale(&mut self, scale: f64) {
-        self.time_scale = scale.max(0.0);
+        self.game_mut().set_scale(scale);
     }
 
     /// Check if the game is paused
     pub fn is_paused(&self) -> bool {
-        self.paused
+        self.game().paused
     }
 
     /// Pause the game
     pub fn pause(&mut self) {
-        self.paused = true;
+        self.game_mut().pause();
     }
 
     /// Resume the game
     pub fn resume(&mut self) {
-        self.paused = false;
+        self.game_mut().resume();
     }
 
     /// Toggle pause state
     pub fn toggle_pause(&mut self) {
-        self.paused = !self.paused;
+        let clock = self.game_mut();
+        clock.paused = !clock.paused;
     }
 
     /// Get time since engine start (wall clock)
@@ -341,6 +534,7 @@ pub struct Timer {
     elapsed: Duration,
     repeating: bool,
     finished: bool,
+    clock: ClockId,
 }
 
 impl Timer {
@@ -351,6 +545,7 @@ impl Timer {
             elapsed: Duration::ZERO,
             repeating: false,
             finished: false,
+            clock: ClockId::GAME,
         }
     }
 
@@ -361,9 +556,26 @@ impl Timer {
             elapsed: Duration::ZERO,
             repeating: true,
             finished: false,
+            clock: ClockId::GAME,
         }
     }
 
+    /// Drive the timer from another clock (game time by default)
+    pub fn with_clock(mut self, clock: ClockId) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Get the clock driving the timer
+    pub fn clock(&self) -> ClockId {
+        self.clock
+    }
+
+    /// Advance the timer by its clock's delta for this frame
+    pub fn update(&mut self, time: &TimeManager) -> bool {
+        self.tick(Duration::from_secs_f64(time.clock_delta(self.clock)))
+    }
+
     /// Update the timer with delta time
     pub fn tick(&mut self, delta: Duration) -> bool {
         if self.finished && !self.repeating {
@@ -460,6 +672,51 @@ mod tests {
         assert!((tm.delta_time().as_secs() - 0.032).abs() < 0.001);
     }
 
+    #[test]
+    fn test_clocks_are_independent() {
+        let mut tm = TimeManager::new();
+        tm.pause();
+        tm.clock_mut(ClockId::UI).unwrap().set_scale(0.5);
+        
+        tm.update(0.016);
+        
+        // Pausing gameplay leaves UI and real time running
+        assert_eq!(tm.clock_delta(ClockId::GAME), 0.0);
+        assert!((tm.clock_delta(ClockId::UI) - 0.008).abs() < 1e-9);
+        assert!((tm.raw_delta_time() - 0.016).abs() < 1e-9);
+        assert_eq!(tm.fixed_updates_needed(&FixedTimeStep::from_step(0.001)), 0);
+    }
+
+    #[test]
+    fn test_custom_clock_follows_parent() {
+        let mut tm = TimeManager::new();
+        let cutscene = tm.add_clock("cutscene", ClockId::GAME).unwrap();
+        assert_eq!(tm.find_clock("cutscene"), Some(cutscene));
+        tm.clock_mut(cutscene).unwrap().set_scale(2.0);
+        tm.set_time_scale(0.25);
+        
+        tm.update(0.02);
+        assert!((tm.clock_delta(cutscene) - 0.01).abs() < 1e-9);
+        
+        tm.pause();
+        tm.update(0.02);
+        assert_eq!(tm.clock_delta(cutscene), 0.0);
+        assert!((tm.clock(cutscene).unwrap().elapsed() - 0.01).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_time_dilation() {
+        let mut tm = TimeManager::new();
+        tm.set_time_scale(0.2);
+        tm.update(0.02);
+        
+        // Bullet time: the world slows down, the player does not
+        let player = TimeDilation::new(5.0);
+        assert!((player.delta(&tm) - 0.02).abs() < 1e-9);
+        let hud = TimeDilation::default().with_clock(ClockId::UI);
+        assert!((hud.delta(&tm) - 0.02).abs() < 1e-9);
+    }
+
     #[test]
     fn test_fixed_updates_needed() {
         let mut tm = TimeManager::new();
@@ -495,6 +752,23 @@ mod tests {
         assert!(timer.is_finished());
     }
 
+    #[test]
+    fn test_timer_clock() {
+        let mut tm = TimeManager::new();
+        let mut game_timer = Timer::new(Duration::from_millis(30));
+        let mut ui_timer = Timer::new(Duration::from_millis(30)).with_clock(ClockId::UI);
+        
+        tm.pause();
+        for _ in 0..2 {
+            tm.update(0.02);
+            assert!(!game_timer.update(&tm));
+            ui_timer.update(&tm);
+        }
+        
+        assert!(ui_timer.is_finished());
+        assert!(!game_timer.is_finished());
+    }
+
     #[test]
     fn test_repeating_timer() {
         let mut timer = Timer::repeating(Duration::from_millis(100));
@@ -0,0 +1,459 @@
+//! Timer Wheel
+//!
+//! Scheduling service for large numbers of delayed and repeating actions
+//! (cooldowns, respawns, damage-over-time ticks). Timers are stored in a
+//! hierarchical timing wheel per clock, so advancing time costs O(1) per
+//! tick no matter how many timers are pending, and cancellation is O(1).
+
+use std::time::Duration;
+
+use crate::time::{ClockId, TimeManager};
+
+/// Bits of tick index resolved by each wheel level
+const LEVEL_BITS: u32 = 6;
+/// Slots per wheel level
+const SLOTS: usize = 1 << LEVEL_BITS;
+/// Number of wheel levels
+const LEVELS: usize = 4;
+/// Furthest deadline a wheel can hold directly; later timers are re-filed
+/// when they come within range
+const MAX_SPAN: u64 = 1 << (LEVEL_BITS * LEVELS as u32);
+
+/// Handle to a scheduled timer
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TimerHandle {
+    index: u32,
+    generation: u32,
+}
+
+/// What happens when a timer fires
+pub enum TimerAction<E> {
+    /// Run a callback
+    Callback(Box<dyn FnMut() + Send>),
+    /// Queue an event for [`TimerService::drain_events`]
+    Event(E),
+}
+
+impl<E> TimerAction<E> {
+    /// Create a callback action
+    pub fn callback<F: FnMut() + Send + 'static>(f: F) -> Self {
+        Self::Callback(Box::new(f))
+    }
+}
+
+impl<E> From<E> for TimerAction<E> {
+    fn from(event: E) -> Self {
+        Self::Event(event)
+    }
+}
+
+struct ScheduledTimer<E> {
+    /// Index of the wheel the timer lives in
+    wheel: usize,
+    deadline: u64,
+    interval: Option<u64>,
+    action: TimerAction<E>,
+}
+
+struct TimerSlot<E> {
+    generation: u32,
+    timer: Option<ScheduledTimer<E>>,
+}
+
+/// Timer reference stored in wheel buckets; stale once the slot's generation moves on
+#[derive(Clone, Copy)]
+struct TimerKey {
+    index: u32,
+    generation: u32,
+}
+
+/// Hierarchical timing wheel driven by one clock
+struct Wheel {
+    clock: ClockId,
+    /// Ticks processed so far
+    current: u64,
+    /// Clock time not yet converted into ticks
+    accumulator: f64,
+    levels: Vec<Vec<Vec<TimerKey>>>,
+}
+
+impl Wheel {
+    fn new(clock: ClockId) -> Self {
+        Self {
+            clock,
+            current: 0,
+            accumulator: 0.0,
+            levels: (0..LEVELS).map(|_| (0..SLOTS).map(|_| Vec::new()).collect()).collect(),
+        }
+    }
+
+    fn insert(&mut self, key: TimerKey, deadline: u64) {
+        let delta = deadline.saturating_sub(self.current).max(1);
+        // Out-of-range deadlines park in the top level and get re-filed on cascade
+        let target = if delta >= MAX_SPAN {
+            self.current + MAX_SPAN - 1
+        } else {
+            self.current + delta
+        };
+        let delta = target - self.current;
+
+        let mut level = 0;
+        while level + 1 < LEVELS && delta >= 1 << (LEVEL_BITS * (level as u32 + 1)) {
+            level += 1;
+        }
+        let slot = (target >> (LEVEL_BITS * level as u32)) as usize & (SLOTS - 1);
+        self.levels[level][slot].push(key);
+    }
+}
+
+/// Timer scheduling service
+///
+/// Time is quantized to a fixed tick (1 ms by default); timers fire on the
+/// first tick at or after their deadline. `E` is the event type queued by
+/// [`TimerAction::Event`] timers.
+pub struct TimerService<E = ()> {
+    /// Tick length
+    tick: Duration,
+    timers: Vec<TimerSlot<E>>,
+    free_list: Vec<u32>,
+    wheels: Vec<Wheel>,
+    events: Vec<E>,
+    pending: usize,
+}
+
+impl<E: Clone> TimerService<E> {
+    /// Create a service with a 1 ms tick
+    pub fn new() -> Self {
+        Self::with_tick(Duration::from_millis(1))
+    }
+
+    /// Create a service with the given tick length
+    pub fn with_tick(tick: Duration) -> Self {
+        assert!(!tick.is_zero(), "Timer tick must be non-zero");
+        Self {
+            tick,
+            timers: Vec::new(),
+            free_list: Vec::new(),
+            wheels: Vec::new(),
+            events: Vec::new(),
+            pending: 0,
+        }
+    }
+
+    /// Fire an action once after `delay` of `clock` time
+    pub fn schedule_after(
+        &mut self,
+        delay: Duration,
+        clock: ClockId,
+        action: impl Into<TimerAction<E>>,
+    ) -> TimerHandle {
+        self.schedule(delay, None, clock, action.into())
+    }
+
+    /// Fire an action every `interval` of `clock` time, starting one interval from now
+    pub fn schedule_repeating(
+        &mut self,
+        interval: Duration,
+        clock: ClockId,
+        action: impl Into<TimerAction<E>>,
+    ) -> TimerHandle {
+        let ticks = self.ticks(interval).max(1);
+        self.schedule(interval, Some(ticks), clock, action.into())
+    }
+
+    /// Cancel a timer
+    ///
+    /// Returns false if it already fired (one-shot) or was cancelled.
+    pub fn cancel(&mut self, handle: TimerHandle) -> bool {
+        let Some(slot) = self.timers.get_mut(handle.index as usize) else {
+            return false;
+        };
+        if slot.generation != handle.generation || slot.timer.is_none() {
+            return false;
+        }
+        // Bucket entries become stale and are skipped when reached
+        self.release(handle.index);
+        true
+    }
+
+    /// Check if a timer is still scheduled
+    pub fn is_pending(&self, handle: TimerHandle) -> bool {
+        self.timers
+            .get(handle.index as usize)
+            .is_some_and(|slot| slot.generation == handle.generation && slot.timer.is_some())
+    }
+
+    /// Get the time left until a timer fires
+    pub fn remaining(&self, handle: TimerHandle) -> Option<Duration> {
+        let slot = self.timers.get(handle.index as usize)?;
+        if slot.generation != handle.generation {
+            return None;
+        }
+        let timer = slot.timer.as_ref()?;
+        let ticks = timer.deadline.saturating_sub(self.wheels[timer.wheel].current);
+        Some(Duration::from_nanos((self.tick.as_nanos() as u64).saturating_mul(ticks)))
+    }
+
+    /// Get the number of scheduled timers
+    pub fn pending_count(&self) -> usize {
+        self.pending
+    }
+
+    /// Advance every wheel by its clock's delta for this frame
+    pub fn update(&mut self, time: &TimeManager) {
+        for index in 0..self.wheels.len() {
+            let delta = time.clock_delta(self.wheels[index].clock);
+            self.advance_wheel(index, delta);
+        }
+    }
+
+    /// Advance one clock's timers by `delta`, e.g. when not using a [`TimeManager`]
+    pub fn advance(&mut self, clock: ClockId, delta: Duration) {
+        if let Some(index) = self.wheels.iter().position(|w| w.clock == clock) {
+            self.advance_wheel(index, delta.as_secs_f64());
+        }
+    }
+
+    /// Take all events queued by fired timers
+    pub fn drain_events(&mut self) -> Vec<E> {
+        std::mem::take(&mut self.events)
+    }
+
+    fn ticks(&self, duration: Duration) -> u64 {
+        duration.as_nanos().div_ceil(self.tick.as_nanos()) as u64
+    }
+
+    fn schedule(
+        &mut self,
+        delay: Duration,
+        interval: Option<u64>,
+        clock: ClockId,
+        action: TimerAction<E>,
+    ) -> TimerHandle {
+        let wheel = match self.wheels.iter().position(|w| w.clock == clock) {
+            Some(index) => index,
+            None => {
+                self.wheels.push(Wheel::new(clock));
+                self.wheels.len() - 1
+            }
+        };
+        let deadline = self.wheels[wheel].current + self.ticks(delay).max(1);
+
+        let index = match self.free_list.pop() {
+            Some(index) => index,
+            None => {
+                self.timers.push(TimerSlot {
+                    generation: 0,
+                    timer: None,
+                });
+                self.timers.len() as u32 - 1
+            }
+        };
+        let slot = &mut self.timers[index as usize];
+        slot.timer = Some(ScheduledTimer {
+            wheel,
+            deadline,
+            interval,
+            action,
+        });
+        let key = TimerKey {
+            index,
+            generation: slot.generation,
+        };
+        self.wheels[wheel].insert(key, deadline);
+        self.pending += 1;
+
+        TimerHandle {
+            index,
+            generation: key.generation,
+        }
+    }
+
+    fn release(&mut self, index: u32) {
+        let slot = &mut self.timers[index as usize];
+        slot.timer = None;
+        slot.generation = slot.generation.wrapping_add(1);
+        self.free_list.push(index);
+        self.pending -= 1;
+    }
+
+    fn advance_wheel(&mut self, wheel: usize, delta: f64) {
+        let accumulator = self.wheels[wheel].accumulator + delta.max(0.0);
+        let tick = self.tick.as_secs_f64();
+        let ticks = (accumulator / tick).floor();
+        self.wheels[wheel].accumulator = accumulator - ticks * tick;
+        for _ in 0..ticks as u64 {
+            self.step(wheel);
+        }
+    }
+
+    /// Process a single tick
+    fn step(&mut self, wheel: usize) {
+        let current = self.wheels[wheel].current + 1;
+        self.wheels[wheel].current = current;
+
+        // Cascade higher levels whose slot boundary was crossed
+        for level in 1..LEVELS {
+            let shift = LEVEL_BITS * level as u32;
+            if current & ((1 << shift) - 1) != 0 {
+                break;
+            }
+            let slot = (current >> shift) as usize & (SLOTS - 1);
+            let bucket = std::mem::take(&mut self.wheels[wheel].levels[level][slot]);
+            for key in bucket {
+                match self.live_deadline(key) {
+                    Some(deadline) if deadline <= current => self.fire(wheel, key),
+                    Some(deadline) => self.wheels[wheel].insert(key, deadline),
+                    None => {}
+                }
+            }
+        }
+
+        let slot = current as usize & (SLOTS - 1);
+        let bucket = std::mem::take(&mut self.wheels[wheel].levels[0][slot]);
+        for key in bucket {
+            let Some(deadline) = self.live_deadline(key) else {
+                continue;
+            };
+            if deadline > current {
+                // Parked beyond the wheel's span; file it again
+                self.wheels[wheel].insert(key, deadline);
+                continue;
+            }
+            self.fire(wheel, key);
+        }
+    }
+
+    fn live_deadline(&self, key: TimerKey) -> Option<u64> {
+        let slot = &self.timers[key.index as usize];
+        if slot.generation != key.generation {
+            return None;
+        }
+        slot.timer.as_ref().map(|timer| timer.deadline)
+    }
+
+    fn fire(&mut self, wheel: usize, key: TimerKey) {
+        let timer = self.timers[key.index as usize]
+            .timer
+            .as_mut()
+            .expect("Fired timer slot is empty");
+        match &mut timer.action {
+            TimerAction::Callback(callback) => callback(),
+            TimerAction::Event(event) => self.events.push(event.clone()),
+        }
+
+        match timer.interval {
+            Some(interval) => {
+                timer.deadline += interval;
+                let deadline = timer.deadline;
+                self.wheels[wheel].insert(key, deadline);
+            }
+            None => self.release(key.index),
+        }
+    }
+}
+
+impl<E: Clone> Default for TimerService<E> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    fn ms(millis: u64) -> Duration {
+        Duration::from_millis(millis)
+    }
+
+    #[test]
+    fn test_one_shot_and_repeating() {
+        let mut timers = TimerService::new();
+        timers.schedule_after(ms(30), ClockId::GAME, "respawn");
+        let tick = timers.schedule_repeating(ms(10), ClockId::GAME, "dot");
+
+        timers.advance(ClockId::GAME, ms(25));
+        assert_eq!(timers.drain_events(), ["dot", "dot"]);
+
+        timers.advance(ClockId::GAME, ms(10));
+        assert_eq!(timers.drain_events(), ["respawn", "dot"]);
+        assert_eq!(timers.pending_count(), 1);
+
+        assert!(timers.cancel(tick));
+        assert!(!timers.cancel(tick));
+        timers.advance(ClockId::GAME, ms(100));
+        assert!(timers.drain_events().is_empty());
+        assert_eq!(timers.pending_count(), 0);
+    }
+
+    #[test]
+    fn test_callbacks_and_stale_handles() {
+        let fired = Arc::new(AtomicUsize::new(0));
+        let mut timers: TimerService = TimerService::new();
+
+        let counter = fired.clone();
+        let handle = timers.schedule_after(
+            ms(5),
+            ClockId::GAME,
+            TimerAction::callback(move || {
+                counter.fetch_add(1, Ordering::Relaxed);
+            }),
+        );
+        assert!(timers.is_pending(handle));
+        assert_eq!(timers.remaining(handle), Some(ms(5)));
+        timers.advance(ClockId::GAME, ms(5));
+        assert_eq!(fired.load(Ordering::Relaxed), 1);
+        assert!(!timers.is_pending(handle));
+
+        // The slot is reused, but the old handle must not cancel the new timer
+        let reused = timers.schedule_after(ms(5), ClockId::GAME, ());
+        assert!(!timers.cancel(handle));
+        assert!(timers.is_pending(reused));
+    }
+
+    #[test]
+    fn test_long_delays_cascade() {
+        let mut timers = TimerService::with_tick(ms(1));
+        let delays = [63, 64, 65, 4095, 4096, 300_000, MAX_SPAN + 1000];
+        for delay in delays {
+            timers.schedule_after(ms(delay), ClockId::GAME, delay);
+        }
+
+        let mut fired = Vec::new();
+        let mut elapsed = 0;
+        while fired.len() < delays.len() {
+            timers.advance(ClockId::GAME, ms(1));
+            elapsed += 1;
+            for delay in timers.drain_events() {
+                assert_eq!(delay, elapsed, "timer fired at the wrong tick");
+                fired.push(delay);
+            }
+        }
+        assert_eq!(fired, delays);
+    }
+
+    #[test]
+    fn test_clocks_drive_their_own_timers() {
+        let mut time = TimeManager::new();
+        let mut timers = TimerService::new();
+        timers.schedule_after(ms(50), ClockId::GAME, "game");
+        timers.schedule_after(ms(50), ClockId::UI, "ui");
+
+        time.pause();
+        for _ in 0..4 {
+            time.update(0.016);
+            timers.update(&time);
+        }
+        assert_eq!(timers.drain_events(), ["ui"]);
+
+        time.resume();
+        for _ in 0..4 {
+            time.update(0.016);
+            timers.update(&time);
+        }
+        assert_eq!(timers.drain_events(), ["game"]);
+    }
+}
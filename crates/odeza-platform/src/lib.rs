@@ -9,6 +9,7 @@
 //! - **Threading**: Threading primitives and atomics
 //! - **Timers**: High-resolution timers and telemetry
 //! - **Audio**: Platform-native audio backend selection
+//! - **Replay**: Deterministic input and timing recording
 //!
 //! ## Supported Platforms
 //! - Android (arm64)
@@ -22,12 +23,14 @@ pub mod filesystem;
 pub mod threading;
 pub mod timer;
 pub mod audio;
+pub mod replay;
 
 pub use window::{Window, WindowConfig, WindowEvent};
 pub use input::{InputState, InputEvent, GamepadState, TouchState};
 pub use filesystem::{FileSystem, FileHandle, FileMode};
 pub use threading::{Thread, ThreadPool};
-pub use timer::{HighResTimer, Timestamp};
+pub use timer::{HighResTimer, Timestamp, FrameTimer, FramePacer, FramePacing};
+pub use replay::{ReplayRecorder, ReplayPlayer, ReplayDivergence, ChecksumRegistry, StateHasher};
 
 use thiserror::Error;
 
@@ -48,6 +51,9 @@ pub enum PlatformError {
     
     #[error("Platform not supported: {0}")]
     NotSupported(String),
+    
+    #[error("Replay error: {0}")]
+    Replay(String),
 }
 
 /// Result type for platform operations
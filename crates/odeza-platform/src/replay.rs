@@ -0,0 +1,822 @@
+//! Input and Timing Replay
+//!
+//! Records the per-frame delta time passed to `Engine::update`, every
+//! [`InputEvent`] fed to [`InputState`], and the RNG seed into a compact
+//! binary file. Replaying the file feeds the same values back bit-for-bit,
+//! and periodic state checksums detect where a replay diverges.
+//!
+//! The delta can be taken from and fed back into a [`TimeManager`] with
+//! [`ReplayRecorder::end_frame_with_time`] and [`ReplayPlayer::advance_time`].
+//! When driving an `Engine`, which owns its time manager, pass the delta from
+//! [`ReplayPlayer::next_frame`] to `Engine::update` instead.
+
+use std::hash::Hasher;
+use std::path::Path;
+
+use odeza_core::TimeManager;
+
+use crate::input::{GamepadAxis, GamepadButton, InputEvent, InputState, KeyCode, MouseButton};
+use crate::{PlatformError, PlatformResult};
+
+/// File magic for replay files
+const REPLAY_MAGIC: &[u8; 4] = b"OREP";
+
+/// Current replay file version
+const REPLAY_VERSION: u32 = 1;
+
+/// FNV-1a hasher for state checksums
+///
+/// Stable across platforms and runs, unlike `DefaultHasher`.
+#[derive(Debug, Clone, Copy)]
+pub struct StateHasher {
+    state: u64,
+}
+
+impl StateHasher {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+
+    /// Create a new hasher
+    pub fn new() -> Self {
+        Self {
+            state: Self::OFFSET_BASIS,
+        }
+    }
+
+    /// Hash an f32 by its bit pattern
+    pub fn write_f32(&mut self, value: f32) {
+        self.write_u32(value.to_bits());
+    }
+
+    /// Hash an f64 by its bit pattern
+    pub fn write_f64(&mut self, value: f64) {
+        self.write_u64(value.to_bits());
+    }
+}
+
+impl Default for StateHasher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Hasher for StateHasher {
+    fn finish(&self) -> u64 {
+        self.state
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.state ^= byte as u64;
+            self.state = self.state.wrapping_mul(Self::PRIME);
+        }
+    }
+}
+
+/// Hash function for one piece of registered state
+type StateHashFn<S> = Box<dyn Fn(&S, &mut StateHasher) + Send + Sync>;
+
+/// Registry of state that contributes to replay checksums
+///
+/// `S` is whatever owns the simulated state, typically the engine or world.
+pub struct ChecksumRegistry<S> {
+    entries: Vec<(String, StateHashFn<S>)>,
+}
+
+impl<S> ChecksumRegistry<S> {
+    /// Create an empty registry
+    pub fn new() -> Self {
+        Self { entries: Vec::new() }
+    }
+
+    /// Register state to include in checksums
+    pub fn register<F>(&mut self, name: &str, hash: F)
+    where
+        F: Fn(&S, &mut StateHasher) + Send + Sync + 'static,
+    {
+        self.entries.push((name.to_string(), Box::new(hash)));
+    }
+
+    /// Get the names of registered state in hashing order
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.entries.iter().map(|(name, _)| name.as_str())
+    }
+
+    /// Compute a checksum of all registered state
+    pub fn checksum(&self, state: &S) -> u64 {
+        let mut hasher = StateHasher::new();
+        for (_, hash) in &self.entries {
+            hash(state, &mut hasher);
+        }
+        hasher.finish()
+    }
+}
+
+impl<S> Default for ChecksumRegistry<S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// One recorded frame
+#[derive(Debug, Clone)]
+struct ReplayFrame {
+    /// Delta time passed to the engine
+    delta_time: f64,
+    /// Input events handled before the update
+    events: Vec<InputEvent>,
+    /// State checksum after the update, on checksum frames
+    checksum: Option<u64>,
+}
+
+/// Records input and timing for later replay
+#[derive(Debug, Clone)]
+pub struct ReplayRecorder {
+    seed: u64,
+    checksum_interval: u32,
+    frames: Vec<ReplayFrame>,
+    pending_events: Vec<InputEvent>,
+}
+
+impl ReplayRecorder {
+    /// Create a recorder for a run using the given RNG seed
+    ///
+    /// A checksum is stored every `checksum_interval` frames (0 disables them).
+    pub fn new(seed: u64, checksum_interval: u32) -> Self {
+        Self {
+            seed,
+            checksum_interval,
+            frames: Vec::new(),
+            pending_events: Vec::new(),
+        }
+    }
+
+    /// Get the RNG seed
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Get the number of recorded frames
+    pub fn frame_count(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Record an input event and forward it to the input state
+    pub fn handle_event(&mut self, input: &mut InputState, event: &InputEvent) {
+        self.pending_events.push(event.clone());
+        input.handle_event(event);
+    }
+
+    /// Finish a frame after the engine has been updated with `delta_time`
+    ///
+    /// `checksum` is only called on checksum frames.
+    pub fn end_frame(&mut self, delta_time: f64, checksum: impl FnOnce() -> u64) {
+        let index = self.frames.len() as u64;
+        let checksum = is_checksum_frame(index, self.checksum_interval).then(checksum);
+        self.frames.push(ReplayFrame {
+            delta_time,
+            events: std::mem::take(&mut self.pending_events),
+            checksum,
+        });
+    }
+
+    /// Finish a frame using the delta `time` was last updated with
+    pub fn end_frame_with_time(&mut self, time: &TimeManager, checksum: impl FnOnce() -> u64) {
+        self.end_frame(time.frame_delta(), checksum);
+    }
+
+    /// Encode the recording
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut writer = ByteWriter::default();
+        writer.bytes(REPLAY_MAGIC);
+        writer.u32(REPLAY_VERSION);
+        writer.u64(self.seed);
+        writer.u32(self.checksum_interval);
+        writer.u32(self.frames.len() as u32);
+        for frame in &self.frames {
+            writer.u64(frame.delta_time.to_bits());
+            writer.u32(frame.events.len() as u32);
+            for event in &frame.events {
+                write_event(&mut writer, event);
+            }
+            match frame.checksum {
+                Some(checksum) => {
+                    writer.u8(1);
+                    writer.u64(checksum);
+                }
+                None => writer.u8(0),
+            }
+        }
+        writer.0
+    }
+
+    /// Write the recording to a file
+    pub fn save(&self, path: impl AsRef<Path>) -> PlatformResult<()> {
+        std::fs::write(path, self.to_bytes())?;
+        Ok(())
+    }
+}
+
+/// Replay checksum mismatch
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReplayDivergence {
+    /// Frame whose checksum did not match
+    pub frame: u64,
+    /// Checksum stored in the recording
+    pub expected: u64,
+    /// Checksum computed during replay
+    pub actual: u64,
+}
+
+impl std::fmt::Display for ReplayDivergence {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Replay diverged at frame {}: expected checksum {:#018x}, got {:#018x}",
+            self.frame, self.expected, self.actual
+        )
+    }
+}
+
+impl std::error::Error for ReplayDivergence {}
+
+/// Plays back a recording
+#[derive(Debug, Clone)]
+pub struct ReplayPlayer {
+    seed: u64,
+    checksum_interval: u32,
+    frames: Vec<ReplayFrame>,
+    /// Index of the next frame to play
+    cursor: usize,
+    first_divergence: Option<ReplayDivergence>,
+}
+
+impl ReplayPlayer {
+    /// Decode a recording
+    pub fn from_bytes(bytes: &[u8]) -> PlatformResult<Self> {
+        let mut reader = ByteReader { data: bytes, pos: 0 };
+        if reader.take(4)? != REPLAY_MAGIC {
+            return Err(PlatformError::Replay("Not a replay file".to_string()));
+        }
+        let version = reader.u32()?;
+        if version != REPLAY_VERSION {
+            return Err(PlatformError::Replay(format!(
+                "Unsupported replay version {}",
+                version
+            )));
+        }
+        let seed = reader.u64()?;
+        let checksum_interval = reader.u32()?;
+        let frame_count = reader.u32()?;
+
+        let mut frames = Vec::new();
+        for _ in 0..frame_count {
+            let delta_time = f64::from_bits(reader.u64()?);
+            let event_count = reader.u32()?;
+            let events = (0..event_count)
+                .map(|_| read_event(&mut reader))
+                .collect::<PlatformResult<Vec<_>>>()?;
+            let checksum = match reader.u8()? {
+                0 => None,
+                1 => Some(reader.u64()?),
+                tag => {
+                    return Err(PlatformError::Replay(format!(
+                        "Invalid checksum tag {}",
+                        tag
+                    )))
+                }
+            };
+            frames.push(ReplayFrame {
+                delta_time,
+                events,
+                checksum,
+            });
+        }
+        if reader.pos != bytes.len() {
+            return Err(PlatformError::Replay("Trailing data in replay".to_string()));
+        }
+
+        Ok(Self {
+            seed,
+            checksum_interval,
+            frames,
+            cursor: 0,
+            first_divergence: None,
+        })
+    }
+
+    /// Read a recording from a file
+    pub fn load(path: impl AsRef<Path>) -> PlatformResult<Self> {
+        Self::from_bytes(&std::fs::read(path)?)
+    }
+
+    /// Get the RNG seed the run was recorded with
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Get the checksum interval in frames
+    pub fn checksum_interval(&self) -> u32 {
+        self.checksum_interval
+    }
+
+    /// Get the number of recorded frames
+    pub fn frame_count(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Check if every frame has been played
+    pub fn is_finished(&self) -> bool {
+        self.cursor >= self.frames.len()
+    }
+
+    /// Feed the next frame's input events and return its delta time
+    ///
+    /// Pass the returned delta time to `Engine::update`. Returns None when
+    /// the recording is exhausted.
+    pub fn next_frame(&mut self, input: &mut InputState) -> Option<f64> {
+        let frame = self.frames.get(self.cursor)?;
+        for event in &frame.events {
+            input.handle_event(event);
+        }
+        self.cursor += 1;
+        Some(frame.delta_time)
+    }
+
+    /// Feed the next frame's input events and update `time` with its delta
+    ///
+    /// Returns false when the recording is exhausted.
+    pub fn advance_time(&mut self, input: &mut InputState, time: &mut TimeManager) -> bool {
+        match self.next_frame(input) {
+            Some(delta_time) => {
+                time.update(delta_time);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Compare state after the current frame against the recording
+    ///
+    /// `checksum` is only called on frames that have a stored checksum.
+    pub fn verify_frame(
+        &mut self,
+        checksum: impl FnOnce() -> u64,
+    ) -> Result<(), ReplayDivergence> {
+        let Some(index) = self.cursor.checked_sub(1) else {
+            return Ok(());
+        };
+        let Some(expected) = self.frames[index].checksum else {
+            return Ok(());
+        };
+        let actual = checksum();
+        if actual == expected {
+            return Ok(());
+        }
+
+        let divergence = ReplayDivergence {
+            frame: index as u64,
+            expected,
+            actual,
+        };
+        if self.first_divergence.is_none() {
+            log::error!("{}", divergence);
+            self.first_divergence = Some(divergence);
+        }
+        Err(divergence)
+    }
+
+    /// Get the first checksum mismatch seen during playback
+    ///
+    /// Checksums are sampled, so the state may have drifted up to one
+    /// interval earlier than the reported frame.
+    pub fn first_divergence(&self) -> Option<ReplayDivergence> {
+        self.first_divergence
+    }
+
+    /// Restart playback from the first frame
+    pub fn rewind(&mut self) {
+        self.cursor = 0;
+        self.first_divergence = None;
+    }
+}
+
+fn is_checksum_frame(index: u64, interval: u32) -> bool {
+    interval != 0 && (index + 1).is_multiple_of(interval as u64)
+}
+
+#[derive(Default)]
+struct ByteWriter(Vec<u8>);
+
+impl ByteWriter {
+    fn bytes(&mut self, bytes: &[u8]) {
+        self.0.extend_from_slice(bytes);
+    }
+
+    fn u8(&mut self, value: u8) {
+        self.0.push(value);
+    }
+
+    fn u32(&mut self, value: u32) {
+        self.bytes(&value.to_le_bytes());
+    }
+
+    fn u64(&mut self, value: u64) {
+        self.bytes(&value.to_le_bytes());
+    }
+
+    fn f32(&mut self, value: f32) {
+        self.u32(value.to_bits());
+    }
+}
+
+struct ByteReader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> ByteReader<'a> {
+    fn take(&mut self, len: usize) -> PlatformResult<&'a [u8]> {
+        let end = self
+            .pos
+            .checked_add(len)
+            .filter(|&end| end <= self.data.len())
+            .ok_or_else(|| PlatformError::Replay("Unexpected end of replay".to_string()))?;
+        let bytes = &self.data[self.pos..end];
+        self.pos = end;
+        Ok(bytes)
+    }
+
+    fn u8(&mut self) -> PlatformResult<u8> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u32(&mut self) -> PlatformResult<u32> {
+        Ok(u32::from_le_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    fn u64(&mut self) -> PlatformResult<u64> {
+        Ok(u64::from_le_bytes(self.take(8)?.try_into().unwrap()))
+    }
+
+    fn f32(&mut self) -> PlatformResult<f32> {
+        Ok(f32::from_bits(self.u32()?))
+    }
+
+    /// Read an index into a decoding table
+    fn code<T: Copy>(&mut self, table: &[T], what: &str) -> PlatformResult<T> {
+        let code = self.u8()?;
+        table
+            .get(code as usize)
+            .copied()
+            .ok_or_else(|| PlatformError::Replay(format!("Invalid {} code {}", what, code)))
+    }
+}
+
+/// Key codes in declaration order, for decoding
+const KEY_CODES: [KeyCode; 70] = {
+    use KeyCode::*;
+    [
+        A, B, C, D, E, F, G, H, I, J, K, L, M,
+        N, O, P, Q, R, S, T, U, V, W, X, Y, Z,
+        Key0, Key1, Key2, Key3, Key4, Key5, Key6, Key7, Key8, Key9,
+        F1, F2, F3, F4, F5, F6, F7, F8, F9, F10, F11, F12,
+        Space, Enter, Escape, Tab, Backspace, Delete, Insert,
+        Home, End, PageUp, PageDown,
+        Left, Right, Up, Down,
+        LeftShift, RightShift,
+        LeftControl, RightControl,
+        LeftAlt, RightAlt,
+        Unknown,
+    ]
+};
+
+const MOUSE_BUTTONS: [MouseButton; 5] = [
+    MouseButton::Left,
+    MouseButton::Right,
+    MouseButton::Middle,
+    MouseButton::Button4,
+    MouseButton::Button5,
+];
+
+const GAMEPAD_BUTTONS: [GamepadButton; 16] = {
+    use GamepadButton::*;
+    [
+        South, East, West, North,
+        LeftBumper, RightBumper, LeftTrigger, RightTrigger,
+        Select, Start, LeftStick, RightStick,
+        DPadUp, DPadDown, DPadLeft, DPadRight,
+    ]
+};
+
+const GAMEPAD_AXES: [GamepadAxis; 6] = [
+    GamepadAxis::LeftStickX,
+    GamepadAxis::LeftStickY,
+    GamepadAxis::RightStickX,
+    GamepadAxis::RightStickY,
+    GamepadAxis::LeftTrigger,
+    GamepadAxis::RightTrigger,
+];
+
+fn table_index<T: PartialEq>(table: &[T], value: &T) -> u8 {
+    table.iter().position(|v| v == value).expect("Value missing from replay table") as u8
+}
+
+fn write_event(writer: &mut ByteWriter, event: &InputEvent) {
+    match event {
+        InputEvent::KeyPressed(key) => {
+            writer.u8(0);
+            writer.u8(table_index(&KEY_CODES, key));
+        }
+        InputEvent::KeyReleased(key) => {
+            writer.u8(1);
+            writer.u8(table_index(&KEY_CODES, key));
+        }
+        InputEvent::MousePressed(button) => {
+            writer.u8(2);
+            writer.u8(table_index(&MOUSE_BUTTONS, button));
+        }
+        InputEvent::MouseReleased(button) => {
+            writer.u8(3);
+            writer.u8(table_index(&MOUSE_BUTTONS, button));
+        }
+        InputEvent::MouseMoved { x, y } => {
+            writer.u8(4);
+            writer.f32(*x);
+            writer.f32(*y);
+        }
+        InputEvent::MouseWheel { delta_x, delta_y } => {
+            writer.u8(5);
+            writer.f32(*delta_x);
+            writer.f32(*delta_y);
+        }
+        InputEvent::TouchStarted { id, x, y } => {
+            writer.u8(6);
+            writer.u64(*id);
+            writer.f32(*x);
+            writer.f32(*y);
+        }
+        InputEvent::TouchMoved { id, x, y } => {
+            writer.u8(7);
+            writer.u64(*id);
+            writer.f32(*x);
+            writer.f32(*y);
+        }
+        InputEvent::TouchEnded { id, x, y } => {
+            writer.u8(8);
+            writer.u64(*id);
+            writer.f32(*x);
+            writer.f32(*y);
+        }
+        InputEvent::TouchCancelled { id } => {
+            writer.u8(9);
+            writer.u64(*id);
+        }
+        InputEvent::GamepadConnected { id } => {
+            writer.u8(10);
+            writer.u32(*id);
+        }
+        InputEvent::GamepadDisconnected { id } => {
+            writer.u8(11);
+            writer.u32(*id);
+        }
+        InputEvent::GamepadButtonPressed { id, button } => {
+            writer.u8(12);
+            writer.u32(*id);
+            writer.u8(table_index(&GAMEPAD_BUTTONS, button));
+        }
+        InputEvent::GamepadButtonReleased { id, button } => {
+            writer.u8(13);
+            writer.u32(*id);
+            writer.u8(table_index(&GAMEPAD_BUTTONS, button));
+        }
+        InputEvent::GamepadAxisMoved { id, axis, value } => {
+            writer.u8(14);
+            writer.u32(*id);
+            writer.u8(table_index(&GAMEPAD_AXES, axis));
+            writer.f32(*value);
+        }
+    }
+}
+
+fn read_event(reader: &mut ByteReader<'_>) -> PlatformResult<InputEvent> {
+    let event = match reader.u8()? {
+        0 => InputEvent::KeyPressed(reader.code(&KEY_CODES, "key")?),
+        1 => InputEvent::KeyReleased(reader.code(&KEY_CODES, "key")?),
+        2 => InputEvent::MousePressed(reader.code(&MOUSE_BUTTONS, "mouse button")?),
+        3 => InputEvent::MouseReleased(reader.code(&MOUSE_BUTTONS, "mouse button")?),
+        4 => InputEvent::MouseMoved {
+            x: reader.f32()?,
+            y: reader.f32()?,
+        },
+        5 => InputEvent::MouseWheel {
+            delta_x: reader.f32()?,
+            delta_y: reader.f32()?,
+        },
+        6 => InputEvent::TouchStarted {
+            id: reader.u64()?,
+            x: reader.f32()?,
+            y: reader.f32()?,
+        },
+        7 => InputEvent::TouchMoved {
+            id: reader.u64()?,
+            x: reader.f32()?,
+            y: reader.f32()?,
+        },
+        8 => InputEvent::TouchEnded {
+            id: reader.u64()?,
+            x: reader.f32()?,
+            y: reader.f32()?,
+        },
+        9 => InputEvent::TouchCancelled { id: reader.u64()? },
+        10 => InputEvent::GamepadConnected { id: reader.u32()? },
+        11 => InputEvent::GamepadDisconnected { id: reader.u32()? },
+        12 => InputEvent::GamepadButtonPressed {
+            id: reader.u32()?,
+            button: reader.code(&GAMEPAD_BUTTONS, "gamepad button")?,
+        },
+        13 => InputEvent::GamepadButtonReleased {
+            id: reader.u32()?,
+            button: reader.code(&GAMEPAD_BUTTONS, "gamepad button")?,
+        },
+        14 => InputEvent::GamepadAxisMoved {
+            id: reader.u32()?,
+            axis: reader.code(&GAMEPAD_AXES, "gamepad axis")?,
+            value: reader.f32()?,
+        },
+        tag => return Err(PlatformError::Replay(format!("Invalid event tag {}", tag))),
+    };
+    Ok(event)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Toy simulation driven by input and delta time
+    #[derive(Default)]
+    struct Sim {
+        position: f32,
+        time: f64,
+    }
+
+    impl Sim {
+        fn update(&mut self, input: &InputState, delta_time: f64) {
+            self.time += delta_time;
+            if input.is_key_pressed(KeyCode::Right) {
+                self.position += (delta_time * 3.7) as f32;
+            }
+            self.position += input.mouse_delta().x * 0.01;
+        }
+    }
+
+    fn registry() -> ChecksumRegistry<Sim> {
+        let mut registry = ChecksumRegistry::new();
+        registry.register("position", |sim: &Sim, h: &mut StateHasher| h.write_f32(sim.position));
+        registry.register("time", |sim: &Sim, h: &mut StateHasher| h.write_f64(sim.time));
+        registry
+    }
+
+    fn record() -> (ReplayRecorder, Sim) {
+        let checksums = registry();
+        let mut recorder = ReplayRecorder::new(0xDEAD_BEEF, 4);
+        let mut input = InputState::new();
+        let mut sim = Sim::default();
+
+        for frame in 0..20 {
+            if frame == 2 {
+                recorder.handle_event(&mut input, &InputEvent::KeyPressed(KeyCode::Right));
+            }
+            if frame == 9 {
+                recorder.handle_event(&mut input, &InputEvent::KeyReleased(KeyCode::Right));
+                recorder.handle_event(&mut input, &InputEvent::MouseMoved { x: 13.25, y: 1.0 });
+            }
+            if frame == 12 {
+                recorder.handle_event(
+                    &mut input,
+                    &InputEvent::GamepadAxisMoved {
+                        id: 1,
+                        axis: GamepadAxis::RightTrigger,
+                        value: 0.5,
+                    },
+                );
+            }
+            let delta_time = 1.0 / 60.0 + frame as f64 * 1e-4;
+            sim.update(&input, delta_time);
+            recorder.end_frame(delta_time, || checksums.checksum(&sim));
+            input.end_frame();
+        }
+        (recorder, sim)
+    }
+
+    #[test]
+    fn test_replay_reproduces_run() {
+        let (recorder, recorded) = record();
+        let bytes = recorder.to_bytes();
+
+        let checksums = registry();
+        let mut player = ReplayPlayer::from_bytes(&bytes).unwrap();
+        assert_eq!(player.seed(), 0xDEAD_BEEF);
+        assert_eq!(player.frame_count(), 20);
+
+        let mut input = InputState::new();
+        let mut sim = Sim::default();
+        while let Some(delta_time) = player.next_frame(&mut input) {
+            sim.update(&input, delta_time);
+            player.verify_frame(|| checksums.checksum(&sim)).unwrap();
+            input.end_frame();
+        }
+        assert!(player.is_finished());
+        assert_eq!(sim.position.to_bits(), recorded.position.to_bits());
+        assert_eq!(sim.time.to_bits(), recorded.time.to_bits());
+    }
+
+    #[test]
+    fn test_replay_detects_first_divergence() {
+        let (recorder, _) = record();
+        let checksums = registry();
+        let mut player = ReplayPlayer::from_bytes(&recorder.to_bytes()).unwrap();
+
+        let mut input = InputState::new();
+        let mut sim = Sim::default();
+        let mut frame = 0;
+        while let Some(delta_time) = player.next_frame(&mut input) {
+            sim.update(&input, delta_time);
+            if frame == 5 {
+                sim.position += 1e-3;
+            }
+            let _ = player.verify_frame(|| checksums.checksum(&sim));
+            input.end_frame();
+            frame += 1;
+        }
+
+        // Frame 5 is not a checksum frame; the next one (7) catches it
+        let divergence = player.first_divergence().unwrap();
+        assert_eq!(divergence.frame, 7);
+        assert_ne!(divergence.expected, divergence.actual);
+    }
+
+    #[test]
+    fn test_replay_drives_time_manager() {
+        let mut recorder = ReplayRecorder::new(1, 0);
+        let mut time = TimeManager::new();
+        // The last delta is clamped by the time manager but recorded as passed
+        let deltas = [0.016, 0.0171, 0.5];
+        for delta_time in deltas {
+            time.update(delta_time);
+            recorder.end_frame_with_time(&time, || 0);
+        }
+
+        let mut player = ReplayPlayer::from_bytes(&recorder.to_bytes()).unwrap();
+        let mut input = InputState::new();
+        let mut replayed = TimeManager::new();
+        while player.advance_time(&mut input, &mut replayed) {}
+        assert_eq!(replayed.frame_count(), 3);
+        assert_eq!(replayed.frame_delta(), 0.5);
+        assert_eq!(replayed.total_time().to_bits(), time.total_time().to_bits());
+        assert_eq!(replayed.fps().to_bits(), time.fps().to_bits());
+    }
+
+    #[test]
+    fn test_replay_rejects_corrupt_data() {
+        let (recorder, _) = record();
+        let bytes = recorder.to_bytes();
+
+        assert!(ReplayPlayer::from_bytes(&bytes[..bytes.len() - 3]).is_err());
+        assert!(ReplayPlayer::from_bytes(b"NOPE").is_err());
+
+        let mut trailing = bytes.clone();
+        trailing.push(0);
+        assert!(ReplayPlayer::from_bytes(&trailing).is_err());
+    }
+
+    #[test]
+    fn test_key_code_table_order() {
+        for (index, key) in KEY_CODES.iter().enumerate() {
+            assert_eq!(*key as u32, index as u32);
+        }
+    }
+
+    #[test]
+    fn test_event_encoding_roundtrip() {
+        let events = vec![
+            InputEvent::KeyPressed(KeyCode::Unknown),
+            InputEvent::MouseReleased(MouseButton::Button5),
+            InputEvent::MouseWheel { delta_x: -0.0, delta_y: f32::MIN_POSITIVE },
+            InputEvent::TouchEnded { id: u64::MAX, x: 1.5, y: -2.5 },
+            InputEvent::TouchCancelled { id: 3 },
+            InputEvent::GamepadButtonReleased { id: 2, button: GamepadButton::DPadRight },
+        ];
+        let mut writer = ByteWriter::default();
+        for event in &events {
+            write_event(&mut writer, event);
+        }
+
+        let mut reader = ByteReader { data: &writer.0, pos: 0 };
+        for event in &events {
+            let decoded = read_event(&mut reader).unwrap();
+            assert_eq!(format!("{:?}", decoded), format!("{:?}", event));
+        }
+        assert_eq!(reader.pos, writer.0.len());
+    }
+}
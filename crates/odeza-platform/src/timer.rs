@@ -1,6 +1,6 @@
 //! High-Resolution Timers
 //!
-//! Cross-platform timing utilities for profiling and telemetry.
+//! Cross-platform timing utilities for profiling, telemetry and frame pacing.
 
 use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 use std::sync::atomic::{AtomicU64, Ordering};
@@ -256,6 +256,8 @@ pub struct FrameTimer {
     rolling: RollingTimer,
     frame_count: u64,
     total_time: f64,
+    jitter: RollingTimer,
+    missed_deadlines: u64,
 }
 
 impl FrameTimer {
@@ -266,6 +268,8 @@ impl FrameTimer {
             rolling: RollingTimer::new(60),
             frame_count: 0,
             total_time: 0.0,
+            jitter: RollingTimer::new(60),
+            missed_deadlines: 0,
         }
     }
 
@@ -320,6 +324,29 @@ impl FrameTimer {
             0.0
         }
     }
+
+    /// Record the outcome of a [`FramePacer::wait`]
+    pub fn record_pacing(&mut self, pacing: &FramePacing) {
+        self.jitter.record_duration(pacing.jitter);
+        if pacing.missed {
+            self.missed_deadlines += 1;
+        }
+    }
+
+    /// Get the number of frames that missed their pacing deadline
+    pub fn missed_deadlines(&self) -> u64 {
+        self.missed_deadlines
+    }
+
+    /// Get the average pacing jitter in milliseconds
+    pub fn average_jitter_ms(&self) -> f64 {
+        self.jitter.average() * 1000.0
+    }
+
+    /// Get the worst recent pacing jitter in milliseconds
+    pub fn max_jitter_ms(&self) -> f64 {
+        self.jitter.max() * 1000.0
+    }
 }
 
 impl Default for FrameTimer {
@@ -328,6 +355,151 @@ impl Default for FrameTimer {
     }
 }
 
+/// Outcome of waiting for a frame deadline
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FramePacing {
+    /// Time spent sleeping and spinning
+    pub waited: Duration,
+    /// Distance between the wake-up time and the deadline
+    pub jitter: Duration,
+    /// Whether the frame was already late when the wait started
+    pub missed: bool,
+}
+
+/// Frame rate limiter
+///
+/// Sleeps until shortly before each deadline, then spin-waits the rest of
+/// the way, since OS sleeps can overshoot by a millisecond or more. Deadlines
+/// advance by a fixed interval so pacing does not drift; after a missed
+/// deadline the schedule restarts from the current time instead of
+/// rushing to catch up.
+#[derive(Debug)]
+pub struct FramePacer {
+    target_fps: u32,
+    half_rate: bool,
+    spin_threshold: Duration,
+    next_deadline: Option<Instant>,
+}
+
+impl FramePacer {
+    /// Default time before a deadline at which sleeping switches to spinning
+    pub const DEFAULT_SPIN_THRESHOLD: Duration = Duration::from_micros(1500);
+
+    /// Create a pacer for the given frame rate (0 disables the limit)
+    pub fn new(target_fps: u32) -> Self {
+        Self {
+            target_fps,
+            half_rate: false,
+            spin_threshold: Self::DEFAULT_SPIN_THRESHOLD,
+            next_deadline: None,
+        }
+    }
+
+    /// Set how long before a deadline to stop sleeping and start spinning
+    ///
+    /// Larger values cost more CPU but reduce jitter on coarse OS timers.
+    pub fn with_spin_threshold(mut self, threshold: Duration) -> Self {
+        self.spin_threshold = threshold;
+        self
+    }
+
+    /// Get the target frame rate
+    pub fn target_fps(&self) -> u32 {
+        self.target_fps
+    }
+
+    /// Set the target frame rate (0 disables the limit)
+    pub fn set_target_fps(&mut self, target_fps: u32) {
+        self.target_fps = target_fps;
+        self.next_deadline = None;
+    }
+
+    /// Check if half-rate mode is enabled
+    pub fn is_half_rate(&self) -> bool {
+        self.half_rate
+    }
+
+    /// Present every other frame of the target rate (e.g. 30 fps on a 60 Hz target)
+    pub fn set_half_rate(&mut self, half_rate: bool) {
+        self.half_rate = half_rate;
+        self.next_deadline = None;
+    }
+
+    /// Get the effective frame rate
+    pub fn effective_fps(&self) -> f64 {
+        if self.half_rate {
+            self.target_fps as f64 / 2.0
+        } else {
+            self.target_fps as f64
+        }
+    }
+
+    /// Get the time between frames, or None when uncapped
+    pub fn frame_interval(&self) -> Option<Duration> {
+        if self.target_fps == 0 {
+            return None;
+        }
+        Some(Duration::from_secs_f64(1.0 / self.effective_fps()))
+    }
+
+    /// Restart the schedule from the next call to [`wait`](Self::wait)
+    pub fn reset(&mut self) {
+        self.next_deadline = None;
+    }
+
+    /// Block until the current frame's deadline
+    pub fn wait(&mut self) -> FramePacing {
+        let start = Instant::now();
+        let Some(interval) = self.frame_interval() else {
+            return FramePacing {
+                waited: Duration::ZERO,
+                jitter: Duration::ZERO,
+                missed: false,
+            };
+        };
+        let Some(deadline) = self.next_deadline else {
+            // First frame just establishes the schedule
+            self.next_deadline = Some(start + interval);
+            return FramePacing {
+                waited: Duration::ZERO,
+                jitter: Duration::ZERO,
+                missed: false,
+            };
+        };
+
+        if start >= deadline {
+            self.next_deadline = Some(start + interval);
+            return FramePacing {
+                waited: Duration::ZERO,
+                jitter: start - deadline,
+                missed: true,
+            };
+        }
+
+        let remaining = deadline - start;
+        if remaining > self.spin_threshold {
+            std::thread::sleep(remaining - self.spin_threshold);
+        }
+        while Instant::now() < deadline {
+            std::hint::spin_loop();
+        }
+
+        let woke = Instant::now();
+        self.next_deadline = Some(deadline + interval);
+        FramePacing {
+            waited: woke - start,
+            jitter: woke - deadline,
+            missed: false,
+        }
+    }
+}
+
+impl Default for FramePacer {
+    fn default() -> Self {
+        Self::new(60)
+    }
+}
+
 /// Performance counter for tracking operation counts and timing
 #[derive(Debug)]
 pub struct PerfCounter {
@@ -452,6 +624,55 @@ mod tests {
         assert!(timer.fps() > 0.0);
     }
 
+    #[test]
+    fn test_frame_pacer() {
+        let mut pacer = FramePacer::new(200);
+        let mut timer = FrameTimer::new();
+        let start = Instant::now();
+        
+        for frame in 0..6 {
+            let pacing = pacer.wait();
+            timer.record_pacing(&pacing);
+            // Waits that made their deadline block until it, however late they wake
+            if frame > 0 && !pacing.missed {
+                assert!(pacing.waited > Duration::ZERO);
+            }
+        }
+        
+        // First wait sets the schedule, the next five each take at least one
+        // interval; only lower bounds hold on a loaded machine
+        assert!(start.elapsed() >= Duration::from_millis(25));
+    }
+
+    #[test]
+    fn test_frame_pacer_half_rate() {
+        let mut pacer = FramePacer::new(120);
+        assert!((pacer.frame_interval().unwrap().as_secs_f64() - 1.0 / 120.0).abs() < 1e-9);
+        
+        pacer.set_half_rate(true);
+        assert_eq!(pacer.effective_fps(), 60.0);
+        assert!((pacer.frame_interval().unwrap().as_secs_f64() - 1.0 / 60.0).abs() < 1e-9);
+        
+        pacer.set_target_fps(0);
+        assert!(pacer.frame_interval().is_none());
+        assert_eq!(pacer.wait().waited, Duration::ZERO);
+    }
+
+    #[test]
+    fn test_frame_pacer_missed_deadline() {
+        let mut pacer = FramePacer::new(1000);
+        let mut timer = FrameTimer::new();
+        
+        pacer.wait();
+        std::thread::sleep(Duration::from_millis(5));
+        let pacing = pacer.wait();
+        timer.record_pacing(&pacing);
+        
+        assert!(pacing.missed);
+        assert!(pacing.jitter >= Duration::from_millis(3));
+        assert_eq!(timer.missed_deadlines(), 1);
+    }
+
     #[test]
     fn test_perf_counter() {
         let counter = PerfCounter::new("test");
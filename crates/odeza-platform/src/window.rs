@@ -5,8 +5,10 @@
 use std::sync::Arc;
 
 use glam::UVec2;
-use parking_lot::RwLock;
+use odeza_core::EngineConfig;
+use parking_lot::{Mutex, RwLock};
 
+use crate::timer::{FramePacer, FramePacing};
 use crate::PlatformResult;
 
 /// Window configuration
@@ -125,6 +127,8 @@ pub struct Window {
     events: Arc<RwLock<Vec<WindowEvent>>>,
     /// Whether the window should close
     should_close: Arc<RwLock<bool>>,
+    /// Frame rate limiter driven by `target_fps`
+    pacer: Mutex<FramePacer>,
 }
 
 impl Window {
@@ -140,6 +144,7 @@ impl Window {
         };
 
         Ok(Self {
+            pacer: Mutex::new(FramePacer::new(config.target_fps)),
             config,
             state: Arc::new(RwLock::new(state)),
             events: Arc::new(RwLock::new(Vec::new())),
@@ -237,6 +242,29 @@ impl Window {
     pub fn scale_factor(&self) -> f64 {
         self.state.read().scale_factor
     }
+
+    /// Also cap the frame rate at the engine's `target_fps`
+    ///
+    /// The lower non-zero target of the window and engine configs is used.
+    pub fn apply_engine_config(&self, engine: &EngineConfig) {
+        let target = match (self.config.target_fps, engine.target_fps) {
+            (0, fps) | (fps, 0) => fps,
+            (window, engine) => window.min(engine),
+        };
+        self.pacer.lock().set_target_fps(target);
+    }
+
+    /// Get the frame rate the window is paced at (0 when uncapped)
+    pub fn target_fps(&self) -> u32 {
+        self.pacer.lock().target_fps()
+    }
+
+    /// Block until the next frame is due
+    ///
+    /// Call once per iteration of the main loop, after presenting.
+    pub fn wait_for_frame(&self) -> FramePacing {
+        self.pacer.lock().wait()
+    }
 }
 
 #[cfg(test)]
@@ -251,6 +279,21 @@ mod tests {
         assert!(config.resizable);
     }
 
+    #[test]
+    fn test_window_frame_rate_from_configs() {
+        let window = Window::new(WindowConfig::default()).unwrap();
+        assert_eq!(window.target_fps(), 60);
+
+        let engine = EngineConfig { target_fps: 30, ..Default::default() };
+        window.apply_engine_config(&engine);
+        assert_eq!(window.target_fps(), 30);
+
+        let uncapped = Window::new(WindowConfig { target_fps: 0, ..Default::default() }).unwrap();
+        uncapped.apply_engine_config(&EngineConfig { target_fps: 0, ..Default::default() });
+        assert_eq!(uncapped.target_fps(), 0);
+        assert_eq!(uncapped.wait_for_frame().waited, std::time::Duration::ZERO);
+    }
+
     #[test]
     fn test_window_creation() {
         let window = Window::new(WindowConfig::default()).unwrap();
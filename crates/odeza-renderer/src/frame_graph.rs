@@ -1,9 +1,28 @@
 //! Frame Graph (Render Graph)
 //!
 //! Declarative rendering pipeline with automatic resource management.
+//! Passes are ordered by their resource dependencies, and passes whose
+//! results are never used are culled.
+
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
 
 use ahash::AHashMap;
 use smallvec::SmallVec;
+use thiserror::Error;
+
+/// Passes each pass must run after, by pass index
+type PassEdges = Vec<SmallVec<[usize; 4]>>;
+
+/// Frame graph compilation errors
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum FrameGraphError {
+    #[error("Pass '{pass}' uses unknown resource {resource:?}")]
+    UnknownResource { pass: String, resource: ResourceId },
+
+    #[error("Dependency cycle between passes: {}", .0.join(" -> "))]
+    Cycle(Vec<String>),
+}
 
 /// Unique identifier for a render resource
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -28,6 +47,10 @@ pub struct RenderResource {
     pub format: Option<TextureFormat>,
     /// Whether this resource is transient (can be aliased)
     pub transient: bool,
+    /// Whether this resource comes from outside the graph (e.g. the swapchain)
+    pub imported: bool,
+    /// Whether this resource is a result of the graph and must be produced
+    pub output: bool,
 }
 
 /// Resource types
@@ -82,6 +105,8 @@ pub struct RenderPass {
     pub async_compute: bool,
     /// Pass queue type
     pub queue: QueueType,
+    /// Whether the pass does work outside the graph (e.g. readback), so it's never culled
+    pub side_effects: bool,
 }
 
 /// Queue types
@@ -102,6 +127,7 @@ impl RenderPass {
             writes: SmallVec::new(),
             async_compute: false,
             queue: QueueType::Graphics,
+            side_effects: false,
         }
     }
 
@@ -129,6 +155,12 @@ impl RenderPass {
         self.queue = queue;
         self
     }
+
+    /// Mark as having side effects, so the pass is never culled
+    pub fn has_side_effects(mut self) -> Self {
+        self.side_effects = true;
+        self
+    }
 }
 
 /// Resource lifetime tracking
@@ -146,6 +178,8 @@ pub struct FrameGraph {
     passes: Vec<RenderPass>,
     /// Pass execution order (after compilation)
     execution_order: Vec<PassId>,
+    /// Passes removed because nothing uses their results (after compilation)
+    culled: Vec<PassId>,
     /// Resource lifetimes
     lifetimes: AHashMap<ResourceId, ResourceLifetime>,
     /// Resource aliasing (transient resources sharing memory)
@@ -165,6 +199,7 @@ impl FrameGraph {
             resources: AHashMap::new(),
             passes: Vec::new(),
             execution_order: Vec::new(),
+            culled: Vec::new(),
             lifetimes: AHashMap::new(),
             aliases: AHashMap::new(),
             next_resource_id: 0,
@@ -175,21 +210,7 @@ impl FrameGraph {
 
     /// Create a new transient resource
     pub fn create_transient(&mut self, name: impl Into<String>, resource_type: ResourceType) -> ResourceId {
-        let id = ResourceId(self.next_resource_id);
-        self.next_resource_id += 1;
-
-        let resource = RenderResource {
-            id,
-            name: name.into(),
-            resource_type,
-            size: None,
-            format: None,
-            transient: true,
-        };
-
-        self.resources.insert(id, resource);
-        self.compiled = false;
-        id
+        self.add_resource(name.into(), resource_type, None, None, true, false)
     }
 
     /// Create a new texture resource
@@ -200,17 +221,46 @@ impl FrameGraph {
         height: u32,
         format: TextureFormat,
         transient: bool,
+    ) -> ResourceId {
+        self.add_resource(name.into(), ResourceType::Texture2D, Some((width, height)), Some(format), transient, false)
+    }
+
+    /// Import an external resource (e.g. the swapchain image)
+    ///
+    /// Passes writing imported resources are never culled.
+    pub fn import_resource(&mut self, name: impl Into<String>, resource_type: ResourceType) -> ResourceId {
+        self.add_resource(name.into(), resource_type, None, None, false, true)
+    }
+
+    /// Mark a resource as a graph output, keeping the passes that produce it
+    pub fn mark_output(&mut self, resource: ResourceId) {
+        if let Some(resource) = self.resources.get_mut(&resource) {
+            resource.output = true;
+            self.compiled = false;
+        }
+    }
+
+    fn add_resource(
+        &mut self,
+        name: String,
+        resource_type: ResourceType,
+        size: Option<(u32, u32)>,
+        format: Option<TextureFormat>,
+        transient: bool,
+        imported: bool,
     ) -> ResourceId {
         let id = ResourceId(self.next_resource_id);
         self.next_resource_id += 1;
 
         let resource = RenderResource {
             id,
-            name: name.into(),
-            resource_type: ResourceType::Texture2D,
-            size: Some((width, height)),
-            format: Some(format),
+            name,
+            resource_type,
+            size,
+            format,
             transient,
+            imported,
+            output: false,
         };
 
         self.resources.insert(id, resource);
@@ -231,27 +281,140 @@ impl FrameGraph {
     }
 
     /// Compile the frame graph
-    pub fn compile(&mut self) {
+    ///
+    /// Culls passes whose results are unused, orders the remaining passes so
+    /// that every read happens after the writes it depends on, and fails if
+    /// the dependencies form a cycle.
+    pub fn compile(&mut self) -> Result<(), FrameGraphError> {
         if self.compiled {
-            return;
+            return Ok(());
         }
 
-        // Calculate resource lifetimes
-        self.calculate_lifetimes();
+        let (mut dependencies, write_after_read) = self.build_dependencies()?;
+        let live = self.cull_passes(&dependencies);
+
+        // Readers that survived culling must also run before the next write
+        // to what they read; culled readers impose no order
+        for (index, readers) in write_after_read.into_iter().enumerate() {
+            let list = &mut dependencies[index];
+            list.extend(readers.into_iter().filter(|&reader| live[reader]));
+            list.sort_unstable();
+            list.dedup();
+        }
 
         // Topological sort for execution order
-        self.topological_sort();
+        self.topological_sort(&dependencies, &live)?;
+
+        // Calculate resource lifetimes
+        self.calculate_lifetimes();
 
         // Calculate resource aliasing
         self.calculate_aliasing();
 
         self.compiled = true;
+        Ok(())
+    }
+
+    /// Find the passes each pass depends on, by index
+    ///
+    /// Writers of a resource run in declaration order. A pass that only reads
+    /// it sees the most recent write declared before it, or the last write if
+    /// it is declared before every writer. Returns the data dependencies and,
+    /// separately, the readers each writer must wait for so it doesn't
+    /// overwrite what an earlier reader still needs.
+    fn build_dependencies(&self) -> Result<(PassEdges, PassEdges), FrameGraphError> {
+        let mut writers: AHashMap<ResourceId, SmallVec<[usize; 4]>> = AHashMap::new();
+        let mut readers: AHashMap<ResourceId, SmallVec<[usize; 4]>> = AHashMap::new();
+
+        for (index, pass) in self.passes.iter().enumerate() {
+            for &resource in pass.reads.iter().chain(pass.writes.iter()) {
+                if !self.resources.contains_key(&resource) {
+                    return Err(FrameGraphError::UnknownResource {
+                        pass: pass.name.clone(),
+                        resource,
+                    });
+                }
+            }
+            for &resource in &pass.writes {
+                let list = writers.entry(resource).or_default();
+                if list.last() != Some(&index) {
+                    list.push(index);
+                }
+            }
+            for &resource in &pass.reads {
+                if !pass.writes.contains(&resource) {
+                    readers.entry(resource).or_default().push(index);
+                }
+            }
+        }
+
+        let mut dependencies: PassEdges = vec![SmallVec::new(); self.passes.len()];
+        let mut write_after_read: PassEdges = vec![SmallVec::new(); self.passes.len()];
+        for (resource, writer_list) in &writers {
+            for pair in writer_list.windows(2) {
+                dependencies[pair[1]].push(pair[0]);
+            }
+            for &reader in readers.get(resource).into_iter().flatten() {
+                // Writers are in declaration order
+                match writer_list.partition_point(|&writer| writer < reader) {
+                    0 => dependencies[reader].push(*writer_list.last().unwrap()),
+                    next => {
+                        dependencies[reader].push(writer_list[next - 1]);
+                        if let Some(&writer) = writer_list.get(next) {
+                            write_after_read[writer].push(reader);
+                        }
+                    }
+                }
+            }
+        }
+        for list in dependencies.iter_mut().chain(&mut write_after_read) {
+            list.sort_unstable();
+            list.dedup();
+        }
+        Ok((dependencies, write_after_read))
+    }
+
+    /// Mark passes that contribute to a side effect or an imported or output resource
+    fn cull_passes(&mut self, dependencies: &[SmallVec<[usize; 4]>]) -> Vec<bool> {
+        let mut live = vec![false; self.passes.len()];
+        let mut stack: Vec<usize> = Vec::new();
+
+        for (index, pass) in self.passes.iter().enumerate() {
+            let required = pass.side_effects
+                || pass.writes.iter().any(|id| {
+                    let resource = &self.resources[id];
+                    resource.imported || resource.output
+                });
+            if required {
+                live[index] = true;
+                stack.push(index);
+            }
+        }
+
+        while let Some(index) = stack.pop() {
+            for &dependency in &dependencies[index] {
+                if !live[dependency] {
+                    live[dependency] = true;
+                    stack.push(dependency);
+                }
+            }
+        }
+
+        self.culled = self
+            .passes
+            .iter()
+            .zip(&live)
+            .filter(|&(_, &is_live)| !is_live)
+            .map(|(pass, _)| pass.id)
+            .collect();
+        live
     }
 
     fn calculate_lifetimes(&mut self) {
         self.lifetimes.clear();
 
-        for pass in &self.passes {
+        for &pass_id in &self.execution_order {
+            let pass = self.passes.iter().find(|p| p.id == pass_id).unwrap();
             // Update first/last use for all resources
             for &resource_id in pass.reads.iter().chain(pass.writes.iter()) {
                 self.lifetimes
@@ -267,9 +430,65 @@ impl FrameGraph {
         }
     }
 
-    fn topological_sort(&mut self) {
-        // Simple implementation - in production would use proper graph algorithms
-        self.execution_order = self.passes.iter().map(|p| p.id).collect();
+    /// Order live passes after their dependencies (Kahn's algorithm)
+    ///
+    /// Ties are broken by declaration order so the result is stable.
+    fn topological_sort(&mut self, dependencies: &[SmallVec<[usize; 4]>], live: &[bool]) -> Result<(), FrameGraphError> {
+        self.execution_order.clear();
+
+        let mut remaining: Vec<usize> = dependencies.iter().map(|deps| deps.len()).collect();
+        let mut dependents: Vec<SmallVec<[usize; 4]>> = vec![SmallVec::new(); self.passes.len()];
+        for (index, deps) in dependencies.iter().enumerate() {
+            if live[index] {
+                for &dependency in deps {
+                    dependents[dependency].push(index);
+                }
+            }
+        }
+
+        let mut ready: BinaryHeap<Reverse<usize>> = (0..self.passes.len())
+            .filter(|&index| live[index] && remaining[index] == 0)
+            .map(Reverse)
+            .collect();
+        while let Some(Reverse(index)) = ready.pop() {
+            self.execution_order.push(self.passes[index].id);
+            for &dependent in &dependents[index] {
+                remaining[dependent] -= 1;
+                if remaining[dependent] == 0 {
+                    ready.push(Reverse(dependent));
+                }
+            }
+        }
+
+        let live_count = live.iter().filter(|&&is_live| is_live).count();
+        if self.execution_order.len() < live_count {
+            let cycle = self.find_cycle(dependencies, live, &remaining);
+            self.execution_order.clear();
+            return Err(FrameGraphError::Cycle(cycle));
+        }
+        Ok(())
+    }
+
+    /// Name the passes of a cycle among passes the sort couldn't schedule
+    fn find_cycle(&self, dependencies: &[SmallVec<[usize; 4]>], live: &[bool], remaining: &[usize]) -> Vec<String> {
+        let blocked = |index: usize| live[index] && remaining[index] > 0;
+        let mut path: Vec<usize> = Vec::new();
+        let mut current = (0..self.passes.len()).find(|&index| blocked(index)).unwrap();
+
+        // Every blocked pass waits on another blocked pass, so walking
+        // dependencies must revisit a pass
+        loop {
+            if let Some(start) = path.iter().position(|&index| index == current) {
+                // Report in execution direction, starting from the earliest declared pass
+                let mut cycle: Vec<usize> = path[start..].iter().rev().copied().collect();
+                let first = cycle.iter().enumerate().min_by_key(|&(_, &index)| index).unwrap().0;
+                cycle.rotate_left(first);
+                cycle.push(cycle[0]);
+                return cycle.into_iter().map(|index| self.passes[index].name.clone()).collect();
+            }
+            path.push(current);
+            current = dependencies[current].iter().copied().find(|&dependency| blocked(dependency)).unwrap();
+        }
     }
 
     fn calculate_aliasing(&mut self) {
@@ -282,6 +501,11 @@ impl FrameGraph {
         &self.execution_order
     }
 
+    /// Get the passes culled during compilation
+    pub fn culled_passes(&self) -> &[PassId] {
+        &self.culled
+    }
+
     /// Get a pass by ID
     pub fn get_pass(&self, id: PassId) -> Option<&RenderPass> {
         self.passes.iter().find(|p| p.id == id)
@@ -297,6 +521,7 @@ impl FrameGraph {
         self.resources.clear();
         self.passes.clear();
         self.execution_order.clear();
+        self.culled.clear();
         self.lifetimes.clear();
         self.aliases.clear();
         self.next_resource_id = 0;
@@ -353,6 +578,12 @@ impl<'a> PassBuilder<'a> {
         self
     }
 
+    /// Mark as having side effects, so the pass is never culled
+    pub fn has_side_effects(mut self) -> Self {
+        self.pass.side_effects = true;
+        self
+    }
+
     /// Build and add the pass to the graph
     pub fn build(self) -> PassId {
         let id = self.pass.id;
@@ -420,12 +651,84 @@ mod tests {
             .read(gbuffer)
             .write(lighting)
             .build();
+        graph.mark_output(lighting);
         
-        graph.compile();
+        graph.compile().unwrap();
         
         assert_eq!(graph.execution_order().len(), 2);
     }
 
+    #[test]
+    fn test_dependency_order() {
+        let mut graph = FrameGraph::new();
+        let backbuffer = graph.import_resource("Backbuffer", ResourceType::RenderTarget);
+        let gbuffer = graph.create_transient("GBuffer", ResourceType::RenderTarget);
+        let lighting = graph.create_transient("Lighting", ResourceType::RenderTarget);
+
+        // Declared consumer-first
+        let tonemap = graph.add_pass("Tonemap").read(lighting).write(backbuffer).build();
+        let light = graph.add_pass("Lighting").read(gbuffer).write(lighting).build();
+        let geometry = graph.add_pass("GBuffer").write(gbuffer).build();
+        let ui = graph.add_pass("UI").write(backbuffer).build();
+
+        graph.compile().unwrap();
+        assert_eq!(graph.execution_order(), &[geometry, light, tonemap, ui]);
+        assert!(graph.culled_passes().is_empty());
+    }
+
+    #[test]
+    fn test_read_between_writes() {
+        let mut graph = FrameGraph::new();
+        let history = graph.create_transient("History", ResourceType::RenderTarget);
+        let output = graph.import_resource("Output", ResourceType::RenderTarget);
+        let debug = graph.create_transient("Debug", ResourceType::RenderTarget);
+
+        // Readers see the first write and must finish before the second,
+        // except a culled reader, which mustn't block it
+        let write = graph.add_pass("Write").write(history).build();
+        let read = graph.add_pass("Read").read(history).write(output).build();
+        let unused = graph.add_pass("Unused Read").read(history).write(debug).build();
+        let overwrite = graph.add_pass("Overwrite").write(history).has_side_effects().build();
+
+        graph.compile().unwrap();
+        assert_eq!(graph.execution_order(), &[write, read, overwrite]);
+        assert_eq!(graph.culled_passes(), &[unused]);
+    }
+
+    #[test]
+    fn test_dead_pass_culling() {
+        let mut graph = FrameGraph::new();
+        let color = graph.create_transient("Color", ResourceType::RenderTarget);
+        let debug = graph.create_transient("Debug", ResourceType::RenderTarget);
+        let stats = graph.create_transient("Stats", ResourceType::Buffer);
+        graph.mark_output(color);
+
+        let main = graph.add_pass("Main").write(color).build();
+        let unused = graph.add_pass("Debug Overlay").write(debug).build();
+        let readback = graph.add_pass("Readback").write(stats).has_side_effects().build();
+
+        graph.compile().unwrap();
+        assert_eq!(graph.execution_order(), &[main, readback]);
+        assert_eq!(graph.culled_passes(), &[unused]);
+
+        graph.add_pass("Broken").read(ResourceId(99)).build();
+        assert!(matches!(graph.compile(), Err(FrameGraphError::UnknownResource { .. })));
+    }
+
+    #[test]
+    fn test_cycle_detection() {
+        let mut graph = FrameGraph::new();
+        let a = graph.create_transient("A", ResourceType::Buffer);
+        let b = graph.create_transient("B", ResourceType::Buffer);
+
+        graph.add_pass("First").read(b).write(a).has_side_effects().build();
+        graph.add_pass("Second").read(a).write(b).build();
+
+        let error = graph.compile().unwrap_err();
+        assert_eq!(error, FrameGraphError::Cycle(vec!["First".into(), "Second".into(), "First".into()]));
+        assert!(graph.execution_order().is_empty());
+    }
+
     #[test]
     fn test_async_compute_pass() {
         let mut graph = FrameGraph::new();
@@ -10,6 +10,7 @@
 //! - Volumetric lighting and fog
 //! - Temporal upscaling (TAA/TAAU)
 //! - Virtual texturing for 4K asset support
+//! - Hierarchical frustum culling and mesh LOD selection
 
 pub mod frame_graph;
 pub mod material;
@@ -20,11 +21,13 @@ pub mod volumetric;
 pub mod texture;
 pub mod mesh;
 pub mod pipeline;
+pub mod visibility;
 
-pub use frame_graph::{FrameGraph, RenderPass, RenderResource};
+pub use frame_graph::{FrameGraph, FrameGraphError, RenderPass, RenderResource};
 pub use material::{Material, MaterialInstance, PbrMaterial};
 pub use lighting::{Light, LightType, LightingSystem};
 pub use post::{PostProcess, TaaSettings, BloomSettings};
+pub use visibility::{VisibilitySystem, Renderable, View, ViewKind, VisibleItem, VisibleList};
 
 use odeza_core::PerformanceTier;
 use thiserror::Error;
@@ -0,0 +1,411 @@
+//! Visibility
+//!
+//! Per-view visibility pass run before rendering:
+//! - Hierarchical frustum culling using scene node bounds
+//! - Honors node `visible` and `enabled` flags for whole subtrees
+//! - Mesh LOD selection from projected screen size with hysteresis
+//! - Sorted visible lists per view (main camera, shadow cascades)
+
+use ahash::AHashMap;
+use glam::{Mat4, Vec3};
+use smallvec::SmallVec;
+
+use odeza_core::ecs::Entity;
+use odeza_core::math::{Aabb, Frustum};
+use odeza_core::scene::SceneGraph;
+
+use crate::mesh::Mesh;
+
+/// Default LOD hysteresis (fraction of the switch threshold)
+pub const DEFAULT_LOD_HYSTERESIS: f32 = 0.1;
+
+/// Renderable attached to a scene node
+#[derive(Debug, Clone)]
+pub struct Renderable {
+    /// Bounds in the node's local space
+    pub local_bounds: Aabb,
+    /// Screen size thresholds per LOD, finest first
+    ///
+    /// LOD `i` is used while the projected screen size is at least
+    /// `lod_screen_sizes[i]`; below the last threshold the last LOD is used.
+    pub lod_screen_sizes: SmallVec<[f32; 4]>,
+    /// Whether the renderable is drawn into shadow views
+    pub casts_shadows: bool,
+}
+
+impl Renderable {
+    /// Create a renderable without LODs
+    pub fn new(local_bounds: Aabb) -> Self {
+        Self {
+            local_bounds,
+            lod_screen_sizes: SmallVec::new(),
+            casts_shadows: true,
+        }
+    }
+
+    /// Create a renderable from a mesh's bounds and LODs
+    pub fn from_mesh(mesh: &Mesh) -> Self {
+        let mut lods: SmallVec<[_; 4]> = mesh.lods.iter().collect();
+        lods.sort_by_key(|lod| lod.level);
+        Self {
+            local_bounds: Aabb::new(Vec3::from(mesh.bounds_min), Vec3::from(mesh.bounds_max)),
+            lod_screen_sizes: lods.iter().map(|lod| lod.screen_size).collect(),
+            casts_shadows: true,
+        }
+    }
+}
+
+/// Kind of view being culled
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ViewKind {
+    /// Main camera
+    Main,
+    /// Shadow cascade by index
+    ShadowCascade(u32),
+    /// Other views (reflection probes, secondary cameras)
+    Custom(u32),
+}
+
+/// Camera view to cull against
+#[derive(Debug, Clone)]
+pub struct View {
+    /// View identity, also keys LOD hysteresis state
+    pub kind: ViewKind,
+    /// Camera position for distance sorting and screen size
+    pub position: Vec3,
+    /// Culling frustum
+    pub frustum: Frustum,
+    /// Vertical projection scale (`cot(fov / 2)` for perspective views)
+    pub projection_scale: f32,
+    /// Whether the projection is orthographic (screen size ignores distance)
+    pub orthographic: bool,
+    /// Multiplier applied to screen sizes before LOD selection
+    pub lod_bias: f32,
+}
+
+impl View {
+    /// Create a view from view and projection matrices
+    pub fn new(kind: ViewKind, view: Mat4, projection: Mat4) -> Self {
+        Self {
+            kind,
+            position: view.inverse().w_axis.truncate(),
+            frustum: Frustum::from_matrix(projection * view),
+            projection_scale: projection.y_axis.y,
+            orthographic: projection.w_axis.w == 1.0,
+            lod_bias: 1.0,
+        }
+    }
+
+    /// Check if this is a shadow view
+    pub fn is_shadow(&self) -> bool {
+        matches!(self.kind, ViewKind::ShadowCascade(_))
+    }
+
+    /// Projected height of bounds as a fraction of the screen height
+    pub fn screen_size(&self, bounds: &Aabb) -> f32 {
+        let radius = bounds.half_extents().length();
+        let size = if self.orthographic {
+            radius * self.projection_scale
+        } else {
+            let distance = (bounds.center() - self.position).length().max(f32::EPSILON);
+            radius * self.projection_scale / distance
+        };
+        size * self.lod_bias
+    }
+}
+
+/// Renderable that passed culling
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct VisibleItem {
+    /// Node entity
+    pub entity: Entity,
+    /// Selected LOD
+    pub lod: u32,
+    /// Distance from the view position to the bounds center
+    pub distance: f32,
+    /// Projected screen size
+    pub screen_size: f32,
+}
+
+/// Visible renderables for one view, sorted front to back
+#[derive(Debug, Clone)]
+pub struct VisibleList {
+    /// View the list was built for
+    pub view: ViewKind,
+    /// Visible items, nearest first
+    pub items: Vec<VisibleItem>,
+}
+
+/// Result of testing bounds against a frustum
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Containment {
+    Outside,
+    Intersecting,
+    Inside,
+}
+
+/// Classify bounds against every frustum plane
+fn classify(frustum: &Frustum, aabb: &Aabb) -> Containment {
+    let mut result = Containment::Inside;
+    for plane in &frustum.planes {
+        let positive = Vec3::select(plane.normal.cmpge(Vec3::ZERO), aabb.max, aabb.min);
+        let negative = Vec3::select(plane.normal.cmpge(Vec3::ZERO), aabb.min, aabb.max);
+        if plane.distance_to_point(positive) < 0.0 {
+            return Containment::Outside;
+        }
+        if plane.distance_to_point(negative) < 0.0 {
+            result = Containment::Intersecting;
+        }
+    }
+    result
+}
+
+/// Pick a LOD index for a screen size
+fn select_lod(screen_size: f32, thresholds: &[f32]) -> u32 {
+    thresholds
+        .iter()
+        .position(|&threshold| screen_size >= threshold)
+        .unwrap_or(thresholds.len().saturating_sub(1)) as u32
+}
+
+/// Visibility pass producing per-view visible lists
+pub struct VisibilitySystem {
+    renderables: AHashMap<Entity, Renderable>,
+    /// LOD hysteresis as a fraction of the switch threshold
+    lod_hysteresis: f32,
+    /// LODs selected in the previous pass
+    previous_lods: AHashMap<(ViewKind, Entity), u32>,
+    /// Scratch: world bounds of renderables
+    world_bounds: AHashMap<Entity, Aabb>,
+    /// Scratch: bounds of each subtree containing renderables
+    subtree_bounds: AHashMap<Entity, Aabb>,
+}
+
+impl VisibilitySystem {
+    /// Create an empty visibility system
+    pub fn new() -> Self {
+        Self {
+            renderables: AHashMap::new(),
+            lod_hysteresis: DEFAULT_LOD_HYSTERESIS,
+            previous_lods: AHashMap::new(),
+            world_bounds: AHashMap::new(),
+            subtree_bounds: AHashMap::new(),
+        }
+    }
+
+    /// Attach a renderable to a scene node
+    pub fn set_renderable(&mut self, entity: Entity, renderable: Renderable) {
+        self.renderables.insert(entity, renderable);
+    }
+
+    /// Detach a node's renderable
+    pub fn remove_renderable(&mut self, entity: Entity) -> Option<Renderable> {
+        self.renderables.remove(&entity)
+    }
+
+    /// Get a node's renderable
+    pub fn renderable(&self, entity: Entity) -> Option<&Renderable> {
+        self.renderables.get(&entity)
+    }
+
+    /// Get the number of renderables
+    pub fn renderable_count(&self) -> usize {
+        self.renderables.len()
+    }
+
+    /// Get the LOD hysteresis
+    pub fn lod_hysteresis(&self) -> f32 {
+        self.lod_hysteresis
+    }
+
+    /// Set the LOD hysteresis (fraction of the switch threshold, 0 to disable)
+    pub fn set_lod_hysteresis(&mut self, hysteresis: f32) {
+        self.lod_hysteresis = hysteresis.clamp(0.0, 0.9);
+    }
+
+    /// Cull the scene for each view
+    ///
+    /// Uses the world transforms from the last
+    /// [`SceneGraph::update_transforms`]. Returns one list per view, in order.
+    pub fn cull(&mut self, scene_graph: &SceneGraph, views: &[View]) -> Vec<VisibleList> {
+        self.compute_bounds(scene_graph);
+
+        let mut lists = Vec::with_capacity(views.len());
+        let mut lods = AHashMap::with_capacity(self.previous_lods.len());
+        let mut stack: Vec<(Entity, bool)> = Vec::new();
+
+        for view in views {
+            let mut items = Vec::new();
+            stack.extend(scene_graph.roots().iter().map(|&root| (root, false)));
+
+            while let Some((entity, parent_inside)) = stack.pop() {
+                let Some(bounds) = self.subtree_bounds.get(&entity) else {
+                    continue;
+                };
+                let Some(node) = scene_graph.get_node(entity) else {
+                    continue;
+                };
+                if !node.visible || !node.enabled {
+                    continue;
+                }
+
+                // Subtrees fully inside the frustum skip further plane tests
+                let inside = parent_inside || match classify(&view.frustum, bounds) {
+                    Containment::Outside => continue,
+                    Containment::Inside => true,
+                    Containment::Intersecting => false,
+                };
+
+                if let (Some(renderable), Some(world_bounds)) =
+                    (self.renderables.get(&entity), self.world_bounds.get(&entity))
+                {
+                    let wanted = !view.is_shadow() || renderable.casts_shadows;
+                    if wanted && (inside || view.frustum.intersects_aabb(world_bounds)) {
+                        let screen_size = view.screen_size(world_bounds);
+                        let previous = self.previous_lods.get(&(view.kind, entity)).copied();
+                        let lod = self.select_lod(screen_size, &renderable.lod_screen_sizes, previous);
+                        lods.insert((view.kind, entity), lod);
+                        items.push(VisibleItem {
+                            entity,
+                            lod,
+                            distance: (world_bounds.center() - view.position).length(),
+                            screen_size,
+                        });
+                    }
+                }
+
+                stack.extend(node.children.iter().map(|&child| (child, inside)));
+            }
+
+            items.sort_by(|a, b| a.distance.total_cmp(&b.distance));
+            lists.push(VisibleList { view: view.kind, items });
+        }
+
+        self.previous_lods = lods;
+        lists
+    }
+
+    /// Pick a LOD, keeping the previous one while within the hysteresis band
+    fn select_lod(&self, screen_size: f32, thresholds: &[f32], previous: Option<u32>) -> u32 {
+        let lod = select_lod(screen_size, thresholds);
+        match previous {
+            Some(previous) if self.lod_hysteresis > 0.0 => {
+                // Switching finer needs a larger size, switching coarser a smaller one
+                let finest = select_lod(screen_size / (1.0 + self.lod_hysteresis), thresholds);
+                let coarsest = select_lod(screen_size / (1.0 - self.lod_hysteresis), thresholds);
+                previous.clamp(coarsest.min(finest), finest.max(coarsest))
+            }
+            _ => lod,
+        }
+    }
+
+    /// Compute world bounds of renderables and of every subtree containing one
+    fn compute_bounds(&mut self, scene_graph: &SceneGraph) {
+        self.world_bounds.clear();
+        self.subtree_bounds.clear();
+
+        for (&entity, renderable) in &self.renderables {
+            if let Some(node) = scene_graph.get_node(entity) {
+                let bounds = renderable.local_bounds.transform(node.world_matrix());
+                self.world_bounds.insert(entity, bounds);
+
+                // Grow every ancestor's subtree bounds
+                self.subtree_bounds.insert(entity, self.subtree_bounds.get(&entity).map_or(bounds, |b| b.merge(&bounds)));
+                for ancestor in scene_graph.ancestors(entity) {
+                    let merged = self.subtree_bounds.get(&ancestor).map_or(bounds, |b| b.merge(&bounds));
+                    self.subtree_bounds.insert(ancestor, merged);
+                }
+            }
+        }
+    }
+}
+
+impl Default for VisibilitySystem {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use glam::Quat;
+    use odeza_core::scene::Transform;
+
+    fn main_view() -> View {
+        let view = Mat4::look_at_rh(Vec3::ZERO, Vec3::NEG_Z, Vec3::Y);
+        let projection = Mat4::perspective_rh(std::f32::consts::FRAC_PI_2, 1.0, 0.1, 100.0);
+        View::new(ViewKind::Main, view, projection)
+    }
+
+    fn unit_bounds() -> Aabb {
+        Aabb::from_center_half_extents(Vec3::ZERO, Vec3::splat(0.5))
+    }
+
+    fn spawn(sg: &mut SceneGraph, index: u32, position: Vec3, parent: Option<Entity>) -> Entity {
+        let entity = Entity::new(index, 0);
+        sg.add_node(entity, "Node").local_transform = Transform::new(position, Quat::IDENTITY, Vec3::ONE);
+        sg.set_parent(entity, parent);
+        entity
+    }
+
+    #[test]
+    fn test_cull_honors_frustum_and_flags() {
+        let mut sg = SceneGraph::new();
+        let group = spawn(&mut sg, 0, Vec3::new(0.0, 0.0, -10.0), None);
+        let near = spawn(&mut sg, 1, Vec3::ZERO, Some(group));
+        let far = spawn(&mut sg, 2, Vec3::new(0.0, 0.0, -20.0), Some(group));
+        let behind = spawn(&mut sg, 3, Vec3::new(0.0, 0.0, 10.0), None);
+        let hidden = spawn(&mut sg, 4, Vec3::new(0.0, 0.0, -5.0), None);
+        sg.get_node_mut(hidden).unwrap().visible = false;
+        sg.update_transforms();
+
+        let mut visibility = VisibilitySystem::new();
+        for entity in [near, far, behind, hidden] {
+            let mut renderable = Renderable::new(unit_bounds());
+            renderable.casts_shadows = entity != far;
+            visibility.set_renderable(entity, renderable);
+        }
+
+        let shadow = View { kind: ViewKind::ShadowCascade(0), ..main_view() };
+        let lists = visibility.cull(&sg, &[main_view(), shadow]);
+        let main: Vec<_> = lists[0].items.iter().map(|item| item.entity).collect();
+        assert_eq!(main, [near, far]);
+        assert!(lists[0].items[0].distance < lists[0].items[1].distance);
+
+        let shadows: Vec<_> = lists[1].items.iter().map(|item| item.entity).collect();
+        assert_eq!(shadows, [near]);
+
+        // Disabling a parent hides its subtree
+        sg.get_node_mut(group).unwrap().enabled = false;
+        assert!(visibility.cull(&sg, &[main_view()])[0].items.is_empty());
+    }
+
+    #[test]
+    fn test_lod_hysteresis() {
+        let mut sg = SceneGraph::new();
+        let entity = spawn(&mut sg, 0, Vec3::new(0.0, 0.0, -5.0), None);
+        sg.update_transforms();
+
+        let mut visibility = VisibilitySystem::new();
+        let mut renderable = Renderable::new(unit_bounds());
+        renderable.lod_screen_sizes = SmallVec::from_slice(&[0.1, 0.05, 0.0]);
+        visibility.set_renderable(entity, renderable);
+
+        let mut lod_at = |visibility: &mut VisibilitySystem, distance: f32| {
+            sg.get_node_mut(entity).unwrap().local_transform.position.z = -distance;
+            sg.update_transforms();
+            visibility.cull(&sg, &[main_view()])[0].items[0].lod
+        };
+
+        // Screen size is about 0.866 / distance; LOD 0 ends near distance 8.66
+        assert_eq!(lod_at(&mut visibility, 5.0), 0);
+        assert_eq!(lod_at(&mut visibility, 9.0), 0);
+        assert_eq!(lod_at(&mut visibility, 10.0), 1);
+        assert_eq!(lod_at(&mut visibility, 8.5), 1);
+        assert_eq!(lod_at(&mut visibility, 7.5), 0);
+
+        visibility.set_lod_hysteresis(0.0);
+        assert_eq!(lod_at(&mut visibility, 9.0), 1);
+    }
+}